@@ -0,0 +1,176 @@
+// File: crates/adapters/cli/src/archive.rs
+// Purpose: Attachment archiving for feeds with settings.archive: download each entry's enclosures and
+//   content images into the blob store and keep an archived copy of the content pointing at them.
+// Inputs/Outputs: FetchCtx + Feed + the feed's entries; writes blobs and one ArchivedEntry per entry;
+//   returns counters.
+// Invariants: Each entry is archived once (skipped attachments are recorded, not retried). The MIME
+//   allowlist and the per-feed quota (declared, then read size) are checked before anything reaches
+//   the blob store; identical bytes count once toward the quota. Downloads go through the run's
+//   fetcher, rate limiter and the feed's size limits without feed credentials; with --warc each
+//   attachment links the WARC response record it was stored from.
+
+use super::fetch_feed::{FetchCtx, FetchRepo};
+use rssify_core::{
+    blob_path, ArchiveRepo, ArchivedEntry, Attachment, AttachmentKind, BlobRepo, ContentBlob,
+    ContentKind, Enclosure, Entry, Feed, FetchRequest, Fetcher, RepoError, SkippedAttachment,
+};
+use rssify_fetch::{image_refs, rewrite_image_refs};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveStats {
+    pub entries: u32,
+    pub attachments: u32,
+    /// New bytes charged to the feed's quota in this pass.
+    pub bytes: u64,
+    pub wait_ms: u64,
+}
+
+/// Quota bookkeeping for one feed: bytes used and the blobs already charged.
+struct Quota {
+    used: u64,
+    limit: Option<u64>,
+    charged: BTreeSet<String>,
+}
+
+impl Quota {
+    fn remaining(&self) -> u64 {
+        self.limit.map_or(u64::MAX, |l| l.saturating_sub(self.used))
+    }
+}
+
+/// Archive every entry of `feed` (as `entries` holds it) that has no archived copy yet.
+pub fn archive_entries<R, F>(ctx: &FetchCtx<'_, R, F>, feed: &Feed, entries: &[Entry]) -> Result<ArchiveStats, RepoError>
+where
+    R: FetchRepo,
+    F: Fetcher,
+{
+    let mut stats = ArchiveStats::default();
+    let Some(settings) = &feed.settings.archive else {
+        return Ok(stats);
+    };
+    let mut quota = Quota { used: 0, limit: settings.quota_bytes, charged: BTreeSet::new() };
+    for a in ArchiveRepo::archives_by_feed(ctx.repo, ctx.tx, &feed.id)? {
+        for att in a.attachments {
+            if quota.charged.insert(att.sha256) {
+                quota.used += att.size;
+            }
+        }
+    }
+    let headers: Vec<(String, String)> =
+        feed.settings.request.user_agent.iter().map(|ua| ("User-Agent".to_string(), ua.clone())).collect();
+    for entry in entries {
+        if ArchiveRepo::archive(ctx.repo, ctx.tx, &entry.id)?.is_some() {
+            continue;
+        }
+        let html = entry
+            .content
+            .as_ref()
+            .filter(|c| c.kind == ContentKind::Html)
+            .map(|c| String::from_utf8_lossy(&c.bytes).into_owned());
+        let mut refs: Vec<(String, AttachmentKind, Option<&Enclosure>)> =
+            entry.enclosures.iter().map(|e| (e.url.clone(), AttachmentKind::Enclosure, Some(e))).collect();
+        for url in html.as_deref().map(|h| image_refs(h, entry.url.as_deref())).unwrap_or_default() {
+            refs.push((url, AttachmentKind::Image, None));
+        }
+        let mut archived = ArchivedEntry {
+            entry: entry.id.clone(),
+            feed: feed.id.clone(),
+            archived_ts: ctx.now,
+            content: None,
+            attachments: Vec::new(),
+            skipped: Vec::new(),
+        };
+        let mut local = BTreeMap::new();
+        for (url, kind, declared) in refs {
+            let req = FetchRequest { url: url.clone(), headers: headers.clone(), ..FetchRequest::default() };
+            match download(ctx, feed, &mut quota, &req, kind, declared, &mut stats) {
+                Ok(attachment) => {
+                    local.insert(url, blob_path(&attachment.sha256));
+                    archived.attachments.push(attachment);
+                    stats.attachments += 1;
+                }
+                Err(Skip::Repo(e)) => return Err(e),
+                Err(Skip::Reason(reason)) => {
+                    ctx.log.info("archive_skip", &[("entry_id", entry.id.as_str()), ("url", url.as_str()), ("reason", reason.as_str())]);
+                    archived.skipped.push(SkippedAttachment { url, reason });
+                }
+            }
+        }
+        archived.content = html.map(|h| ContentBlob {
+            kind: ContentKind::Html,
+            bytes: rewrite_image_refs(&h, entry.url.as_deref(), &local).into_bytes(),
+        });
+        ArchiveRepo::put_archive(ctx.repo, ctx.tx, &archived)?;
+        stats.entries += 1;
+    }
+    Ok(stats)
+}
+
+enum Skip {
+    Reason(String),
+    Repo(RepoError),
+}
+
+/// Fetch one attachment, vet MIME and quota (declared size before fetching, read size after), and
+/// only then store it.
+fn download<R, F>(
+    ctx: &FetchCtx<'_, R, F>,
+    feed: &Feed,
+    quota: &mut Quota,
+    req: &FetchRequest,
+    kind: AttachmentKind,
+    declared: Option<&Enclosure>,
+    stats: &mut ArchiveStats,
+) -> Result<Attachment, Skip>
+where
+    R: FetchRepo,
+    F: Fetcher,
+{
+    let settings = feed.settings.archive.clone().unwrap_or_default();
+    let declared_mime = declared.and_then(|e| e.mime.as_deref());
+    if let Some(m) = declared_mime.filter(|m| !settings.allows(m)) {
+        return Err(Skip::Reason(format!("mime not allowed: {m}")));
+    }
+    if declared.and_then(|e| e.length).is_some_and(|len| len > quota.remaining()) {
+        return Err(Skip::Reason("quota exceeded".to_string()));
+    }
+    stats.wait_ms += ctx.limiter.acquire(&req.url);
+    ctx.warc_responses();
+    let resp = match ctx.fetch(feed, req) {
+        Ok(resp) if (200..300).contains(&resp.status) => resp,
+        Ok(resp) => return Err(Skip::Reason(format!("http {}", resp.status))),
+        Err(outcome) => return Err(Skip::Reason(format!("{outcome:?}"))),
+    };
+    let mime = resp
+        .header("content-type")
+        .or(declared_mime)
+        .map_or_else(|| "application/octet-stream".to_string(), |m| m.split(';').next().unwrap_or("").trim().to_ascii_lowercase());
+    if !settings.allows(&mime) {
+        return Err(Skip::Reason(format!("mime not allowed: {mime}")));
+    }
+    // Vet the read size before storing: bytes over quota never reach the blob store.
+    let size = resp.body.len() as u64;
+    let charge = !quota.charged.contains(&sha256_hex(&resp.body));
+    if charge && size > quota.remaining() {
+        return Err(Skip::Reason("quota exceeded".to_string()));
+    }
+    let sha256 = BlobRepo::put_blob(ctx.repo, ctx.tx, &resp.body).map_err(Skip::Repo)?;
+    if charge {
+        quota.charged.insert(sha256.clone());
+        quota.used += size;
+        stats.bytes += size;
+    }
+    let warc_record = ctx.warc_responses().pop();
+    Ok(Attachment { url: req.url.clone(), kind, mime, size, sha256, warc_record })
+}
+
+/// Lowercase hex sha256, the key every BlobRepo stores `bytes` under.
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().fold(String::with_capacity(64), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
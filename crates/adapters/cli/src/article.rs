@@ -0,0 +1,83 @@
+// File: crates/adapters/cli/src/article.rs
+// Purpose: Full-article fetching for feeds with settings.full_content: fetch each entry's page through
+//   the run's fetcher and rate limiter, extract the readable content and store it on the entry.
+// Inputs/Outputs: FetchCtx + Feed + the feed's entries; stores and updates in place the entries that
+//   gained content, returns how many did and the rate-limit wait.
+// Invariants: Entries that already carry content are skipped and summaries are never touched.
+//   Article requests send only the feed's User-Agent, never its credentials: pages may live on
+//   other origins. Pages are size-capped by the feed's limits and go through the run's fetcher
+//   stack, so robots.txt (RobotsFetcher, per --user-agent) and --ssrf-guard apply per hop.
+//   With --warc, the entry links the WARC response records of its article page (all hops).
+
+use super::fetch_feed::{FetchCtx, FetchRepo};
+use rssify_core::{
+    content_kind, ContentBlob, ContentKind, Entry, EntryRepo, Feed, FetchRequest, FetchResponse, Fetcher, RepoError, TxRepo,
+};
+use rssify_fetch::extract_article;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ArticleStats {
+    pub written: u32,
+    pub wait_ms: u64,
+}
+
+/// Fetch and store the article of every entry of `feed` that has a URL but no content yet.
+/// Per-article failures are logged and skipped; only repository errors abort.
+pub fn fetch_articles<R, F>(ctx: &FetchCtx<'_, R, F>, feed: &Feed, entries: &mut [Entry]) -> Result<ArticleStats, RepoError>
+where
+    R: FetchRepo,
+    F: Fetcher,
+{
+    let mut stats = ArticleStats::default();
+    for entry in entries {
+        if enrich_entry(ctx, feed, entry, &mut stats.wait_ms) {
+            EntryRepo::upsert(ctx.repo, ctx.tx, entry)?;
+            stats.written += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// Fetch `entry`'s page and fill in its content when it has a URL but no content yet; returns
+/// whether it gained content. Writes nothing, so `fetch --dry-run` can project the same change.
+pub fn enrich_entry<R, F>(ctx: &FetchCtx<'_, R, F>, feed: &Feed, entry: &mut Entry, wait_ms: &mut u64) -> bool
+where
+    R: TxRepo,
+    F: Fetcher,
+{
+    let Some(url) = entry.url.clone().filter(|_| entry.content.is_none()) else {
+        return false;
+    };
+    let headers: Vec<(String, String)> =
+        feed.settings.request.user_agent.iter().map(|ua| ("User-Agent".to_string(), ua.clone())).collect();
+    *wait_ms += ctx.limiter.acquire(&url);
+    let req = FetchRequest { url: url.clone(), headers, ..FetchRequest::default() };
+    ctx.warc_responses();
+    let page = match ctx.fetch(feed, &req) {
+        Ok(resp) if (200..300).contains(&resp.status) && is_html(&resp) => resp,
+        Ok(resp) => {
+            let reason = format!("http {} {}", resp.status, resp.header("content-type").unwrap_or("-"));
+            ctx.log.warn("article_skip", &[("entry_id", entry.id.as_str()), ("url", url.as_str()), ("reason", reason.as_str())]);
+            return false;
+        }
+        Err(outcome) => {
+            let reason = format!("{:?}", outcome);
+            ctx.log.warn("article_skip", &[("entry_id", entry.id.as_str()), ("url", url.as_str()), ("reason", reason.as_str())]);
+            return false;
+        }
+    };
+    let Some(article) = extract_article(&String::from_utf8_lossy(&page.body)) else {
+        ctx.log.info("article_empty", &[("entry_id", entry.id.as_str()), ("url", url.as_str())]);
+        return false;
+    };
+    entry.content = Some(ContentBlob { kind: ContentKind::Html, bytes: article.html.into_bytes() });
+    entry.content_text = Some(article.text);
+    entry.warc_records = ctx.warc_responses();
+    true
+}
+
+/// HTML or XHTML, or no Content-Type at all (cassettes and sloppy servers).
+fn is_html(resp: &FetchResponse) -> bool {
+    resp.header("content-type")
+        .is_none_or(|ct| content_kind(Some(ct)) == ContentKind::Html || ct.to_ascii_lowercase().contains("xhtml"))
+}
@@ -0,0 +1,43 @@
+// File: crates/adapters/cli/src/backend.rs
+// Purpose: Open the repo named by a fs:/sqlite:/mem: spec and write mem snapshots back.
+// Inputs/Outputs: spec::RepoSpec in; an opened repo or a user-facing error string out.
+// Invariants: A bare `mem:` never touches disk. `compress` is the only fs option; unknown options
+//   are refused rather than ignored.
+
+use super::spec;
+
+/// Open the fs repo named by a `fs:<root>[?compress=zstd|none]` spec.
+pub fn open_fs(spec: &spec::RepoSpec) -> Result<rssify_repo_fs::FsRepo, String> {
+    let mut compression = rssify_repo_fs::Compression::None;
+    for (key, value) in &spec.options {
+        match key.as_str() {
+            "compress" => compression = value.parse().map_err(|e| format!("invalid --store {}: {}", spec, e))?,
+            other => return Err(format!("invalid --store {}: unknown fs option {:?}", spec, other)),
+        }
+    }
+    Ok(rssify_repo_fs::FsRepo::open(&spec.target).with_compression(compression))
+}
+
+/// Open the sqlite database named by a `sqlite:<path>` spec.
+pub fn open_sqlite(spec: &spec::RepoSpec) -> Result<rssify_repo_sqlite::SqliteRepo, String> {
+    rssify_repo_sqlite::SqliteRepo::open(&spec.target).map_err(|e| format!("cannot open --store {}: {}", spec, e))
+}
+
+/// A `mem:` store, seeded from its snapshot file when the spec names one that exists.
+pub fn open_mem(spec: &spec::RepoSpec) -> Result<rssify_repo_mem::MemRepo, String> {
+    let path = std::path::Path::new(&spec.target);
+    if spec.target.is_empty() || !path.exists() {
+        return Ok(rssify_repo_mem::MemRepo::new());
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("cannot open --store {}: {}", spec, e))?;
+    let snapshot = serde_json::from_slice(&bytes).map_err(|e| format!("cannot open --store {}: {}", spec, e))?;
+    Ok(rssify_repo_mem::MemRepo::from_snapshot(snapshot))
+}
+
+/// Export a `mem:` store to its snapshot file; a bare `mem:` never touches disk.
+pub fn save_mem(spec: &spec::RepoSpec, repo: &rssify_repo_mem::MemRepo) -> Result<(), String> {
+    if spec.target.is_empty() {
+        return Ok(());
+    }
+    repo.write_snapshot(&spec.target).map_err(|e| format!("cannot write --store {}: {}", spec, e))
+}
@@ -0,0 +1,249 @@
+// File: crates/adapters/cli/src/cmd_fetch.rs
+// Purpose: `rssify fetch` orchestration: parse flags, load seeds, secrets, rate limits and the
+//   cassette, open the selected repository (fs or sqlite), hand the run to fetch_run and print the
+//   summary, optionally with entries newer than --since.
+// Inputs/Outputs: FetchOpts from clap; prints the summary (text or JSON) to stdout, logs to stderr.
+// Invariants: No transport is built in yet, so feeds are only fetched when a cassette is replayed
+//   (--replay); otherwise targets are registered and reported. Every request, feed, article or
+//   attachment, goes through one stack: redirects (+ SSRF guard) > robots.txt > WARC > [record] >
+//   replay; --record wraps the transport, so it sees each hop exactly as the transport answered.
+
+use super::dry_run::print_report;
+use super::fetch_run::{run_feeds, RunInputs, RunTotals};
+use super::log::{LogLevel, Logger};
+use super::register::load_overrides;
+use super::store::{resolve_store_spec, ENV_REPO};
+use super::{pipeline, spec, timefmt};
+use rssify_core::{Entry, EntryRepo, FeedId, Fetcher};
+use rssify_fetch::{
+    RateLimiter, RateLimits, RecordFetcher, RedirectFetcher, ReplayFetcher, RobotsFetcher, Secrets, SsrfPolicy, Warc, WarcFetcher,
+    DEFAULT_WARC_FILE_BYTES, ENV_SECRETS,
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Flags of the `fetch` subcommand.
+#[derive(Debug, Default)]
+pub struct FetchOpts {
+    pub from: Option<String>,
+    pub store: Option<String>,
+    pub json: bool,
+    pub verbose: u8,
+    pub replay: Option<String>,
+    /// Cassette directory every transport interaction is recorded into (needs a transport).
+    pub record: Option<String>,
+    /// Secrets file for per-feed credentials (falls back to env RSSIFY_SECRETS).
+    pub secrets: Option<String>,
+    /// Per-domain rate limits file; without it requests are not rate limited.
+    pub rate_limits: Option<String>,
+    /// Fetch just this feed (id or URL) instead of reading the seed file.
+    pub one: Option<String>,
+    /// Print entries of the processed feeds newer than this timestamp.
+    pub since: Option<String>,
+    /// Report would-be changes per feed without writing anything.
+    pub dry_run: bool,
+    /// Continue the last incomplete run instead of starting a new one.
+    pub resume: bool,
+    /// Directory for rotating .warc.gz files recording every request/response pair.
+    pub warc: Option<String>,
+    /// Vet every URL and redirect hop with rssify_fetch::SsrfPolicy.
+    pub ssrf_guard: bool,
+    /// Hosts (or host:port) exempt from the guard's address check.
+    pub ssrf_allow: Vec<String>,
+    /// Product token matched against robots.txt groups (default DEFAULT_USER_AGENT).
+    pub user_agent: Option<String>,
+}
+
+/// User agent robots.txt rules are evaluated for when --user-agent is not given.
+pub const DEFAULT_USER_AGENT: &str = concat!("rssify/", env!("CARGO_PKG_VERSION"));
+
+pub fn run(opts: FetchOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::from_verbosity(opts.verbose));
+    let seed_path = opts.from.clone().unwrap_or_else(|| "feeds.json".to_string());
+
+    // Resolve store spec with precedence: flag > env > default.
+    let resolved = resolve_store_spec(opts.store.clone());
+    log.info("fetch_start", &[
+        ("from", opts.one.as_deref().map_or(seed_path.as_str(), |_| "--one")),
+        ("store", resolved.as_str()),
+        ("env", ENV_REPO),
+    ]);
+
+    let since = match &opts.since {
+        Some(s) => Some(timefmt::parse_ts(s).map_err(|e| format!("invalid --since: {}", e))?),
+        None => None,
+    };
+    let replay = match &opts.replay {
+        Some(dir) => Some(ReplayFetcher::open(dir).map_err(|e| format!("invalid --replay: {}", e))?),
+        None => None,
+    };
+    let recorder = match (&replay, &opts.record) {
+        (Some(_), Some(dir)) if Some(dir) == opts.replay.as_ref() => {
+            return Err("--record must name a different directory than --replay".into());
+        }
+        (Some(r), Some(dir)) => Some(RecordFetcher::new(r, dir).map_err(|e| format!("invalid --record {}: {}", dir, e))?),
+        (None, Some(_)) => return Err("--record needs a transport to wrap; pass --replay <dir>".into()),
+        _ => None,
+    };
+    let transport: Option<&dyn Fetcher> = match (&recorder, &replay) {
+        (Some(rec), _) => Some(rec),
+        (None, Some(r)) => Some(r),
+        (None, None) => None,
+    };
+    let secrets = match opts.secrets.clone().or_else(|| std::env::var(ENV_SECRETS).ok()) {
+        Some(path) => Secrets::load(&path).map_err(|e| format!("invalid secrets file {}: {}", path, e))?,
+        None => Secrets::default(),
+    };
+    let limits = match &opts.rate_limits {
+        Some(path) => RateLimits::load(path).map_err(|e| format!("invalid --rate-limits {}: {}", path, e))?,
+        None => RateLimits::default(),
+    };
+    let limiter = RateLimiter::new(limits);
+    let warc = match &opts.warc {
+        Some(dir) => Some(Warc::open(dir, DEFAULT_WARC_FILE_BYTES).map_err(|e| format!("invalid --warc {}: {}", dir, e))?),
+        None => None,
+    };
+
+    let (ids, overrides) = match &opts.one {
+        Some(one) => (vec![one.clone()], BTreeMap::new()),
+        None => match pipeline::load_feed_seeds(&seed_path)
+            .and_then(|ids| Ok((ids, load_overrides(&seed_path)?)))
+        {
+            Ok(v) => v,
+            Err(e) => {
+                Logger::new(LogLevel::Error).error("fetch_parse_error", &[("error", format!("{}", e))]);
+                return Err(format!("failed to parse seeds: {}", e).into());
+            }
+        },
+    };
+
+    // Open repo via RepoSpec.
+    let spec = spec::RepoSpec::from_str(&resolved)
+        .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
+
+    // Robots and WARC sit inside the redirect follower so every hop is checked against robots.txt
+    // and is its own record pair; the SSRF guard vets each hop before it reaches the inner stack,
+    // robots.txt hops included.
+    let guard = opts.ssrf_guard.then(|| opts.ssrf_allow.iter().fold(SsrfPolicy::new(), |p, host| p.allow(host)));
+    let user_agent = opts.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+    let fetcher = transport.map(|t| {
+        let robots = RobotsFetcher::new(WarcFetcher::new(t, warc.clone()), user_agent);
+        match guard.clone() {
+            Some(policy) => RedirectFetcher::new(robots.with_guard(policy.clone())).with_guard(policy),
+            None => RedirectFetcher::new(robots),
+        }
+    });
+    let inputs = RunInputs {
+        opts: &opts,
+        ids: &ids,
+        overrides: &overrides,
+        fetcher: fetcher.as_ref(),
+        secrets: &secrets,
+        limiter: &limiter,
+        warc: warc.as_ref(),
+        log: &log,
+        since,
+    };
+    let RunTotals { run_id, ids, written, inactive, counts, entries, previews } = match spec.kind {
+        spec::RepoKind::Fs => run_feeds(&super::backend::open_fs(&spec)?, &inputs)?,
+        spec::RepoKind::Sqlite => run_feeds(&super::backend::open_sqlite(&spec)?, &inputs)?,
+        spec::RepoKind::Mem => {
+            let repo = super::backend::open_mem(&spec)?;
+            let totals = run_feeds(&repo, &inputs)?;
+            if !opts.dry_run {
+                super::backend::save_mem(&spec, &repo)?;
+            }
+            totals
+        }
+    };
+
+    let processed = ids.len() - inactive;
+    if opts.dry_run {
+        return print_report(previews, ids.len(), processed, opts.json);
+    }
+    let mut summary = json!({
+        "run_id": run_id,
+        "feeds_total": ids.len(),
+        "feeds_processed": processed,
+        "items_written": written,
+        "fetched": counts.fetched,
+        "not_modified": counts.not_modified,
+        "failed": counts.failed,
+        "deactivated": counts.deactivated,
+        "articles_written": counts.articles,
+        "attachments_archived": counts.attachments
+    });
+    if let Some(w) = &warc {
+        summary["warc_files"] = json!(w.files().iter().map(|p| p.display().to_string()).collect::<Vec<_>>());
+    }
+    if since.is_some() {
+        summary["entries"] = json!(entries.iter().map(entry_json).collect::<Vec<_>>());
+    }
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+    } else {
+        println!("Run {}", run_id);
+        println!(
+            "Processed {}/{} feeds; items written={}",
+            processed,
+            ids.len(),
+            written
+        );
+        if replay.is_some() {
+            println!(
+                "fetched={} not_modified={} failed={} deactivated={} articles={} attachments={}",
+                counts.fetched,
+                counts.not_modified,
+                counts.failed,
+                counts.deactivated,
+                counts.articles,
+                counts.attachments
+            );
+        }
+        for e in &entries {
+            println!(
+                "entry feed={} id={} ts={} title={}",
+                e.feed.as_str(),
+                e.id.as_str(),
+                e.published_ts.or(e.updated_ts).unwrap_or_default(),
+                e.title.as_deref().unwrap_or("-")
+            );
+        }
+    }
+
+    log.info("fetch_done", &[("feeds", ids.len()), ("written", written)]);
+    Ok(())
+}
+
+/// Entries of `feeds` whose published (else updated) time is after `since`, oldest first.
+pub fn entries_since<R: EntryRepo>(
+    repo: &R,
+    feeds: &[FeedId],
+    since: i64,
+) -> Result<Vec<Entry>, rssify_core::RepoError> {
+    let mut out = Vec::new();
+    for feed in feeds {
+        out.extend(
+            EntryRepo::list_by_feed(repo, None, feed)?
+                .into_iter()
+                .filter(|e| e.published_ts.or(e.updated_ts).is_some_and(|ts| ts > since)),
+        );
+    }
+    out.sort_by(|a, b| {
+        (a.published_ts.or(a.updated_ts), a.id.as_str()).cmp(&(b.published_ts.or(b.updated_ts), b.id.as_str()))
+    });
+    Ok(out)
+}
+
+fn entry_json(e: &Entry) -> serde_json::Value {
+    json!({
+        "feed_id": e.feed.as_str(),
+        "id": e.id.as_str(),
+        "url": e.url,
+        "title": e.title,
+        "published_ts": e.published_ts,
+        "updated_ts": e.updated_ts
+    })
+}
@@ -0,0 +1,96 @@
+// File: crates/adapters/cli/src/cmd_migrate.rs
+// Purpose: `rssify migrate`: back up a repo, then run its backend's ordered upgrade steps.
+// Inputs/Outputs: MigrateOpts from clap; prints the migration report (text or JSON) to stdout.
+// Invariants: A backup is taken only when steps are pending, and always before the first one;
+//   backups never overwrite an existing path. A failed step leaves the repo at the last
+//   recorded version, so re-running resumes. Mem stores keep no format to migrate.
+
+use super::fetch_feed::now_unix;
+use super::log::{LogLevel, Logger};
+use super::spec;
+use super::store::resolve_store_spec;
+use rssify_core::{MigrationReport, RepoError};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Flags of the `migrate` subcommand.
+pub struct MigrateOpts {
+    pub store: Option<String>,
+    pub json: bool,
+    /// Directory for the pre-migration backup; defaults to a sibling of the repo.
+    pub backup_dir: Option<String>,
+}
+
+pub fn run(opts: MigrateOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+    let resolved = resolve_store_spec(opts.store);
+    let spec = spec::RepoSpec::from_str(&resolved)
+        .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
+
+    let target = Path::new(&spec.target);
+    let (current, latest) = match spec.kind {
+        spec::RepoKind::Fs => (rssify_repo_fs::repo_version(target)?, rssify_repo_fs::REPO_VERSION),
+        spec::RepoKind::Sqlite => (rssify_repo_sqlite::repo_version(target)?, rssify_repo_sqlite::SCHEMA_VERSION),
+        spec::RepoKind::Mem => {
+            if opts.json {
+                println!("{}", json!({ "store": spec.to_string(), "status": "nothing_to_migrate" }));
+            } else {
+                println!("{} keeps no on-disk format to migrate", spec);
+            }
+            return Ok(());
+        }
+    };
+
+    let backup = match current {
+        Some(v) if v < latest => {
+            let dest = backup_path(target, opts.backup_dir.as_deref(), v)?;
+            let taken = match spec.kind {
+                spec::RepoKind::Fs => rssify_repo_fs::backup(target, &dest),
+                _ => rssify_repo_sqlite::backup(target, &dest),
+            };
+            taken.map_err(|e| format!("backup to {} failed: {}", dest.display(), e))?;
+            Some(dest.display().to_string())
+        }
+        _ => None,
+    };
+    let migrated: Result<MigrationReport, RepoError> = match spec.kind {
+        spec::RepoKind::Fs => rssify_repo_fs::migrate(target),
+        _ => rssify_repo_sqlite::migrate(target),
+    };
+    let mut report = migrated.map_err(|e| match &backup {
+        Some(b) => format!("migrate failed: {} (backup at {})", e, b),
+        None => format!("migrate failed: {}", e),
+    })?;
+    report.backup = backup;
+
+    if opts.json {
+        let mut out = serde_json::to_value(&report)?;
+        out["store"] = json!(spec.to_string());
+        println!("{}", serde_json::to_string_pretty(&out)?);
+    } else if report.applied.is_empty() {
+        println!("{} already at version {}", spec, report.to);
+    } else {
+        if let Some(b) = &report.backup {
+            println!("backed up {} to {}", spec, b);
+        }
+        println!("migrated {} version {} -> {}", spec, report.from, report.to);
+        for step in &report.applied {
+            println!("  v{} {}: {}", step.to, step.name, step.note);
+        }
+    }
+    log.info("migrate_done", &[("from", report.from), ("to", report.to)]);
+    Ok(())
+}
+
+/// `<dir>/<name>.backup-v<from>-<unix ts>`, where `dir` defaults to the repo's parent.
+fn backup_path(target: &Path, backup_dir: Option<&str>, from: u32) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let abs = std::fs::canonicalize(target)?;
+    let name = abs.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| "rssify".into());
+    let dir = match backup_dir {
+        Some(d) => PathBuf::from(d),
+        None => abs.parent().map(Path::to_path_buf).unwrap_or_default(),
+    };
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir.join(format!("{}.backup-v{}-{}", name, from, now_unix())))
+}
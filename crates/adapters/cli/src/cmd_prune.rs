@@ -0,0 +1,140 @@
+// File: crates/adapters/cli/src/cmd_prune.rs
+// Purpose: `rssify prune`: apply a retention policy (keep last N / newer than D days) per feed.
+// Inputs/Outputs: PruneOpts from clap; prints per-feed and total counts (text or JSON) to stdout.
+// Invariants: Starred entries are never pruned; an entry survives if either bound keeps it.
+//   Refuses to run without a bound. --dry-run only reads. Each feed is pruned in its own tx;
+//   blobs no remaining entry or archive references are collected after the last feed.
+
+use super::backend::{open_fs, open_mem, open_sqlite, save_mem};
+use super::fetch_feed::now_unix;
+use super::log::{LogLevel, Logger};
+use super::spec;
+use super::store::resolve_store_spec;
+use rssify_core::{BlobRepo, EntryRepo, FeedId, FeedRepo, RetentionPolicy};
+use serde::Serialize;
+use serde_json::json;
+use std::str::FromStr;
+
+/// Flags of the `prune` subcommand.
+pub struct PruneOpts {
+    pub store: Option<String>,
+    pub json: bool,
+    pub keep_last: Option<usize>,
+    pub max_age_days: Option<u32>,
+    /// Only this feed id (or feed URL); defaults to every feed.
+    pub feed: Option<String>,
+    pub dry_run: bool,
+}
+
+/// What prune did (or would do) to one feed.
+#[derive(Debug, Serialize)]
+pub struct PrunedFeed {
+    pub feed_id: String,
+    pub pruned: usize,
+    pub kept: usize,
+}
+
+/// Prune `feed` (or every feed) of `repo` under `policy` at `now` (unix seconds).
+pub fn prune_repo<R: FeedRepo + EntryRepo>(
+    repo: &R,
+    feed: Option<&str>,
+    policy: &RetentionPolicy,
+    now: i64,
+    dry_run: bool,
+) -> Result<Vec<PrunedFeed>, String> {
+    let ids: Vec<FeedId> = match feed {
+        Some(f) => vec![
+            FeedRepo::get(repo, None, &FeedId::new(f))
+                .or_else(|_| FeedRepo::get(repo, None, &FeedId::from_url(f)))
+                .map_err(|_| format!("feed not found: {}", f))?
+                .id,
+        ],
+        None => {
+            let mut ids: Vec<FeedId> =
+                FeedRepo::list(repo, None).map_err(|e| e.to_string())?.into_iter().map(|f| f.id).collect();
+            ids.sort();
+            ids
+        }
+    };
+    let mut out = Vec::with_capacity(ids.len());
+    for id in ids {
+        let entries = EntryRepo::list_by_feed(repo, None, &id).map_err(|e| e.to_string())?;
+        let pruned = if dry_run {
+            policy.expired(&entries, now).len()
+        } else {
+            EntryRepo::prune(repo, None, &id, policy, now)
+                .map_err(|e| format!("prune of {} failed: {}", id.as_str(), e))?
+                .len()
+        };
+        out.push(PrunedFeed { feed_id: id.as_str().to_string(), pruned, kept: entries.len() - pruned });
+    }
+    Ok(out)
+}
+
+/// `prune_repo`, then collect the blobs it left unreferenced (none on a dry run).
+pub fn prune_and_collect<R: FeedRepo + EntryRepo + BlobRepo>(
+    repo: &R,
+    feed: Option<&str>,
+    policy: &RetentionPolicy,
+    now: i64,
+    dry_run: bool,
+) -> Result<(Vec<PrunedFeed>, usize), String> {
+    let feeds = prune_repo(repo, feed, policy, now, dry_run)?;
+    if dry_run {
+        return Ok((feeds, 0));
+    }
+    let removed = BlobRepo::gc_blobs(repo, None).map_err(|e| format!("blob collection failed: {}", e))?;
+    Ok((feeds, removed))
+}
+
+pub fn run(opts: PruneOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+    let policy = RetentionPolicy { keep_last: opts.keep_last, max_age_days: opts.max_age_days };
+    if policy.is_unbounded() {
+        return Err("prune needs --keep-last and/or --max-age-days".into());
+    }
+    let resolved = resolve_store_spec(opts.store);
+    let spec = spec::RepoSpec::from_str(&resolved)
+        .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
+
+    let (feed, now, dry_run) = (opts.feed.as_deref(), now_unix(), opts.dry_run);
+    let (feeds, blobs_removed) = match spec.kind {
+        spec::RepoKind::Fs => prune_and_collect(&open_fs(&spec)?, feed, &policy, now, dry_run)?,
+        spec::RepoKind::Sqlite => prune_and_collect(&open_sqlite(&spec)?, feed, &policy, now, dry_run)?,
+        spec::RepoKind::Mem => {
+            let repo = open_mem(&spec)?;
+            let done = prune_and_collect(&repo, feed, &policy, now, dry_run)?;
+            if !dry_run {
+                save_mem(&spec, &repo)?;
+            }
+            done
+        }
+    };
+    let total: usize = feeds.iter().map(|f| f.pruned).sum();
+
+    if opts.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "store": spec.to_string(),
+                "dry_run": dry_run,
+                "keep_last": policy.keep_last,
+                "max_age_days": policy.max_age_days,
+                "pruned": total,
+                "blobs_removed": blobs_removed,
+                "feeds": feeds
+            }))?
+        );
+    } else {
+        let verb = if dry_run { "would prune" } else { "pruned" };
+        for f in feeds.iter().filter(|f| f.pruned > 0) {
+            println!("{} {} entries from {} ({} kept)", verb, f.pruned, f.feed_id, f.kept);
+        }
+        println!("{} {} entries across {} feeds", verb, total, feeds.len());
+        if !dry_run {
+            println!("removed {} unreferenced blobs", blobs_removed);
+        }
+    }
+    log.info("prune_done", &[("pruned", total)]);
+    Ok(())
+}
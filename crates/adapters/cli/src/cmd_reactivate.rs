@@ -0,0 +1,44 @@
+// File: crates/adapters/cli/src/cmd_reactivate.rs
+// Purpose: `rssify reactivate <feed>`: put a deactivated feed back into rotation.
+// Inputs/Outputs: ReactivateOpts from clap; prints the feed id (text or JSON) to stdout.
+// Invariants: Resolution and state changes live in outcome::reactivate; mem snapshots are saved
+//   back after the change.
+
+use super::backend::{open_fs, open_mem, open_sqlite, save_mem};
+use super::log::{LogLevel, Logger};
+use super::store::resolve_store_spec;
+use super::{outcome, spec};
+use serde_json::json;
+use std::str::FromStr;
+
+/// Flags of the `reactivate` subcommand.
+pub struct ReactivateOpts {
+    pub feed: String,
+    pub store: Option<String>,
+    pub json: bool,
+}
+
+pub fn run(opts: ReactivateOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+    let resolved = resolve_store_spec(opts.store);
+    let spec = spec::RepoSpec::from_str(&resolved)
+        .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
+
+    let (id, was_active) = match spec.kind {
+        spec::RepoKind::Fs => outcome::reactivate(&open_fs(&spec)?, &opts.feed)?,
+        spec::RepoKind::Sqlite => outcome::reactivate(&open_sqlite(&spec)?, &opts.feed)?,
+        spec::RepoKind::Mem => {
+            let repo = open_mem(&spec)?;
+            let done = outcome::reactivate(&repo, &opts.feed)?;
+            save_mem(&spec, &repo)?;
+            done
+        }
+    };
+    if opts.json {
+        println!("{}", json!({"feed_id": id.as_str(), "was_active": was_active}));
+    } else {
+        println!("reactivated {}", id.as_str());
+    }
+    log.info("reactivate_done", &[("feed_id", id.as_str())]);
+    Ok(())
+}
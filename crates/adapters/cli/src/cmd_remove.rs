@@ -0,0 +1,56 @@
+// File: crates/adapters/cli/src/cmd_remove.rs
+// Purpose: `rssify remove <feed>`: unsubscribe a feed, dropping its entries and schedule state.
+// Inputs/Outputs: RemoveOpts from clap; prints the removed feed (text or JSON) to stdout.
+// Invariants: Resolves the feed by stored id, then by URL; an unknown feed is an error and
+//   changes nothing. Seed files are not edited, so a feed still listed there returns on the
+//   next `fetch --from`.
+
+use super::backend::{open_fs, open_mem, open_sqlite, save_mem};
+use super::log::{LogLevel, Logger};
+use super::spec;
+use super::store::resolve_store_spec;
+use rssify_core::{EntryRepo, FeedId, FeedRepo};
+use serde_json::json;
+use std::str::FromStr;
+
+/// Flags of the `remove` subcommand.
+pub struct RemoveOpts {
+    pub feed: String,
+    pub store: Option<String>,
+    pub json: bool,
+}
+
+/// Delete `feed` (id or URL) from `repo`; returns its id and how many entries went with it.
+pub fn remove_feed<R: FeedRepo + EntryRepo>(repo: &R, feed: &str) -> Result<(FeedId, usize), String> {
+    let found = FeedRepo::get(repo, None, &FeedId::new(feed))
+        .or_else(|_| FeedRepo::get(repo, None, &FeedId::from_url(feed)))
+        .map_err(|_| format!("feed not found: {}", feed))?;
+    let entries = EntryRepo::list_by_feed(repo, None, &found.id).map_err(|e| e.to_string())?.len();
+    FeedRepo::delete(repo, None, &found.id).map_err(|e| format!("remove failed: {}", e))?;
+    Ok((found.id, entries))
+}
+
+pub fn run(opts: RemoveOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+    let resolved = resolve_store_spec(opts.store);
+    let spec = spec::RepoSpec::from_str(&resolved)
+        .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
+
+    let (id, entries) = match spec.kind {
+        spec::RepoKind::Fs => remove_feed(&open_fs(&spec)?, &opts.feed)?,
+        spec::RepoKind::Sqlite => remove_feed(&open_sqlite(&spec)?, &opts.feed)?,
+        spec::RepoKind::Mem => {
+            let repo = open_mem(&spec)?;
+            let done = remove_feed(&repo, &opts.feed)?;
+            save_mem(&spec, &repo)?;
+            done
+        }
+    };
+    if opts.json {
+        println!("{}", json!({"feed_id": id.as_str(), "removed": true, "entries_removed": entries}));
+    } else {
+        println!("removed {} ({} entries)", id.as_str(), entries);
+    }
+    log.info("remove_done", &[("feed_id", id.as_str())]);
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// File: crates/adapters/cli/src/cmd_search.rs
+// Purpose: `rssify search "<query>"` over the repo's full-text index, and `rssify reindex` to rebuild it.
+// Inputs/Outputs: SearchOpts/ReindexOpts from clap; prints ranked hits or the reindexed count
+//   (text or JSON) to stdout.
+// Invariants: Query syntax and ranking are rssify_core's (SearchQuery::parse, rank); hits are
+//   decorated with the stored title and URL only. A hit whose entry vanished since it was
+//   ranked is skipped.
+
+use super::backend::{open_fs, open_mem, open_sqlite};
+use super::log::{LogLevel, Logger};
+use super::spec;
+use super::store::resolve_store_spec;
+use rssify_core::{EntryRepo, SearchQuery, SearchRepo};
+use serde::Serialize;
+use serde_json::json;
+use std::str::FromStr;
+
+/// Hits shown when --limit is not given.
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// Flags of the `search` subcommand.
+pub struct SearchOpts {
+    pub query: String,
+    pub limit: usize,
+    pub store: Option<String>,
+    pub json: bool,
+}
+
+/// Flags of the `reindex` subcommand.
+pub struct ReindexOpts {
+    pub store: Option<String>,
+    pub json: bool,
+}
+
+/// One ranked entry, as printed.
+#[derive(Debug, Serialize)]
+pub struct SearchRow {
+    pub entry_id: String,
+    pub feed_id: String,
+    pub score: f64,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub published_ts: Option<i64>,
+}
+
+/// The best `limit` entries of `repo` for `query`, best first.
+pub fn search_repo<R: SearchRepo + EntryRepo>(repo: &R, query: &str, limit: usize) -> Result<Vec<SearchRow>, String> {
+    let parsed = SearchQuery::parse(query).map_err(|e| e.to_string())?;
+    let hits = SearchRepo::search(repo, None, &parsed, limit).map_err(|e| format!("search failed: {}", e))?;
+    let mut rows = Vec::with_capacity(hits.len());
+    for hit in hits {
+        let Ok(entry) = EntryRepo::get(repo, None, &hit.entry) else { continue };
+        rows.push(SearchRow {
+            entry_id: hit.entry.as_str().to_string(),
+            feed_id: hit.feed.as_str().to_string(),
+            score: hit.score,
+            title: entry.title,
+            url: entry.url,
+            published_ts: hit.published_ts,
+        });
+    }
+    Ok(rows)
+}
+
+fn open_spec(store: Option<String>) -> Result<spec::RepoSpec, String> {
+    let resolved = resolve_store_spec(store);
+    spec::RepoSpec::from_str(&resolved).map_err(|e| format!("invalid --store: {} ({})", resolved, e))
+}
+
+pub fn run_search(opts: SearchOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+    let spec = open_spec(opts.store)?;
+    let rows = match spec.kind {
+        spec::RepoKind::Fs => search_repo(&open_fs(&spec)?, &opts.query, opts.limit)?,
+        spec::RepoKind::Sqlite => search_repo(&open_sqlite(&spec)?, &opts.query, opts.limit)?,
+        spec::RepoKind::Mem => search_repo(&open_mem(&spec)?, &opts.query, opts.limit)?,
+    };
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&json!({"query": opts.query, "hits": rows}))?);
+    } else {
+        for r in &rows {
+            let title = r.title.as_deref().unwrap_or("(untitled)");
+            println!("{:.3} {} {} {}", r.score, r.entry_id, title, r.url.as_deref().unwrap_or("-"));
+        }
+    }
+    log.info("search_done", &[("items", rows.len())]);
+    Ok(())
+}
+
+pub fn run_reindex(opts: ReindexOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+    let spec = open_spec(opts.store)?;
+    let indexed = match spec.kind {
+        spec::RepoKind::Fs => SearchRepo::reindex(&open_fs(&spec)?, None),
+        spec::RepoKind::Sqlite => SearchRepo::reindex(&open_sqlite(&spec)?, None),
+        spec::RepoKind::Mem => SearchRepo::reindex(&open_mem(&spec)?, None),
+    }
+    .map_err(|e| format!("reindex failed: {}", e))?;
+    if opts.json {
+        println!("{}", json!({"reindexed": indexed}));
+    } else {
+        println!("reindexed {} entries", indexed);
+    }
+    log.info("reindex_done", &[("items", indexed)]);
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// File: crates/adapters/cli/src/cmd_stats.rs
+// Purpose: `rssify stats`: open the selected repo and print feed/entry counts, deactivated feeds
+//   and (fs only) on-disk versus logical storage.
+// Inputs/Outputs: StatsOpts from clap; prints the summary (text or JSON) to stdout.
+// Invariants: Read-only. fs repos are counted by path (stats_fs), other backends through the
+//   repo traits (stats_repo).
+
+use super::backend::{open_mem, open_sqlite};
+use super::log::{LogLevel, Logger};
+use super::store::resolve_store_spec;
+use super::{spec, stats};
+use serde_json::json;
+use std::str::FromStr;
+
+/// Flags of the `stats` subcommand.
+pub struct StatsOpts {
+    pub store: Option<String>,
+    pub json: bool,
+}
+
+pub fn run(opts: StatsOpts) -> Result<(), Box<dyn std::error::Error>> {
+    let log = Logger::new(LogLevel::Warn);
+
+    // Resolve store spec with precedence: flag > env > default.
+    let resolved = resolve_store_spec(opts.store);
+    let spec = spec::RepoSpec::from_str(&resolved)
+        .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
+
+    let s = match spec.kind {
+        spec::RepoKind::Fs => stats::stats_fs(&spec.target)?,
+        spec::RepoKind::Sqlite => stats::stats_repo(&open_sqlite(&spec)?)?,
+        spec::RepoKind::Mem => stats::stats_repo(&open_mem(&spec)?)?,
+    };
+    if opts.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "feeds": s.feeds,
+                "entries": s.entries,
+                "deactivated": s.deactivated,
+                "storage": s.storage
+            }))?
+        );
+    } else {
+        println!("feeds={} entries={}", s.feeds, s.entries);
+        if let Some(z) = &s.storage {
+            println!("on_disk_bytes={} logical_bytes={}", z.on_disk_bytes, z.logical_bytes);
+        }
+        for d in &s.deactivated {
+            println!(
+                "deactivated id={} reason={} ts={}",
+                d.id,
+                d.reason.as_deref().unwrap_or("-"),
+                d.ts.map_or_else(|| "-".to_string(), |t| t.to_string())
+            );
+        }
+    }
+    log.info("stats_done", &[("feeds", s.feeds), ("entries", s.entries)]);
+    Ok(())
+}
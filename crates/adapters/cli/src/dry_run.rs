@@ -0,0 +1,171 @@
+// File: crates/adapters/cli/src/dry_run.rs
+// Purpose: Read-only preview of one feed's fetch: outcome, entry diff, metadata deltas and
+//   whether the deactivation policy would fire. Backs `fetch --dry-run`.
+// Inputs/Outputs: FetchCtx + Feed (+ whether it is already stored); returns FeedPreview;
+//   print_report renders the sorted report (text or JSON) to stdout.
+// Invariants: Never writes to the repo. Output carries no timings or wall-clock values so two
+//   builds can be diffed on the same cassette. Entry counts are core::diff_entries of the stored
+//   entries against what the fetch would leave behind. No feed parser is built in yet, so that is
+//   the stored entries plus full-article content (settings.full_content), fetched but not
+//   stored; nothing is reported new until a parser supplies entries.
+
+use super::article::enrich_entry;
+use super::failover::fetch_with_failover;
+use super::fetch_feed::{build_request, FetchCtx};
+use rssify_core::{
+    diff_entries, EntryDiff, EntryRepo, Feed, FeedRepo, FetchOutcome, Fetcher, RepoError, ScheduleRepo, TxRepo,
+};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+/// Old and new value of one feed metadata field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MetaChange {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+/// What a real fetch of one feed would change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeedPreview {
+    pub feed_id: String,
+    /// True when the feed is not stored yet and would be registered.
+    pub would_register: bool,
+    /// new_content, not_modified, failed, or not_fetched (no fetcher configured).
+    pub outcome: String,
+    pub hint: Option<String>,
+    /// Stored entries the fetch would add, change or leave as they are; zero when it fails.
+    pub entries: EntryDiff,
+    /// Changed metadata fields only, keyed by field name.
+    pub meta: BTreeMap<String, MetaChange>,
+    pub would_deactivate: Option<String>,
+}
+
+/// Preview `feed` through `ctx` without persisting anything.
+pub fn preview_feed<R, F>(ctx: &FetchCtx<'_, R, F>, feed: &Feed, stored: bool) -> Result<FeedPreview, RepoError>
+where
+    R: TxRepo + FeedRepo + EntryRepo + ScheduleRepo,
+    F: Fetcher,
+{
+    let mut preview = FeedPreview {
+        feed_id: feed.id.as_str().to_string(),
+        would_register: !stored,
+        outcome: "failed".to_string(),
+        hint: None,
+        entries: EntryDiff::default(),
+        meta: BTreeMap::new(),
+        would_deactivate: None,
+    };
+    let req = match build_request(feed, ctx.secrets) {
+        Ok(req) => req,
+        Err(missing) => {
+            preview.hint = Some(format!("missing secret {}", missing));
+            return Ok(preview);
+        }
+    };
+    // Mirror health is consulted for ordering but the updated copy is dropped.
+    let mut health = if stored { ScheduleRepo::mirror_health(ctx.repo, None, &feed.id)? } else { Default::default() };
+    let outcome = match fetch_with_failover(ctx, &req, feed, &mut health).result {
+        Ok(resp) => {
+            let outcome = resp.outcome();
+            if matches!(outcome, FetchOutcome::NewContent { .. }) {
+                let mut change = |field: &str, from: &Option<String>, to: Option<&str>| {
+                    if from.as_deref() != to {
+                        let to = to.map(str::to_string);
+                        preview.meta.insert(field.to_string(), MetaChange { from: from.clone(), to });
+                    }
+                };
+                change("etag", &feed.etag, resp.header("etag"));
+                change("last_modified", &feed.last_modified, resp.header("last-modified"));
+            }
+            outcome
+        }
+        Err(outcome) => outcome,
+    };
+    if matches!(outcome, FetchOutcome::NewContent { .. } | FetchOutcome::NotModified) && stored {
+        let stored_entries = EntryRepo::list_by_feed(ctx.repo, None, &feed.id)?;
+        let mut after = stored_entries.clone();
+        if feed.settings.full_content && matches!(outcome, FetchOutcome::NewContent { .. }) {
+            let mut wait_ms = 0;
+            for entry in &mut after {
+                enrich_entry(ctx, feed, entry, &mut wait_ms);
+            }
+        }
+        preview.entries = diff_entries(&stored_entries, &after);
+    }
+    preview.outcome = match &outcome {
+        FetchOutcome::NewContent { .. } => "new_content",
+        FetchOutcome::NotModified => "not_modified",
+        _ => "failed",
+    }
+    .to_string();
+    preview.hint = match &outcome {
+        FetchOutcome::Gone => Some("gone".to_string()),
+        FetchOutcome::TransientFailure { hint } | FetchOutcome::PermanentFailure { hint } => hint.clone(),
+        _ => None,
+    };
+    let streak = if stored { ScheduleRepo::failure_streak(ctx.repo, None, &feed.id)? } else { Default::default() };
+    let streak = streak.observe(&outcome, ctx.now);
+    if feed.active {
+        preview.would_deactivate = ctx.policy.evaluate(&outcome, &streak, ctx.now).map(|r| r.to_string());
+    }
+    Ok(preview)
+}
+
+/// Preview for a feed that cannot be fetched in this run.
+pub fn not_fetched(feed: &Feed, stored: bool) -> FeedPreview {
+    FeedPreview {
+        feed_id: feed.id.as_str().to_string(),
+        would_register: !stored,
+        outcome: "not_fetched".to_string(),
+        hint: None,
+        entries: EntryDiff::default(),
+        meta: BTreeMap::new(),
+        would_deactivate: None,
+    }
+}
+
+/// Print the dry-run report: per-feed previews sorted by id plus totals; stable for diffing.
+pub fn print_report(
+    mut previews: Vec<FeedPreview>,
+    total: usize,
+    processed: usize,
+    as_json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    previews.sort_by(|a, b| a.feed_id.cmp(&b.feed_id));
+    let sum = |f: fn(&FeedPreview) -> usize| previews.iter().map(f).sum::<usize>();
+    let report = json!({
+        "dry_run": true,
+        "feeds_total": total,
+        "feeds_processed": processed,
+        "would_register": sum(|p| usize::from(p.would_register)),
+        "would_deactivate": sum(|p| usize::from(p.would_deactivate.is_some())),
+        "entries": {
+            "new": sum(|p| p.entries.new),
+            "updated": sum(|p| p.entries.updated),
+            "unchanged": sum(|p| p.entries.unchanged)
+        },
+        "feeds": previews
+    });
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    for p in &previews {
+        let meta: Vec<String> = p.meta.keys().cloned().collect();
+        println!(
+            "feed id={} outcome={} new={} updated={} unchanged={} meta={} register={} deactivate={}",
+            p.feed_id,
+            p.outcome,
+            p.entries.new,
+            p.entries.updated,
+            p.entries.unchanged,
+            if meta.is_empty() { "-".to_string() } else { meta.join(",") },
+            p.would_register,
+            p.would_deactivate.as_deref().unwrap_or("-")
+        );
+    }
+    println!("Dry run: {}/{} feeds previewed; nothing written", processed, total);
+    Ok(())
+}
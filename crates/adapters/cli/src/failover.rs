@@ -0,0 +1,56 @@
+// File: crates/adapters/cli/src/failover.rs
+// Purpose: Fetch a feed from its primary URL and mirrors, healthiest first, failing over on errors.
+// Inputs/Outputs: FetchCtx + base request + Feed + MirrorHealth; returns the first good response
+//   (or the folded failure), the URL it came from and the rate-limit wait, updating health in place.
+// Invariants: Requests differ only in URL; entries stay under the feed's id whichever URL served them.
+//   Feeds without mirrors make exactly one attempt. Every attempt first takes a rate-limit token and
+//   is size-capped by FetchCtx::fetch (an oversize body fails over like any other failure).
+
+use super::fetch_feed::FetchCtx;
+use rssify_core::{combine_failures, Feed, FetchOutcome, FetchRequest, FetchResponse, Fetcher, MirrorHealth, TxRepo};
+
+/// Result of fetching one feed across its URLs.
+pub struct Attempt {
+    pub result: Result<FetchResponse, FetchOutcome>,
+    /// URL that served `result` (the first one tried when all failed).
+    pub url: String,
+    /// Total milliseconds spent waiting on rate limits.
+    pub wait_ms: u64,
+}
+
+/// Try `feed.urls()` in health order until one returns new content or not-modified.
+pub fn fetch_with_failover<R: TxRepo, F: Fetcher>(
+    ctx: &FetchCtx<'_, R, F>,
+    base: &FetchRequest,
+    feed: &Feed,
+    health: &mut MirrorHealth,
+) -> Attempt {
+    let urls = health.order(&feed.urls());
+    let mut failures = Vec::new();
+    let mut wait_ms = 0;
+    for (i, url) in urls.iter().enumerate() {
+        let waited = ctx.limiter.acquire(url);
+        if waited > 0 {
+            ctx.log.info("fetch_rate_wait", &[("feed_id", feed.id.as_str()), ("url", url.as_str()), ("wait_ms", waited.to_string().as_str())]);
+        }
+        wait_ms += waited;
+        let req = FetchRequest { url: url.clone(), ..base.clone() };
+        let failure = match ctx.fetch(feed, &req) {
+            Ok(resp) => match resp.outcome() {
+                FetchOutcome::NewContent { .. } | FetchOutcome::NotModified => {
+                    health.record(url, true, resp.elapsed_ms, ctx.now);
+                    return Attempt { result: Ok(resp), url: url.clone(), wait_ms };
+                }
+                failure => failure,
+            },
+            Err(failure) => failure,
+        };
+        health.record(url, false, 0, ctx.now);
+        if let Some(next) = urls.get(i + 1) {
+            ctx.log.info("fetch_failover", &[("feed_id", feed.id.as_str()), ("from", url.as_str()), ("to", next.as_str())]);
+        }
+        failures.push(failure);
+    }
+    let url = urls.into_iter().next().unwrap_or_else(|| feed.url.clone());
+    Attempt { result: Err(combine_failures(&failures)), url, wait_ms }
+}
@@ -0,0 +1,250 @@
+// File: crates/adapters/cli/src/fetch_feed.rs
+// Purpose: Per-feed fetch step shared by `fetch` modes: build the request, fetch (failing over to
+//   mirrors), persist blob/validators/mirror health, fetch full articles and archive attachments when
+//   the feed opts in, and fold the outcome into streaks and deactivation.
+// Inputs/Outputs: FetchCtx (repo, fetcher, size limits, secrets, rate limiter, logger, clock) + one
+//   Feed; returns PersistStats (including rate-limit wait).
+// Invariants: All outcome bookkeeping goes through outcome::apply_outcome. Every response of a feed
+//   (feed, articles, attachments) passes FetchCtx::fetch, which enforces the feed's size limits.
+//   Every repo call of a feed goes through FetchCtx::tx, so its writes land together; the article
+//   and archive passes share one in-memory entry list instead of reading back uncommitted writes.
+
+use super::archive::archive_entries;
+use super::article::fetch_articles;
+use super::failover::fetch_with_failover;
+use super::log::Logger;
+use super::outcome::apply_outcome;
+use super::pipeline::PersistStats;
+use rssify_core::{
+    ArchiveRepo, BlobRepo, DeactivationPolicy, EntryRepo, Feed, FeedId, FeedRepo, FetchOutcome, FetchRequest, FetchResponse,
+    Fetcher, LastBlob, RepoError, ScheduleRepo, SecretRef, TxRepo,
+};
+use rssify_fetch::{RateLimiter, Secrets, SizeLimits, Warc};
+
+/// The repo traits a feed fetch goes through, all taking the backend's `TxRepo` transaction.
+/// `'static` because the lifetime-generic bounds need it; every backend owns its data.
+pub trait FetchRepo:
+    'static
+    + TxRepo
+    + for<'t> FeedRepo<Tx<'t> = <Self as TxRepo>::Tx<'t>>
+    + for<'t> EntryRepo<Tx<'t> = <Self as TxRepo>::Tx<'t>>
+    + for<'t> ScheduleRepo<Tx<'t> = <Self as TxRepo>::Tx<'t>>
+    + for<'t> BlobRepo<Tx<'t> = <Self as TxRepo>::Tx<'t>>
+    + for<'t> ArchiveRepo<Tx<'t> = <Self as TxRepo>::Tx<'t>>
+{
+}
+
+impl<R> FetchRepo for R where
+    R: 'static
+        + TxRepo
+        + for<'t> FeedRepo<Tx<'t> = <R as TxRepo>::Tx<'t>>
+        + for<'t> EntryRepo<Tx<'t> = <R as TxRepo>::Tx<'t>>
+        + for<'t> ScheduleRepo<Tx<'t> = <R as TxRepo>::Tx<'t>>
+        + for<'t> BlobRepo<Tx<'t> = <R as TxRepo>::Tx<'t>>
+        + for<'t> ArchiveRepo<Tx<'t> = <R as TxRepo>::Tx<'t>>
+{
+}
+
+/// Everything one feed fetch needs besides the feed itself.
+pub struct FetchCtx<'a, R: TxRepo, F> {
+    pub repo: &'a R,
+    /// The feed's transaction, committed by the caller once fetch_feed returns; `None` writes
+    /// straight through (dry-run previews, which write nothing).
+    pub tx: Option<&'a R::Tx<'a>>,
+    pub fetcher: &'a F,
+    /// Global body caps; `Feed.settings.max_bytes` overrides the download cap per feed.
+    pub limits: SizeLimits,
+    pub secrets: &'a Secrets,
+    /// Per-domain token buckets shared by every feed in the run.
+    pub limiter: &'a RateLimiter,
+    /// WARC output shared with the fetcher stack (`--warc`); used to link record ids.
+    pub warc: Option<&'a Warc>,
+    pub log: &'a Logger,
+    pub policy: DeactivationPolicy,
+    /// Unix seconds used for blob, streak and deactivation timestamps.
+    pub now: i64,
+}
+
+impl<R: TxRepo, F: Fetcher> FetchCtx<'_, R, F> {
+    /// Fetch `req` on behalf of `feed`; oversize and decompression-bomb bodies come back as
+    /// `PermanentFailure { hint: "too large" }`.
+    pub fn fetch(&self, feed: &Feed, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        self.fetcher.fetch(req).and_then(|resp| self.limits.for_feed(feed).apply(resp))
+    }
+}
+
+impl<R: TxRepo, F> FetchCtx<'_, R, F> {
+    /// Response record ids written to the WARC since the last call (empty without --warc).
+    pub fn warc_responses(&self) -> Vec<String> {
+        self.warc.map(Warc::take_responses).unwrap_or_default()
+    }
+}
+
+/// Per-run outcome counters reported in the summary.
+#[derive(Debug, Default)]
+pub struct Counts {
+    pub fetched: usize,
+    pub not_modified: usize,
+    pub failed: usize,
+    pub deactivated: usize,
+    /// Entries that gained full-article content.
+    pub articles: usize,
+    /// Attachments stored in the blob store.
+    pub attachments: usize,
+}
+
+impl Counts {
+    pub fn record(&mut self, stats: &PersistStats, deactivated: bool) {
+        if stats.failure_hint.is_some() {
+            self.failed += 1;
+        } else if stats.not_modified {
+            self.not_modified += 1;
+        } else {
+            self.fetched += 1;
+        }
+        if deactivated {
+            self.deactivated += 1;
+        }
+        self.articles += stats.articles_written as usize;
+        self.attachments += stats.attachments_archived as usize;
+    }
+}
+
+/// A fresh, active feed for a URL seen for the first time.
+pub fn new_feed(id: FeedId, url: &str) -> Feed {
+    Feed {
+        id,
+        url: url.to_string(),
+        title: None,
+        site_url: None,
+        etag: None,
+        last_modified: None,
+        active: true,
+        deactivated: None,
+        settings: Default::default(),
+    }
+}
+
+pub fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Request for `feed` with validators and resolved per-feed headers; errors name the missing secret.
+pub fn build_request(feed: &Feed, secrets: &Secrets) -> Result<FetchRequest, SecretRef> {
+    Ok(FetchRequest {
+        url: feed.url.clone(),
+        etag: feed.etag.clone(),
+        last_modified: feed.last_modified.clone(),
+        headers: feed.settings.request.resolve(|r| secrets.lookup(r))?,
+        ..FetchRequest::default()
+    })
+}
+
+/// Fetch one feed, keep its validators and last blob current and record the outcome.
+/// The bool is true when this outcome deactivated the feed.
+pub fn fetch_feed<R, F>(ctx: &FetchCtx<'_, R, F>, feed: &mut Feed) -> Result<(PersistStats, bool), RepoError>
+where
+    R: FetchRepo,
+    F: Fetcher,
+{
+    let mut stats = PersistStats {
+        feed: feed.id.clone(),
+        items_written: 0,
+        elapsed_ms: 0,
+        wait_ms: 0,
+        articles_written: 0,
+        attachments_archived: 0,
+        not_modified: false,
+        failure_hint: None,
+    };
+    let req = match build_request(feed, ctx.secrets) {
+        Ok(req) => req,
+        Err(missing) => {
+            // Configuration problem, not an upstream failure: skip without touching the streak.
+            ctx.log.warn("fetch_secret_missing", &[("feed_id", feed.id.as_str()), ("ref", missing.to_string().as_str())]);
+            stats.failure_hint = Some(format!("missing secret {}", missing));
+            return Ok((stats, false));
+        }
+    };
+    let mirrored = !feed.settings.mirrors.is_empty();
+    let mut health = if mirrored { ScheduleRepo::mirror_health(ctx.repo, ctx.tx, &feed.id)? } else { Default::default() };
+    ctx.warc_responses();
+    let attempt = fetch_with_failover(ctx, &req, feed, &mut health);
+    let warc_record = ctx.warc_responses().pop();
+    stats.wait_ms = attempt.wait_ms;
+    if mirrored {
+        ScheduleRepo::record_mirror_health(ctx.repo, ctx.tx, &feed.id, &health)?;
+    }
+    let outcome = match attempt.result {
+        Ok(resp) => {
+            stats.elapsed_ms = resp.elapsed_ms;
+            let outcome = resp.outcome();
+            if let FetchOutcome::NewContent { blob, .. } = &outcome {
+                feed.etag = resp.header("etag").map(str::to_string);
+                feed.last_modified = resp.header("last-modified").map(str::to_string);
+                FeedRepo::put(ctx.repo, ctx.tx, feed)?;
+                let last = LastBlob { blob: blob.clone(), headers: resp.headers, fetched_ts: ctx.now, warc_record };
+                FeedRepo::put_last_blob(ctx.repo, ctx.tx, &feed.id, &last)?;
+            }
+            outcome
+        }
+        Err(outcome) => outcome,
+    };
+    let new_content = matches!(outcome, FetchOutcome::NewContent { .. });
+    let mut entries = if new_content && (feed.settings.full_content || feed.settings.archive.is_some()) {
+        EntryRepo::list_by_feed(ctx.repo, ctx.tx, &feed.id)?
+    } else {
+        Vec::new()
+    };
+    if feed.settings.full_content && new_content {
+        let articles = fetch_articles(ctx, feed, &mut entries)?;
+        stats.articles_written = articles.written;
+        stats.wait_ms += articles.wait_ms;
+        ctx.log.info("fetch_articles", &[
+            ("feed_id", feed.id.as_str()),
+            ("written", articles.written.to_string().as_str()),
+            ("wait_ms", articles.wait_ms.to_string().as_str()),
+        ]);
+    }
+    if feed.settings.archive.is_some() && new_content {
+        let archived = archive_entries(ctx, feed, &entries)?;
+        stats.attachments_archived = archived.attachments;
+        stats.wait_ms += archived.wait_ms;
+        ctx.log.info("fetch_archive", &[
+            ("feed_id", feed.id.as_str()),
+            ("entries", archived.entries.to_string().as_str()),
+            ("attachments", archived.attachments.to_string().as_str()),
+            ("bytes", archived.bytes.to_string().as_str()),
+            ("wait_ms", archived.wait_ms.to_string().as_str()),
+        ]);
+    }
+    let label = match &outcome {
+        FetchOutcome::NewContent { .. } => "new_content",
+        FetchOutcome::NotModified => {
+            stats.not_modified = true;
+            "not_modified"
+        }
+        FetchOutcome::Gone => {
+            stats.failure_hint = Some("gone".to_string());
+            "failed"
+        }
+        FetchOutcome::TransientFailure { hint } | FetchOutcome::PermanentFailure { hint } => {
+            stats.failure_hint = Some(hint.clone().unwrap_or_else(|| "failed".to_string()));
+            "failed"
+        }
+    };
+    ctx.log.info("fetch_feed", &[
+        ("feed_id", feed.id.as_str()),
+        ("url", attempt.url.as_str()),
+        ("outcome", label),
+        ("elapsed_ms", stats.elapsed_ms.to_string().as_str()),
+        ("wait_ms", stats.wait_ms.to_string().as_str()),
+    ]);
+    let deactivated = apply_outcome(ctx.repo, ctx.tx, feed, &outcome, &ctx.policy, ctx.now)?;
+    if let Some(d) = &deactivated {
+        ctx.log.info("fetch_deactivated", &[("feed_id", feed.id.as_str()), ("reason", d.reason.to_string().as_str())]);
+    }
+    Ok((stats, deactivated.is_some()))
+}
@@ -0,0 +1,110 @@
+// File: crates/adapters/cli/src/fetch_run.rs
+// Purpose: The backend-independent body of `rssify fetch`: start or resume the run, register each
+//   target, fetch (or preview) active feeds through fetch_feed, checkpoint, finish the run.
+// Inputs/Outputs: Any repo implementing the core repo traits + RunInputs; returns RunTotals.
+// Invariants: With --dry-run nothing is written (registration, checkpoints, run manifest). Each
+//   feed's fetch writes go through one transaction, committed before its checkpoint.
+
+use super::cmd_fetch::{entries_since, FetchOpts};
+use super::dry_run::{not_fetched, preview_feed, FeedPreview};
+use super::fetch_feed::{fetch_feed, now_unix, Counts, FetchCtx, FetchRepo};
+use super::log::Logger;
+use super::register::{register, SeedOverrides};
+use super::run_state::{checkpoint, finish_run, start_run};
+use rssify_core::{DeactivationPolicy, Entry, FeedId, FeedRunStatus, Fetcher, RunRepo, TxRepo};
+use rssify_fetch::{RateLimiter, Secrets, SizeLimits, Warc};
+use std::collections::BTreeMap;
+
+/// Per-invocation state shared by every feed of the run.
+pub struct RunInputs<'a, F> {
+    pub opts: &'a FetchOpts,
+    /// Targets from seeds or --one; replaced by the pending ids when resuming.
+    pub ids: &'a [String],
+    pub overrides: &'a BTreeMap<String, SeedOverrides>,
+    /// Without a fetcher (no --replay) targets are only registered.
+    pub fetcher: Option<&'a F>,
+    pub secrets: &'a Secrets,
+    pub limiter: &'a RateLimiter,
+    pub warc: Option<&'a Warc>,
+    pub log: &'a Logger,
+    pub since: Option<i64>,
+}
+
+/// What the run did, for the summary.
+#[derive(Default)]
+pub struct RunTotals {
+    pub run_id: String,
+    /// Ids visited by this invocation.
+    pub ids: Vec<String>,
+    pub written: usize,
+    pub inactive: usize,
+    pub counts: Counts,
+    pub entries: Vec<Entry>,
+    pub previews: Vec<FeedPreview>,
+}
+
+/// Run every target against `repo`.
+pub fn run_feeds<R, F>(repo: &R, inp: &RunInputs<'_, F>) -> Result<RunTotals, Box<dyn std::error::Error>>
+where
+    R: FetchRepo + RunRepo,
+    F: Fetcher,
+{
+    let opts = inp.opts;
+    let write = !opts.dry_run;
+    let mut totals = RunTotals::default();
+    let (mut run, targets) = start_run(repo, inp.ids, opts.resume, write, now_unix(), inp.log)?;
+    let mut processed_ids: Vec<FeedId> = Vec::new();
+    for id in &targets {
+        let (mut feed, stored) = register(repo, id, opts.one.is_some(), inp.overrides.get(id), write)?;
+        totals.written += usize::from(!stored && write);
+        // --one is an explicit request, so it also runs deactivated feeds (without reactivating).
+        if !feed.active && opts.one.is_none() {
+            totals.inactive += 1;
+            inp.log.info("fetch_skip_inactive", &[("feed_id", id.as_str())]);
+            checkpoint(repo, &mut run, id, FeedRunStatus::Skipped, write)?;
+            continue;
+        }
+        let mut status = FeedRunStatus::Done;
+        match inp.fetcher {
+            Some(fetcher) => {
+                let tx = if write { Some(TxRepo::begin_tx(repo)?) } else { None };
+                let ctx = FetchCtx {
+                    repo,
+                    tx: tx.as_ref().map(R::reborrow),
+                    fetcher,
+                    limits: SizeLimits::default(),
+                    secrets: inp.secrets,
+                    limiter: inp.limiter,
+                    warc: inp.warc,
+                    log: inp.log,
+                    policy: DeactivationPolicy::default(),
+                    now: now_unix(),
+                };
+                if opts.dry_run {
+                    totals.previews.push(preview_feed(&ctx, &feed, stored)?);
+                } else {
+                    let (stats, deactivated) = fetch_feed(&ctx, &mut feed)?;
+                    if stats.failure_hint.is_some() {
+                        status = FeedRunStatus::Failed;
+                    }
+                    totals.counts.record(&stats, deactivated);
+                }
+                if let Some(tx) = tx {
+                    R::commit_tx(tx)?;
+                }
+            }
+            None if opts.dry_run => totals.previews.push(not_fetched(&feed, stored)),
+            None => {}
+        }
+        checkpoint(repo, &mut run, id, status, write)?;
+        processed_ids.push(feed.id);
+    }
+    finish_run(repo, &mut run, write, now_unix())?;
+    if let Some(since) = inp.since {
+        totals.entries = entries_since(repo, &processed_ids, since)?;
+    }
+    inp.log.debug("fetch_persist_done", &[("written", totals.written), ("feeds", targets.len())]);
+    totals.run_id = run.run_id;
+    totals.ids = targets;
+    Ok(totals)
+}
@@ -1,10 +1,12 @@
-//// File: crates/adapters/cli/src/log.rs
-//// Purpose: Minimal structured logging helper for the CLI without external deps.
-//// Inputs/Outputs: Logger writes key=value lines to stderr. Public API: Logger::new(level), info/warn/error/debug.
-//// Invariants: ASCII key names; values Display-escaped with basic whitespace compaction; never writes to stdout.
-//// Examples:
-////   let log = Logger::new(LogLevel::Info);
-////   log.info("fetch", &[("items", 5), ("status", "ok")]);
+// File: crates/adapters/cli/src/log.rs
+// Purpose: Minimal structured logging helper for the CLI without external deps.
+// Inputs/Outputs: Logger writes key=value lines to stderr. Public API: Logger::new(level), info/warn/error/debug.
+// Invariants: ASCII key names; values Display-escaped with basic whitespace compaction; never writes to stdout.
+//   Credentials are redacted before printing: sensitive keys, URL userinfo, sensitive query
+//   parameters and Basic/Bearer tokens (see redact).
+// Examples:
+//   let log = Logger::new(LogLevel::Info);
+//   log.info("fetch", &[("items", 5), ("status", "ok")]);
 
 use std::fmt::Display;
 use std::io::{self, Write};
@@ -78,9 +80,9 @@ impl Logger {
         let mut line = format!("ts={} level={} component={} op={}", ts_ms, lvl.as_str(), self.component, op);
         for (k, v) in kvs {
             line.push(' ');
-            line.push_str(*k);
+            line.push_str(k);
             line.push('=');
-            let mut s = format!("{}", v);
+            let mut s = redact(k, &format!("{}", v));
             if s.contains(char::is_whitespace) {
                 // compact whitespace to underscores to keep one-token values
                 s = s.split_whitespace().collect::<Vec<_>>().join("_");
@@ -91,3 +93,84 @@ impl Logger {
         let _ = writeln!(io::stderr(), "{}", line);
     }
 }
+
+/// Placeholder printed instead of a credential.
+pub const REDACTED: &str = "[redacted]";
+
+const SENSITIVE: [&str; 8] = [
+    "authorization", "cookie", "password", "passwd", "token", "secret", "api_key", "apikey",
+];
+
+fn is_sensitive(name: &str) -> bool {
+    let name = name.to_ascii_lowercase().replace('-', "_");
+    SENSITIVE.iter().any(|s| name.contains(s)) || name == "key" || name == "sig"
+}
+
+/// Strip credentials from a log value: whole value for sensitive keys, otherwise URL
+/// userinfo, sensitive query parameters and Basic/Bearer tokens inside the value.
+pub fn redact(key: &str, value: &str) -> String {
+    if is_sensitive(key) {
+        return REDACTED.to_string();
+    }
+    redact_auth_schemes(&redact_query(&redact_userinfo(value)))
+}
+
+fn redact_userinfo(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find("://") {
+        let (head, tail) = rest.split_at(i + 3);
+        out.push_str(head);
+        let end = tail
+            .find(|c: char| matches!(c, '/' | '?' | '#') || c.is_whitespace())
+            .unwrap_or(tail.len());
+        let authority = &tail[..end];
+        match authority.rfind('@') {
+            Some(at) => {
+                out.push_str(REDACTED);
+                out.push_str(&authority[at..]);
+            }
+            None => out.push_str(authority),
+        }
+        rest = &tail[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn redact_query(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find(['?', '&']) {
+        out.push_str(&rest[..=i]);
+        rest = &rest[i + 1..];
+        let param_end = rest.find(|c: char| c == '&' || c == '#' || c.is_whitespace()).unwrap_or(rest.len());
+        if let Some((name, _)) = rest[..param_end].split_once('=')
+            && is_sensitive(name)
+        {
+            out.push_str(name);
+            out.push('=');
+            out.push_str(REDACTED);
+            rest = &rest[param_end..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn redact_auth_schemes(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let Some(i) = ["bearer ", "basic "].iter().filter_map(|p| lower.find(p).map(|i| i + p.len())).min() else {
+            break;
+        };
+        out.push_str(&rest[..i]);
+        out.push_str(REDACTED);
+        rest = &rest[i..];
+        rest = &rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..];
+    }
+    out.push_str(rest);
+    out
+}
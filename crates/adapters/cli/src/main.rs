@@ -1,5 +1,5 @@
-//// File: crates/adapters/cli/src/main.rs
-//// Role: CLI entrypoint; uses pipeline, repo_fs, stats, spec, store, and a minimal logger.
+// File: crates/adapters/cli/src/main.rs
+// Role: CLI entrypoint; each subcommand's work lives in its cmd_* module.
 // Contract:
 // Purpose: Parse CLI args and dispatch to simple adapter functions; no business logic.
 // Inputs/Outputs: Reads flags/subcommands via clap; prints user-facing output (text or JSON) to stdout.
@@ -9,17 +9,34 @@
 
 use clap::{Parser, Subcommand};
 use serde_json::json;
-use std::str::FromStr;
 
+pub mod archive;
+pub mod article;
+pub mod backend;
+pub mod cmd_fetch;
+pub mod cmd_migrate;
+pub mod cmd_prune;
+pub mod cmd_reactivate;
+pub mod cmd_remove;
+pub mod cmd_reparse;
+pub mod cmd_search;
+pub mod cmd_stats;
+pub mod dry_run;
+pub mod failover;
+pub mod fetch_feed;
+pub mod fetch_run;
+pub mod outcome;
 pub mod pipeline;
+pub mod register;
+pub mod run_state;
 pub mod repo_fs;
 pub mod stats;
 pub mod spec;
 pub mod log;
 pub mod store;
+pub mod timefmt;
 
 use log::{LogLevel, Logger};
-use store::{resolve_store_spec, ENV_REPO};
 
 #[derive(Debug, Parser)]
 #[command(name = "rssify", version, about = "RSS toolkit CLI")]
@@ -30,11 +47,20 @@ pub struct Cli {
 
 #[derive(Debug, Subcommand)]
 pub enum Command {
-    /// Fetch feeds from a seed source (Phase 2/3: file-only, no network).
+    /// Fetch feeds from a seed source (no live network yet; use --replay for cassettes).
     Fetch {
         /// Seed file to read (JSON). Defaults to "feeds.json" if omitted.
-        #[arg(long)]
+        #[arg(long, conflicts_with = "one")]
         from: Option<String>,
+        /// Fetch only this feed (stored id or URL; unknown URLs are added) without reading seeds.
+        #[arg(long, value_name = "URL")]
+        one: Option<String>,
+        /// Print entries of the processed feeds newer than this time (unix seconds or RFC 3339).
+        #[arg(long, value_name = "TS")]
+        since: Option<String>,
+        /// Report per-feed would-be changes (entries, metadata, deactivation) without writing.
+        #[arg(long)]
+        dry_run: bool,
         /// Repository target (e.g., fs:<root>).
         ///
         /// Precedence: --store > env RSSIFY_REPO > fs:.
@@ -46,8 +72,38 @@ pub enum Command {
         /// Increase verbosity (-v, -vv).
         #[arg(short, long, action = clap::ArgAction::Count)]
         verbose: u8,
+        /// Serve responses from this cassette directory instead of the network.
+        #[arg(long, value_name = "DIR")]
+        replay: Option<String>,
+        /// Record every upstream response of this run into a cassette directory.
+        ///
+        /// Wraps the transport, so it currently re-records the interactions a --replay run
+        /// actually made (trimming or copying a cassette).
+        #[arg(long, value_name = "DIR", requires = "replay")]
+        record: Option<String>,
+        /// JSON secrets file for per-feed credentials (default: env RSSIFY_SECRETS).
+        #[arg(long, value_name = "PATH")]
+        secrets: Option<String>,
+        /// JSON per-domain request ceilings (token buckets keyed by registrable domain).
+        #[arg(long, value_name = "PATH")]
+        rate_limits: Option<String>,
+        /// Continue the last incomplete run, skipping feeds it already visited.
+        #[arg(long, conflicts_with = "one")]
+        resume: bool,
+        /// Write every request/response pair into rotating .warc.gz files in this directory.
+        #[arg(long, value_name = "DIR", conflicts_with = "dry_run")]
+        warc: Option<String>,
+        /// Refuse URLs and redirect hops that resolve to private, loopback or metadata addresses.
+        #[arg(long)]
+        ssrf_guard: bool,
+        /// Exempt this host or host:port from --ssrf-guard's address check (repeatable).
+        #[arg(long, value_name = "HOST", requires = "ssrf_guard")]
+        ssrf_allow: Vec<String>,
+        /// User agent robots.txt rules are matched against (default: rssify/<version>).
+        #[arg(long, value_name = "UA")]
+        user_agent: Option<String>,
     },
-    /// Show repository stats (filesystem only in this phase).
+    /// Show repository stats: feeds, entries, deactivated feeds and (fs) storage size.
     Stats {
         /// Repository target (e.g., fs:<root>).
         ///
@@ -58,6 +114,110 @@ pub enum Command {
         #[arg(long)]
         json: bool,
     },
+    /// Put a deactivated feed back into rotation.
+    Reactivate {
+        /// Feed id as stored in the repo (a feed URL is also accepted).
+        feed: String,
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Re-read stored last_blob payloads without refetching and report on them.
+    Reparse {
+        /// Only this feed id (or feed URL); defaults to every feed.
+        #[arg(long)]
+        feed: Option<String>,
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Back up the repo, then upgrade it to the version this binary writes.
+    Migrate {
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+        /// Directory for the pre-migration backup (default: next to the repo).
+        #[arg(long)]
+        backup_dir: Option<String>,
+    },
+    /// Unsubscribe a feed: delete it with its entries, archive records and schedule state.
+    Remove {
+        /// Feed id as stored in the repo (a feed URL is also accepted).
+        feed: String,
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Delete old entries per feed; starred entries are always kept.
+    Prune {
+        /// Keep the newest N entries of each feed.
+        #[arg(long, value_name = "N")]
+        keep_last: Option<usize>,
+        /// Keep entries published within the last D days.
+        #[arg(long, value_name = "D")]
+        max_age_days: Option<u32>,
+        /// Only this feed id (or feed URL); defaults to every feed.
+        #[arg(long)]
+        feed: Option<String>,
+        /// Report what would be deleted without deleting.
+        #[arg(long)]
+        dry_run: bool,
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Full-text search: words, "phrases", feed:, author: and after: filters; BM25-ranked.
+    Search {
+        /// Query, e.g. `rust "async runtime" author:jane after:2024-01-01`.
+        query: String,
+        /// Show at most N hits.
+        #[arg(long, value_name = "N", default_value_t = cmd_search::DEFAULT_LIMIT)]
+        limit: usize,
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rebuild the search index from the stored entries.
+    Reindex {
+        /// Repository target (e.g., fs:<root>).
+        ///
+        /// Precedence: --store > env RSSIFY_REPO > fs:.
+        #[arg(long)]
+        store: Option<String>,
+        /// Emit machine-readable JSON.
+        #[arg(long)]
+        json: bool,
+    },
     /// Stubs kept for later phases.
     Import {
         #[arg(long)]
@@ -89,108 +249,35 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     match cli.command {
-        Command::Fetch { from, store, json, verbose } => {
-            let log = Logger::new(LogLevel::from_verbosity(verbose));
-            let seed_path = from.unwrap_or_else(|| "feeds.json".to_string());
-
-            // Resolve store spec with precedence: flag > env > default.
-            let resolved = resolve_store_spec(store.clone());
-            log.info("fetch_start", &[
-                ("from", seed_path.as_str()),
-                ("store", resolved.as_str()),
-                ("env", ENV_REPO),
-            ]);
-
-            let ids = match pipeline::load_feed_seeds(&seed_path) {
-                Ok(v) => v,
-                Err(e) => {
-                    Logger::new(LogLevel::Error).error("fetch_parse_error", &[("error", format!("{}", e))]);
-                    return Err(format!("failed to parse seeds: {}", e).into());
-                }
-            };
-
-            // Open repo via RepoSpec.
-            let spec = spec::RepoSpec::from_str(&resolved)
-                .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
-
-            let mut written = 0usize;
-            match spec.kind {
-                spec::RepoKind::Fs => {
-                    let repo = rssify_repo_fs::FsRepo::open(&spec.target);
-                    use rssify_core::{Feed, FeedId, FeedRepo};
-                    for id in &ids {
-                        let fid = FeedId::new(id.clone());
-                        let feed = Feed {
-                            id: fid,
-                            url: id.clone(),
-                            title: None,
-                            site_url: None,
-                            etag: None,
-                            last_modified: None,
-                            active: true,
-                        };
-                        if FeedRepo::put(&repo, None, &feed).is_ok() {
-                            written += 1;
-                        }
-                    }
-                    log.debug("fetch_persist_done", &[("written", written), ("feeds", ids.len())]);
-                }
-                other => {
-                    Logger::new(LogLevel::Error).error("fetch_repo_unsupported", &[("kind", other.as_str())]);
-                    return Err(format!("repo kind '{}' is not supported in this phase", other.as_str()).into());
-                }
-            }
-
-            let summary = json!({
-                "feeds_total": ids.len(),
-                "feeds_processed": ids.len(),
-                "items_parsed": 0,
-                "items_written": written
-            });
-
-            if json {
-                println!("{}", serde_json::to_string_pretty(&summary)?);
-            } else {
-                println!(
-                    "Processed {}/{} feeds; items parsed=0, written={}",
-                    ids.len(),
-                    ids.len(),
-                    written
-                );
-            }
-
-            log.info("fetch_done", &[("feeds", ids.len()), ("written", written)]);
+        Command::Fetch {
+            from, one, since, dry_run, store, json, verbose, replay, record, secrets, rate_limits, resume, warc,
+            ssrf_guard, ssrf_allow, user_agent,
+        } => {
+            cmd_fetch::run(cmd_fetch::FetchOpts {
+                from, one, since, dry_run, store, json, verbose, replay, record, secrets, rate_limits, resume, warc,
+                ssrf_guard, ssrf_allow, user_agent,
+            })?;
         }
-        Command::Stats { store, json } => {
-            let log = Logger::new(LogLevel::Warn);
-
-            // Resolve store spec with precedence: flag > env > default.
-            let resolved = resolve_store_spec(store);
-            let spec = spec::RepoSpec::from_str(&resolved)
-                .map_err(|e| format!("invalid --store: {} ({})", resolved, e))?;
-
-            match spec.kind {
-                spec::RepoKind::Fs => {
-                    let s = stats::stats_fs(&spec.target)?;
-                    if json {
-                        println!(
-                            "{}",
-                            serde_json::to_string_pretty(&json!({
-                                "feeds": s.feeds,
-                                "entries": s.entries
-                            }))?
-                        );
-                    } else {
-                        println!("feeds={} entries={}", s.feeds, s.entries);
-                    }
-                    log.info("stats_done", &[("feeds", s.feeds), ("entries", s.entries)]);
-                }
-                other => {
-                    Logger::new(LogLevel::Error).error("stats_repo_unsupported", &[("kind", other.as_str())]);
-                    return Err(format!("repo kind '{}' is not supported in this phase", other.as_str()).into());
-                }
-            }
+        Command::Stats { store, json } => cmd_stats::run(cmd_stats::StatsOpts { store, json })?,
+        Command::Reactivate { feed, store, json } => {
+            cmd_reactivate::run(cmd_reactivate::ReactivateOpts { feed, store, json })?;
+        }
+        Command::Reparse { feed, store, json } => {
+            cmd_reparse::run(cmd_reparse::ReparseOpts { feed, store, json })?;
+        }
+        Command::Migrate { store, json, backup_dir } => {
+            cmd_migrate::run(cmd_migrate::MigrateOpts { store, json, backup_dir })?;
+        }
+        Command::Remove { feed, store, json } => {
+            cmd_remove::run(cmd_remove::RemoveOpts { feed, store, json })?;
+        }
+        Command::Prune { keep_last, max_age_days, feed, dry_run, store, json } => {
+            cmd_prune::run(cmd_prune::PruneOpts { store, json, keep_last, max_age_days, feed, dry_run })?;
+        }
+        Command::Search { query, limit, store, json } => {
+            cmd_search::run_search(cmd_search::SearchOpts { query, limit, store, json })?;
         }
+        Command::Reindex { store, json } => cmd_search::run_reindex(cmd_search::ReindexOpts { store, json })?,
         Command::Import { json, .. } => {
             if json {
                 println!("{}", json!({"status": "not_implemented", "op": "import"}));
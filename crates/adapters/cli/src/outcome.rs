@@ -0,0 +1,55 @@
+/*
+Module: rssify_cli::outcome
+Purpose: Persist the effect of one fetch outcome on a feed (streak, last ok, deactivation)
+Public API surface: apply_outcome, reactivate
+Invariants: Decisions come from rssify_core::DeactivationPolicy; this file only loads/stores
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use rssify_core::{
+    Deactivation, DeactivationPolicy, FailureStreak, Feed, FeedId, FeedRepo, FetchOutcome, RepoError, ScheduleRepo,
+};
+
+/// Fold `outcome` into the feed's stored failure streak and deactivate it when the
+/// policy says so, through `tx` when given. Successes also record `now` as the last ok fetch
+/// time. Returns the new deactivation, if this outcome caused one.
+pub fn apply_outcome<'t, R>(
+    repo: &'t R,
+    tx: Option<&'t <R as FeedRepo>::Tx<'t>>,
+    feed: &mut Feed,
+    outcome: &FetchOutcome,
+    policy: &DeactivationPolicy,
+    now: i64,
+) -> Result<Option<Deactivation>, RepoError>
+where
+    R: FeedRepo + ScheduleRepo<Tx<'t> = <R as FeedRepo>::Tx<'t>>,
+{
+    let streak = ScheduleRepo::failure_streak(repo, tx, &feed.id)?.observe(outcome, now);
+    ScheduleRepo::record_failure_streak(repo, tx, &feed.id, &streak)?;
+
+    if matches!(outcome, FetchOutcome::NotModified | FetchOutcome::NewContent { .. }) {
+        ScheduleRepo::record_fetch_ts(repo, tx, &feed.id, now)?;
+        return Ok(None);
+    }
+
+    let Some(reason) = policy.evaluate(outcome, &streak, now) else {
+        return Ok(None);
+    };
+    feed.deactivate(reason, now);
+    FeedRepo::put(repo, tx, feed)?;
+    Ok(feed.deactivated.clone())
+}
+
+/// Put a feed (stored id, else URL) back into rotation and clear its failure streak.
+/// Returns the feed id and whether it was already active.
+pub fn reactivate<R: FeedRepo + ScheduleRepo>(repo: &R, feed: &str) -> Result<(FeedId, bool), String> {
+    let mut found = FeedRepo::get(repo, None, &FeedId::new(feed))
+        .or_else(|_| FeedRepo::get(repo, None, &FeedId::from_url(feed)))
+        .map_err(|_| format!("feed not found: {}", feed))?;
+    let was_active = found.active;
+    found.reactivate();
+    FeedRepo::put(repo, None, &found).map_err(|e| e.to_string())?;
+    ScheduleRepo::record_failure_streak(repo, None, &found.id, &FailureStreak::default()).map_err(|e| e.to_string())?;
+    Ok((found.id, was_active))
+}
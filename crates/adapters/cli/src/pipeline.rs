@@ -1,24 +1,30 @@
-//// File: crates/adapters/cli/src/pipeline.rs
-//// Purpose: Seed parsing and Phase-2/3 test-facing pipeline skeleton.
-//// Public API (stable for tests):
-////   - Types: FetchSummary, FeedSeed, FeedMetaDelta, PersistStats
-////   - Functions: load_feed_seeds(path), fetch_from_file(path)
-//// Accepted seed JSON formats for load_feed_seeds:
-////   1) ["https://a", "guid:FEED 01"]
-////   2) [{"id":"X","url":"..."}, {"url":"..."}]  (prefers id, else url, else guid)
-////   3) {"seeds": [ ... either 1 or 2 ... ]}
-//// Notes:
-////   - No network or repo writes here; this is a pure adapter helper used by tests.
-////   - Keep this file <= 300 LOC; split when adding real fetching in later phases.
+// File: crates/adapters/cli/src/pipeline.rs
+// Purpose: Seed parsing and Phase-2/3 test-facing pipeline skeleton.
+// Public API (stable for tests):
+//   - Types: FetchSummary, FeedSeed, FeedMetaDelta, PersistStats
+//   - Functions: load_feed_seeds(path), load_seed_requests(path), load_seed_mirrors(path),
+//     load_seed_key(path, key), fetch_from_file(path)
+// Accepted seed JSON formats for load_feed_seeds:
+//   1) ["https://a", "guid:FEED 01"]
+//   2) [{"id":"X","url":"..."}, {"url":"..."}]  (prefers id, else url, else guid)
+//   3) {"seeds": [ ... either 1 or 2 ... ]}
+// Object seeds may carry "request": RequestSettings (headers, auth, user_agent, cookie);
+// credentials inside are references ({"env": ..} / {"secret": ..}), never values.
+// Object seeds may carry "mirrors": ["https://alt", ...], alternate URLs for the same feed,
+// and "full_content": true to fetch each entry's article page.
+// Notes:
+//   - No network or repo writes here; this is a pure adapter helper used by tests.
+//   - Keep this file <= 300 LOC; split when adding real fetching in later phases.
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io;
 use std::path::Path;
 
 // Core types referenced by tests
-use rssify_core::FeedId;
+use rssify_core::{FeedId, RequestSettings};
 
 /// Summary returned by fetch routines (Phase 2: no network).
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
@@ -51,6 +57,15 @@ pub struct PersistStats {
     pub feed: FeedId,
     pub items_written: u32,
     pub elapsed_ms: u64,
+    /// Time spent waiting on per-domain rate limits before the request(s).
+    #[serde(default)]
+    pub wait_ms: u64,
+    /// Entries that gained full-article content (feeds with settings.full_content).
+    #[serde(default)]
+    pub articles_written: u32,
+    /// Attachments stored by the archiving pass (feeds with settings.archive).
+    #[serde(default)]
+    pub attachments_archived: u32,
     pub not_modified: bool,
     pub failure_hint: Option<String>,
 }
@@ -87,6 +102,47 @@ pub fn load_feed_seeds<P: AsRef<Path>>(path: P) -> Result<Vec<String>, PipelineE
     extract_seeds_from_value(v)
 }
 
+/// Request settings of object seeds that carry a "request" key, keyed by the same id
+/// `load_feed_seeds` returns for that seed.
+pub fn load_seed_requests<P: AsRef<Path>>(
+    path: P,
+) -> Result<BTreeMap<String, RequestSettings>, PipelineError> {
+    load_seed_key(path, "request")
+}
+
+/// Mirror URLs of object seeds that carry a "mirrors" key, keyed like `load_seed_requests`.
+pub fn load_seed_mirrors<P: AsRef<Path>>(
+    path: P,
+) -> Result<BTreeMap<String, Vec<String>>, PipelineError> {
+    load_seed_key(path, "mirrors")
+}
+
+/// Value of `key` in each object seed that carries it, keyed like `load_feed_seeds` ids.
+pub fn load_seed_key<P: AsRef<Path>, T: serde::de::DeserializeOwned>(
+    path: P,
+    key: &str,
+) -> Result<BTreeMap<String, T>, PipelineError> {
+    let data = fs::read_to_string(&path)?;
+    let v: Value =
+        serde_json::from_str(&data).map_err(|e| PipelineError::Json(e.to_string()))?;
+    let ids = extract_seeds_from_value(v.clone())?;
+    let items = match &v {
+        Value::Object(map) => map.get("seeds"),
+        _ => Some(&v),
+    }
+    .and_then(Value::as_array);
+    let mut out = BTreeMap::new();
+    for (i, (item, id)) in items.into_iter().flatten().zip(ids).enumerate() {
+        if let Some(raw) = item.get(key) {
+            let parsed = serde_json::from_value(raw.clone()).map_err(|e| {
+                PipelineError::Structure(format!("item {} has an invalid '{}': {}", i, key, e))
+            })?;
+            out.insert(id, parsed);
+        }
+    }
+    Ok(out)
+}
+
 /// Phase-2 stub: parse seeds and return a count-only summary.
 /// Tests expect items_* fields to equal the number of seeds.
 pub fn fetch_from_file<P: AsRef<Path>>(path: P) -> Result<FetchSummary, PipelineError> {
@@ -0,0 +1,100 @@
+// File: crates/adapters/cli/src/register.rs
+// Purpose: Turn fetch targets into stored feeds: gather per-seed overrides (request settings, mirrors,
+//   full_content, archive, max_bytes) and create or update the feed each target names.
+// Inputs/Outputs: seed file path / FeedRepo + target id; returns the feed and whether it was stored.
+// Invariants: Seeds are the source of truth for the settings they carry; settings a seed omits keep
+//   their stored values. Nothing is written when `write` is unset (dry runs).
+
+use super::fetch_feed::new_feed;
+use super::pipeline::{self, PipelineError};
+use rssify_core::{ArchiveSettings, Feed, FeedId, FeedRepo, FeedSettings, RepoError, RequestSettings};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Settings one seed object overrides; `None` leaves the stored value alone.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedOverrides {
+    pub request: Option<RequestSettings>,
+    pub mirrors: Option<Vec<String>>,
+    pub full_content: Option<bool>,
+    pub archive: Option<ArchiveSettings>,
+    pub max_bytes: Option<u64>,
+}
+
+impl SeedOverrides {
+    fn apply(&self, settings: &mut FeedSettings) {
+        if let Some(req) = &self.request {
+            settings.request = req.clone();
+        }
+        if let Some(m) = &self.mirrors {
+            settings.mirrors = m.clone();
+        }
+        if let Some(full) = self.full_content {
+            settings.full_content = full;
+        }
+        if let Some(archive) = &self.archive {
+            settings.archive = Some(archive.clone());
+        }
+        if let Some(max) = self.max_bytes {
+            settings.max_bytes = Some(max);
+        }
+    }
+}
+
+/// Overrides of every object seed that carries any, keyed like `pipeline::load_feed_seeds` ids.
+pub fn load_overrides<P: AsRef<Path>>(path: P) -> Result<BTreeMap<String, SeedOverrides>, PipelineError> {
+    let mut out: BTreeMap<String, SeedOverrides> = BTreeMap::new();
+    for (id, req) in pipeline::load_seed_requests(&path)? {
+        out.entry(id).or_default().request = Some(req);
+    }
+    for (id, mirrors) in pipeline::load_seed_mirrors(&path)? {
+        out.entry(id).or_default().mirrors = Some(mirrors);
+    }
+    for (id, full) in pipeline::load_seed_key::<_, bool>(&path, "full_content")? {
+        out.entry(id).or_default().full_content = Some(full);
+    }
+    for (id, archive) in pipeline::load_seed_key::<_, ArchiveSettings>(&path, "archive")? {
+        out.entry(id).or_default().archive = Some(archive);
+    }
+    for (id, max) in pipeline::load_seed_key::<_, u64>(&path, "max_bytes")? {
+        out.entry(id).or_default().max_bytes = Some(max);
+    }
+    Ok(out)
+}
+
+/// Load the stored feed for `id` or create it, then apply the seed's overrides.
+/// `by_url` also accepts the `url:` form of an existing feed (used by --one).
+/// Returns the feed and whether it was already stored.
+pub fn register<R: FeedRepo>(
+    repo: &R,
+    id: &str,
+    by_url: bool,
+    seed: Option<&SeedOverrides>,
+    write: bool,
+) -> Result<(Feed, bool), RepoError> {
+    let fid = FeedId::new(id);
+    // Known feeds keep their stored state (validators, deactivation).
+    let existing = FeedRepo::get(repo, None, &fid)
+        .or_else(|e| if by_url { FeedRepo::get(repo, None, &FeedId::from_url(id)) } else { Err(e) });
+    let (mut feed, stored) = match existing {
+        Ok(feed) => (feed, true),
+        Err(_) => {
+            let feed = new_feed(fid, id);
+            if write {
+                FeedRepo::put(repo, None, &feed)?;
+            }
+            (feed, false)
+        }
+    };
+    if let Some(seed) = seed {
+        let mut settings = feed.settings.clone();
+        seed.apply(&mut settings);
+        if settings != feed.settings {
+            feed.settings = settings;
+            if write {
+                FeedRepo::put(repo, None, &feed)?;
+            }
+        }
+    }
+    Ok((feed, stored))
+}
@@ -4,7 +4,7 @@ Purpose: Filesystem repository layout helpers (pure; no I/O)
 Public API surface: FsPaths::{feed_dir, feed_json, last_blob, entry_json}
 Invariants:
 - Filenames are derived only from canonical ID strings (FeedId/EntryId::as_str).
-- Path components are URL-safe via a local percent-encoder.
+- Path components use rssify_repo_fs::encode_component, the encoding FsRepo writes with.
 - No filesystem access; string building only.
 
 Layout expected by tests:
@@ -17,25 +17,7 @@ Layout expected by tests:
 
 use std::path::Path;
 use rssify_core::{EntryId, FeedId};
-
-fn urlsafe_component(s: &str) -> String {
-    fn is_safe(b: u8) -> bool {
-        matches!(
-            b,
-            b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' | b'-' | b'_' | b'.'
-        )
-    }
-    let mut out = String::with_capacity(s.len());
-    for &b in s.as_bytes() {
-        if is_safe(b) {
-            out.push(b as char);
-        } else {
-            out.push('%');
-            out.push_str(&format!("{:02X}", b));
-        }
-    }
-    out
-}
+use rssify_repo_fs::encode_component;
 
 fn join_to_string(parts: &[&str]) -> String {
     let mut p = Path::new(parts[0]).to_path_buf();
@@ -50,7 +32,7 @@ pub struct FsPaths;
 impl FsPaths {
     /// <root>/feeds/<feed_id>
     pub fn feed_dir(root: &str, feed: &FeedId) -> String {
-        join_to_string(&[root, "feeds", &urlsafe_component(feed.as_str())])
+        join_to_string(&[root, "feeds", &encode_component(feed.as_str())])
     }
 
     /// <root>/feeds/<feed_id>/feed.json
@@ -58,16 +40,6 @@ impl FsPaths {
         join_to_string(&[&Self::feed_dir(root, feed), "feed.json"])
     }
 
-    /// Deprecated in tests (kept for completeness): <root>/entries/by_id/<entry>.json
-    pub fn entry_id_file(root: &str, entry: &EntryId) -> String {
-        join_to_string(&[
-            root,
-            "entries",
-            "by_id",
-            &format!("{}.json", urlsafe_component(entry.as_str())),
-        ])
-    }
-
     /// <root>/feeds/<feed_id>/entries
     pub fn entry_by_feed_dir(root: &str, feed: &FeedId) -> String {
         join_to_string(&[
@@ -80,7 +52,7 @@ impl FsPaths {
     pub fn entry_by_feed_file(root: &str, feed: &FeedId, entry: &EntryId) -> String {
         join_to_string(&[
             &Self::entry_by_feed_dir(root, feed),
-            &format!("{}.json", urlsafe_component(entry.as_str())),
+            &format!("{}.json", encode_component(entry.as_str())),
         ])
     }
 
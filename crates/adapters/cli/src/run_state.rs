@@ -0,0 +1,68 @@
+// File: crates/adapters/cli/src/run_state.rs
+// Purpose: Fetch run manifests for `fetch`: start a run (or resume the last incomplete one),
+//   checkpoint each visited feed, and mark the run finished.
+// Inputs/Outputs: RunRepo + target ids; returns the RunManifest and the ids still to visit.
+// Invariants: With `write` unset (dry runs) nothing is persisted; resume never reorders feeds.
+
+use super::log::Logger;
+use rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo};
+
+/// Run id: start time plus process id, unique per repo in practice and sortable by time.
+pub fn new_run_id(started_at: i64) -> String {
+    format!("{}-{}", started_at, std::process::id())
+}
+
+/// Resume the latest incomplete run when asked and one exists, else start a new run over `ids`.
+/// Returns the manifest and the ids to visit in this invocation.
+pub fn start_run<R: RunRepo>(
+    repo: &R,
+    ids: &[String],
+    resume: bool,
+    write: bool,
+    now: i64,
+    log: &Logger,
+) -> Result<(RunManifest, Vec<String>), RepoError> {
+    if resume {
+        match RunRepo::latest_incomplete_run(repo, None)? {
+            Some(run) => {
+                let pending = run.pending();
+                log.info("fetch_resume", &[
+                    ("run_id", run.run_id.as_str()),
+                    ("pending", pending.len().to_string().as_str()),
+                    ("total", run.feeds.len().to_string().as_str()),
+                ]);
+                return Ok((run, pending));
+            }
+            None => log.info("fetch_resume_none", &[("reason", "no incomplete run; starting a new one")]),
+        }
+    }
+    let run = RunManifest::new(new_run_id(now), now, ids);
+    if write {
+        RunRepo::put_run(repo, None, &run)?;
+    }
+    Ok((run, ids.to_vec()))
+}
+
+/// Record one feed's status in memory and, unless dry, durably in the repo.
+pub fn checkpoint<R: RunRepo>(
+    repo: &R,
+    run: &mut RunManifest,
+    id: &str,
+    status: FeedRunStatus,
+    write: bool,
+) -> Result<(), RepoError> {
+    run.mark(id, status);
+    if write {
+        RunRepo::record_run_feed(repo, None, &run.run_id, id, status)?;
+    }
+    Ok(())
+}
+
+/// Mark the run complete so `--resume` no longer picks it up.
+pub fn finish_run<R: RunRepo>(repo: &R, run: &mut RunManifest, write: bool, now: i64) -> Result<(), RepoError> {
+    run.finish(now);
+    if write {
+        RunRepo::put_run(repo, None, run)?;
+    }
+    Ok(())
+}
@@ -15,6 +15,8 @@ use std::str::FromStr;
 pub enum RepoKind {
     Fs,
     Sqlite,
+    /// In-process store; the target (a JSON snapshot path) is optional.
+    Mem,
 }
 
 impl RepoKind {
@@ -22,6 +24,7 @@ impl RepoKind {
         match prefix.to_ascii_lowercase().as_str() {
             "fs" => Some(Self::Fs),
             "sqlite" => Some(Self::Sqlite),
+            "mem" => Some(Self::Mem),
             _ => None,
         }
     }
@@ -30,15 +33,19 @@ impl RepoKind {
         match self {
             RepoKind::Fs => "fs",
             RepoKind::Sqlite => "sqlite",
+            RepoKind::Mem => "mem",
         }
     }
 }
 
-/// Parsed repository specification, e.g. "fs:/path", "sqlite:/path/to.db".
+/// Parsed repository specification, e.g. "fs:/path", "fs:/path?compress=zstd", "sqlite:/path/to.db",
+/// "mem:" or "mem:snap.json".
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RepoSpec {
     pub kind: RepoKind,
     pub target: String,
+    /// `key=value` pairs after a `?` (fs only), in spec order; the backend validates them.
+    pub options: Vec<(String, String)>,
 }
 
 impl RepoSpec {
@@ -49,14 +56,34 @@ impl RepoSpec {
             return Err("missing ':' separator".into());
         };
         let kind = RepoKind::from_prefix(prefix).ok_or_else(|| "unknown repo kind".to_string())?;
-        if rest.is_empty() {
+        let (target, options) = match rest.split_once('?') {
+            Some((target, query)) if kind == RepoKind::Fs => (target, parse_options(query)?),
+            _ => (rest, Vec::new()),
+        };
+        if target.is_empty() && kind != RepoKind::Mem {
             return Err("empty repo target".into());
         }
         Ok(Self {
             kind,
-            target: rest.to_string(),
+            target: target.to_string(),
+            options,
         })
     }
+
+    /// The value of option `key`, if the spec sets it (the last one wins).
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+fn parse_options(query: &str) -> Result<Vec<(String, String)>, String> {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, v)) if !k.is_empty() => Ok((k.to_ascii_lowercase(), v.to_string())),
+            _ => Err(format!("malformed option {:?} (expected key=value)", pair)),
+        })
+        .collect()
 }
 
 impl FromStr for RepoSpec {
@@ -68,6 +95,10 @@ impl FromStr for RepoSpec {
 
 impl Display for RepoSpec {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}:{}", self.kind.as_str(), self.target)
+        write!(f, "{}:{}", self.kind.as_str(), self.target)?;
+        for (i, (k, v)) in self.options.iter().enumerate() {
+            write!(f, "{}{}={}", if i == 0 { '?' } else { '&' }, k, v)?;
+        }
+        Ok(())
     }
 }
@@ -1,17 +1,23 @@
-//// File: crates/adapters/cli/src/stats.rs
-//// Purpose: Read-only repo stats for filesystem backend.
-//// Inputs/Outputs: stats_fs(root) -> StatsSummary { feeds, entries }.
-//// Invariants:
-////   - A "feed" is counted iff <root>/feeds/<feed>/feed.json exists.
-////   - "entries" are counted from the canonical per-feed layout:
-////       <root>/feeds/<feed>/entries/<entry>.json
-////     and (for backward-compatibility) from deprecated:
-////       <root>/entries/by_id/<entry>.json
-//// Examples:
-////   let s = stats_fs("./data")?;
-////   println!("feeds={} entries={}", s.feeds, s.entries);
-//// Task: Keep pure read-only; no external deps; under 200 LOC.
+// File: crates/adapters/cli/src/stats.rs
+// Purpose: Read-only repo stats: path-based for the filesystem backend, trait-based otherwise.
+// Inputs/Outputs: stats_fs(root) / stats_repo(repo) -> StatsSummary { feeds, entries, deactivated,
+//   storage }.
+// Invariants:
+//   - A "feed" is counted iff <root>/feeds/<feed>/feed.json exists.
+//   - A feed is listed as deactivated iff its feed.json has "active": false.
+//   - "entries" are counted from the canonical per-feed layout:
+//       <root>/feeds/<feed>/entries/<entry>.json
+//     and (for backward-compatibility) from deprecated:
+//       <root>/entries/by_id/<entry>.json
+//   - "storage" (fs only) totals every file under the root as stored and as it reads back
+//     uncompressed; records may be zstd-compressed (`fs:<root>?compress=zstd`).
+// Examples:
+//   let s = stats_fs("./data")?;
+//   println!("feeds={} entries={}", s.feeds, s.entries);
+// Task: Keep pure read-only; no external deps beyond the fs repo's record reader; under 200 LOC.
 
+use rssify_core::{Deactivation, EntryRepo, FeedRepo, RepoError};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
@@ -19,6 +25,37 @@ use std::path::{Path, PathBuf};
 pub struct StatsSummary {
     pub feeds: usize,
     pub entries: usize,
+    pub deactivated: Vec<DeactivatedFeed>,
+    /// Size of the repo on disk; `None` for backends without a tree of files.
+    pub storage: Option<StorageSize>,
+}
+
+/// Bytes a repo takes as stored versus uncompressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StorageSize {
+    pub on_disk_bytes: u64,
+    pub logical_bytes: u64,
+}
+
+/// A feed taken out of rotation, as listed by `rssify stats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeactivatedFeed {
+    pub id: String,
+    pub reason: Option<String>,
+    pub ts: Option<i64>,
+}
+
+/// Lenient view of feed.json: only the fields stats needs.
+#[derive(Deserialize)]
+struct FeedStatus {
+    id: Option<String>,
+    #[serde(default = "default_active")]
+    active: bool,
+    deactivated: Option<Deactivation>,
+}
+
+fn default_active() -> bool {
+    true
 }
 
 pub fn stats_fs(root: &str) -> io::Result<StatsSummary> {
@@ -27,6 +64,7 @@ pub fn stats_fs(root: &str) -> io::Result<StatsSummary> {
 
     // Count feeds: <root>/feeds/<feed>/feed.json
     let mut feed_count = 0usize;
+    let mut deactivated = Vec::new();
     if feeds_root.exists() {
         for ent in fs::read_dir(&feeds_root)? {
             let ent = ent?;
@@ -34,10 +72,14 @@ pub fn stats_fs(root: &str) -> io::Result<StatsSummary> {
                 let candidate = ent.path().join("feed.json");
                 if candidate.exists() {
                     feed_count += 1;
+                    if let Some(d) = read_deactivated(&candidate, &ent.file_name().to_string_lossy()) {
+                        deactivated.push(d);
+                    }
                 }
             }
         }
     }
+    deactivated.sort_by(|a, b| a.id.cmp(&b.id));
 
     // Count entries in canonical per-feed layout.
     // <root>/feeds/<feed>/entries/*.json
@@ -53,14 +95,50 @@ pub fn stats_fs(root: &str) -> io::Result<StatsSummary> {
         }
     }
 
-    // Also count legacy flat entries directory if present.
+    // Also count a layout-1 repo's flat entries directory (not yet `rssify migrate`d).
     if legacy_entries_root.exists() {
         entry_count += count_json_files_in_dir(&legacy_entries_root)?;
     }
 
+    let usage = rssify_repo_fs::disk_usage(Path::new(root)).map_err(io::Error::other)?;
     Ok(StatsSummary {
         feeds: feed_count,
         entries: entry_count,
+        deactivated,
+        storage: Some(StorageSize { on_disk_bytes: usage.on_disk_bytes, logical_bytes: usage.logical_bytes }),
+    })
+}
+
+/// Stats through the repo traits (backends without an on-disk tree to walk, e.g. sqlite).
+pub fn stats_repo<R: FeedRepo + EntryRepo>(repo: &R) -> Result<StatsSummary, RepoError> {
+    let feeds = FeedRepo::list(repo, None)?;
+    let mut entries = 0usize;
+    let mut deactivated = Vec::new();
+    for f in &feeds {
+        entries += EntryRepo::count_by_feed(repo, None, &f.id)?;
+        if !f.active {
+            deactivated.push(DeactivatedFeed {
+                id: f.id.as_str().to_string(),
+                reason: f.deactivated.as_ref().map(|d| d.reason.to_string()),
+                ts: f.deactivated.as_ref().map(|d| d.ts),
+            });
+        }
+    }
+    deactivated.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(StatsSummary { feeds: feeds.len(), entries, deactivated, storage: None })
+}
+
+/// Unreadable or malformed feed.json files are treated as active.
+fn read_deactivated(path: &Path, dir_name: &str) -> Option<DeactivatedFeed> {
+    let data = rssify_repo_fs::read_record(path).ok()?;
+    let status: FeedStatus = serde_json::from_slice(&data).ok()?;
+    if status.active {
+        return None;
+    }
+    Some(DeactivatedFeed {
+        id: status.id.unwrap_or_else(|| dir_name.to_string()),
+        reason: status.deactivated.as_ref().map(|d| d.reason.to_string()),
+        ts: status.deactivated.map(|d| d.ts),
     })
 }
 
@@ -71,15 +149,14 @@ fn count_json_files_in_dir(dir: &PathBuf) -> io::Result<usize> {
     let mut n = 0usize;
     for ent in fs::read_dir(dir)? {
         let ent = ent?;
-        if ent.file_type()?.is_file() {
-            if ent
+        if ent.file_type()?.is_file()
+            && ent
                 .path()
                 .extension()
                 .map(|e| e.to_string_lossy().eq_ignore_ascii_case("json"))
                 .unwrap_or(false)
-            {
-                n += 1;
-            }
+        {
+            n += 1;
         }
     }
     Ok(n)
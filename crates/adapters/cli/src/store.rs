@@ -1,7 +1,7 @@
-//// File: crates/adapters/cli/src/store.rs
-//// Purpose: Resolve repository spec from CLI flag, env var, or default.
-//// Precedence: --store flag > RSSIFY_REPO env > "fs:."
-//// Notes: Keep pure and dependency-free. Provide an injectable resolver for tests.
+// File: crates/adapters/cli/src/store.rs
+// Purpose: Resolve repository spec from CLI flag, env var, or default.
+// Precedence: --store flag > RSSIFY_REPO env > "fs:."
+// Notes: Keep pure and dependency-free. Provide an injectable resolver for tests.
 
 pub const ENV_REPO: &str = "RSSIFY_REPO";
 
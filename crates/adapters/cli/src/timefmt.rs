@@ -0,0 +1,66 @@
+// File: crates/adapters/cli/src/timefmt.rs
+// Purpose: Parse user-supplied timestamps (CLI flags) into unix seconds without extra deps.
+// Accepted: unix seconds ("1700000000"), "YYYY-MM-DD", "YYYY-MM-DDTHH:MM:SS" with "Z" or "+HH:MM"/"-HH:MM".
+// Invariants: Pure; dates are proleptic Gregorian, times without an offset are UTC.
+
+/// Parse `s` into unix seconds.
+pub fn parse_ts(s: &str) -> Result<i64, String> {
+    let s = s.trim();
+    if let Ok(n) = s.parse::<i64>() {
+        return Ok(n);
+    }
+    let bad = || format!("invalid timestamp '{}': use unix seconds or RFC 3339", s);
+    let (date, time) = match s.split_once(['T', 't', ' ']) {
+        Some((d, t)) => (d, Some(t)),
+        None => (s, None),
+    };
+    let mut dp = date.splitn(3, '-').map(|p| p.parse::<i64>().map_err(|_| bad()));
+    let (y, m, d) = (
+        dp.next().ok_or_else(bad)??,
+        dp.next().ok_or_else(bad)??,
+        dp.next().ok_or_else(bad)??,
+    );
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return Err(bad());
+    }
+    let mut secs = days_from_civil(y, m, d) * 86_400;
+    if let Some(t) = time {
+        let (clock, offset) = split_offset(t).ok_or_else(bad)?;
+        let mut tp = clock.split(':').map(|p| p.split('.').next().unwrap_or("").parse::<i64>().map_err(|_| bad()));
+        let h = tp.next().ok_or_else(bad)??;
+        let mi = tp.next().ok_or_else(bad)??;
+        let sec = tp.next().transpose()?.unwrap_or(0);
+        if h > 23 || mi > 59 || sec > 60 {
+            return Err(bad());
+        }
+        secs += h * 3600 + mi * 60 + sec - offset;
+    }
+    Ok(secs)
+}
+
+/// Split "HH:MM:SS[.fff](Z|±HH:MM)" into the clock part and the offset in seconds.
+fn split_offset(t: &str) -> Option<(&str, i64)> {
+    if let Some(clock) = t.strip_suffix(['Z', 'z']) {
+        return Some((clock, 0));
+    }
+    match t.rfind(['+', '-']) {
+        Some(i) => {
+            let (clock, off) = t.split_at(i);
+            let sign = if off.starts_with('-') { -1 } else { 1 };
+            let (oh, om) = off[1..].split_once(':').unwrap_or((&off[1..], "0"));
+            Some((clock, sign * (oh.parse::<i64>().ok()? * 3600 + om.parse::<i64>().ok()? * 60)))
+        }
+        None => Some((t, 0)),
+    }
+}
+
+/// Days since 1970-01-01 (Howard Hinnant's algorithm).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -3,10 +3,13 @@ Module: rssify_cli::test::cli
 Purpose: Validate CLI parsing for subcommands and flags (no business logic)
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/main.rs"]
 mod bin_main;
 
-use bin_main::{parse_from, Command};
+use bin_main::{parse_from, Cli, Command};
+use clap::Parser;
 
 #[test]
 fn parses_fetch_with_flags() {
@@ -21,7 +24,7 @@ fn parses_fetch_with_flags() {
         "-vv",
     ]);
     match cli.command {
-        Command::Fetch { from, store, json, verbose } => {
+        Command::Fetch { from, store, json, verbose, .. } => {
             assert_eq!(from.as_deref(), Some("feeds.json"));
             assert_eq!(store.as_deref(), Some("fs:/tmp"));
             assert!(json);
@@ -71,3 +74,114 @@ fn parses_import_and_add() {
     }
 }
 
+
+#[test]
+fn parses_reactivate() {
+    let cli = parse_from(["rssify", "reactivate", "url:https://ex.com/feed", "--store", "fs:/tmp", "--json"]);
+    match cli.command {
+        Command::Reactivate { feed, store, json } => {
+            assert_eq!(feed, "url:https://ex.com/feed");
+            assert_eq!(store.as_deref(), Some("fs:/tmp"));
+            assert!(json);
+        }
+        _ => panic!("expected reactivate"),
+    }
+}
+
+#[test]
+fn parses_reparse() {
+    let cli = parse_from(["rssify", "reparse", "--feed", "url:https://ex.com/feed", "--json"]);
+    match cli.command {
+        Command::Reparse { feed, store, json } => {
+            assert_eq!(feed.as_deref(), Some("url:https://ex.com/feed"));
+            assert!(store.is_none());
+            assert!(json);
+        }
+        _ => panic!("expected reparse"),
+    }
+}
+
+#[test]
+fn parses_fetch_one_and_since() {
+    let cli = parse_from(["rssify", "fetch", "--one", "https://ex.com/feed", "--since", "2024-01-01"]);
+    match cli.command {
+        Command::Fetch { one, since, from, .. } => {
+            assert_eq!(one.as_deref(), Some("https://ex.com/feed"));
+            assert_eq!(since.as_deref(), Some("2024-01-01"));
+            assert!(from.is_none());
+        }
+        _ => panic!("expected fetch"),
+    }
+    let both = Cli::try_parse_from(["rssify", "fetch", "--one", "u", "--from", "feeds.json"]);
+    assert!(both.is_err(), "--one and --from conflict");
+    let resume_one = Cli::try_parse_from(["rssify", "fetch", "--one", "u", "--resume"]);
+    assert!(resume_one.is_err(), "--one and --resume conflict");
+    let warc_dry = Cli::try_parse_from(["rssify", "fetch", "--dry-run", "--warc", "w"]);
+    assert!(warc_dry.is_err(), "--warc and --dry-run conflict");
+    let record = Cli::try_parse_from(["rssify", "fetch", "--record", "c"]);
+    assert!(record.is_err(), "--record requires --replay");
+    match parse_from(["rssify", "fetch", "--replay", "a", "--record", "b"]).command {
+        Command::Fetch { replay, record, .. } => {
+            assert_eq!((replay.as_deref(), record.as_deref()), (Some("a"), Some("b")));
+        }
+        _ => panic!("expected fetch"),
+    }
+}
+
+#[test]
+fn parses_fetch_ssrf_guard_and_user_agent() {
+    let cli = parse_from(["rssify", "fetch", "--ssrf-guard", "--ssrf-allow", "localhost", "--ssrf-allow", "10.0.0.2:8080"]);
+    match cli.command {
+        Command::Fetch { ssrf_guard, ssrf_allow, .. } => {
+            assert!(ssrf_guard);
+            assert_eq!(ssrf_allow, ["localhost", "10.0.0.2:8080"]);
+        }
+        _ => panic!("expected fetch"),
+    }
+    let cli = parse_from(["rssify", "fetch", "--user-agent", "MyReader/1.0"]);
+    assert!(matches!(cli.command, Command::Fetch { user_agent: Some(ua), .. } if ua == "MyReader/1.0"));
+    let allow_only = Cli::try_parse_from(["rssify", "fetch", "--ssrf-allow", "localhost"]);
+    assert!(allow_only.is_err(), "--ssrf-allow requires --ssrf-guard");
+}
+
+#[test]
+fn parses_remove_and_prune() {
+    let cli = parse_from(["rssify", "remove", "https://ex.com/feed", "--json"]);
+    match cli.command {
+        Command::Remove { feed, json, .. } => {
+            assert_eq!(feed, "https://ex.com/feed");
+            assert!(json);
+        }
+        _ => panic!("expected remove"),
+    }
+    let cli = parse_from(["rssify", "prune", "--keep-last", "50", "--max-age-days", "30", "--dry-run"]);
+    match cli.command {
+        Command::Prune { keep_last, max_age_days, feed, dry_run, .. } => {
+            assert_eq!((keep_last, max_age_days), (Some(50), Some(30)));
+            assert!(feed.is_none());
+            assert!(dry_run);
+        }
+        _ => panic!("expected prune"),
+    }
+}
+
+#[test]
+fn parses_search_and_reindex() {
+    let cli = parse_from(["rssify", "search", "rust author:jane", "--json"]);
+    match cli.command {
+        Command::Search { query, limit, json, .. } => {
+            assert_eq!(query, "rust author:jane");
+            assert_eq!(limit, 20);
+            assert!(json);
+        }
+        _ => panic!("expected search"),
+    }
+    let cli = parse_from(["rssify", "reindex", "--store", "sqlite:./r.db"]);
+    match cli.command {
+        Command::Reindex { store, json } => {
+            assert_eq!(store.as_deref(), Some("sqlite:./r.db"));
+            assert!(!json);
+        }
+        _ => panic!("expected reindex"),
+    }
+}
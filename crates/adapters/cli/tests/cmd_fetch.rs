@@ -0,0 +1,644 @@
+/*
+Module: rssify_cli::tests::cmd_fetch
+Purpose: Validate `fetch --replay` runs feeds through a cassette and persists outcomes and blobs
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/main.rs"]
+mod bin_main;
+
+mod support;
+
+use bin_main::cmd_fetch::{run, FetchOpts};
+use rssify_core::{FeedId, FeedRepo, FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use rssify_fetch::RecordFetcher;
+use rssify_repo_fs::FsRepo;
+use rssify_repo_mem::{MemRepo, Snapshot};
+use rssify_repo_sqlite::SqliteRepo;
+use std::path::Path;
+use support::temp_root;
+
+const OK_URL: &str = "https://ok.test/rss";
+const GONE_URL: &str = "https://gone.test/rss";
+const ARTICLE_URL: &str = "https://ok.test/posts/1";
+const ARTICLE: &str = "<html><body><nav>Menu</nav><article>\
+    <p>The whole post, not just the teaser, with enough words to count as prose.</p></article></body></html>";
+const IMAGE_URL: &str = "https://ok.test/img/a.png";
+const IMAGE: &[u8] = b"\x89PNG fake";
+
+struct Upstream;
+
+impl Fetcher for Upstream {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        if req.url == ARTICLE_URL {
+            let headers = vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())];
+            return Ok(FetchResponse { status: 200, headers, body: ARTICLE.as_bytes().to_vec(), elapsed_ms: 5 });
+        }
+        if req.url == IMAGE_URL {
+            let headers = vec![("Content-Type".to_string(), "image/png".to_string())];
+            return Ok(FetchResponse { status: 200, headers, body: IMAGE.to_vec(), elapsed_ms: 2 });
+        }
+        let (status, headers) = if req.url == OK_URL {
+            (200, vec![("ETag".to_string(), "\"abc\"".to_string())])
+        } else {
+            (410, Vec::new())
+        };
+        Ok(FetchResponse { status, headers, body: b"<rss/>".to_vec(), elapsed_ms: 3 })
+    }
+}
+
+fn opts(seeds: &Path, store: &Path) -> FetchOpts {
+    FetchOpts {
+        from: Some(seeds.to_string_lossy().into_owned()),
+        store: Some(format!("fs:{}", store.display())),
+        json: true,
+        ..FetchOpts::default()
+    }
+}
+
+#[test]
+fn replay_updates_validators_and_deactivates_gone_feeds() {
+    let root = temp_root("replay");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\", \"{GONE_URL}\"]")).unwrap();
+    let store = root.join("data");
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("replay run");
+
+    let repo = FsRepo::open(&store);
+    let ok = FeedRepo::get(&repo, None, &FeedId::new(OK_URL)).unwrap();
+    assert_eq!(ok.etag.as_deref(), Some("\"abc\""));
+    assert!(ok.active);
+    let gone = FeedRepo::get(&repo, None, &FeedId::new(GONE_URL)).unwrap();
+    assert!(!gone.active);
+
+    let blob = FeedRepo::last_blob(&repo, None, &ok.id).unwrap().expect("blob stored");
+    assert_eq!(blob.blob.bytes, b"<rss/>");
+    assert_eq!(FeedRepo::last_blob(&repo, None, &gone.id).unwrap(), None);
+}
+
+#[test]
+fn replay_into_a_sqlite_store_persists_feeds_and_reports_stats() {
+    let root = temp_root("sqlite");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\", \"{GONE_URL}\"]")).unwrap();
+    let db = root.join("db").join("rssify.db");
+
+    let mut o = opts(&seeds, &root);
+    o.store = Some(format!("sqlite:{}", db.display()));
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("replay run");
+
+    let repo = SqliteRepo::open(&db).unwrap();
+    let ok = FeedRepo::get(&repo, None, &FeedId::new(OK_URL)).unwrap();
+    assert_eq!(ok.etag.as_deref(), Some("\"abc\""));
+    let blob = FeedRepo::last_blob(&repo, None, &ok.id).unwrap().expect("blob stored");
+    assert_eq!(blob.blob.bytes, b"<rss/>");
+
+    let stats = bin_main::stats::stats_repo(&repo).unwrap();
+    assert_eq!(stats.feeds, 2);
+    assert_eq!(stats.deactivated.len(), 1);
+    assert_eq!(stats.deactivated[0].id, GONE_URL);
+    assert!(!root.join("feeds").exists(), "nothing written to the fs layout");
+}
+
+#[test]
+fn replay_into_a_mem_store_touches_disk_only_for_the_snapshot() {
+    let root = temp_root("mem");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    recorder.fetch(&FetchRequest { url: OK_URL.to_string(), ..FetchRequest::default() }).unwrap();
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\"]")).unwrap();
+
+    let mut o = opts(&seeds, &root);
+    o.store = Some("mem:".into());
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("ephemeral run");
+    let mut names: Vec<_> = std::fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name()).collect();
+    names.sort();
+    assert_eq!(names, ["cassette", "feeds.json"], "bare mem: writes nothing");
+
+    let snap = root.join("out").join("snapshot.json");
+    let mut o = opts(&seeds, &root);
+    o.store = Some(format!("mem:{}", snap.display()));
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("snapshot run");
+    let snapshot: Snapshot = serde_json::from_slice(&std::fs::read(&snap).unwrap()).unwrap();
+    let repo = MemRepo::from_snapshot(snapshot);
+    let ok = FeedRepo::get(&repo, None, &FeedId::new(OK_URL)).unwrap();
+    assert_eq!(ok.etag.as_deref(), Some("\"abc\""));
+    assert_eq!(bin_main::stats::stats_repo(&repo).unwrap().feeds, 1);
+}
+
+#[test]
+fn record_copies_the_interactions_of_a_replayed_run() {
+    let root = temp_root("record");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\"]")).unwrap();
+
+    let copy = root.join("copy");
+    let mut o = opts(&seeds, &root.join("data"));
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    o.record = Some(copy.to_string_lossy().into_owned());
+    run(o).expect("recorded run");
+    let key = rssify_fetch::cassette_key(OK_URL);
+    assert!(copy.join(format!("{key}.json")).exists());
+    assert!(!copy.join(format!("{}.json", rssify_fetch::cassette_key(GONE_URL))).exists(), "only what the run fetched");
+
+    let mut o = opts(&seeds, &root.join("again"));
+    o.replay = Some(copy.to_string_lossy().into_owned());
+    run(o).expect("replay of the recording");
+    let repo = FsRepo::open(root.join("again"));
+    let ok = FeedRepo::get(&repo, None, &FeedId::new(OK_URL)).unwrap();
+    assert_eq!(ok.etag.as_deref(), Some("\"abc\""));
+
+    let mut o = opts(&seeds, &root.join("data"));
+    o.record = Some(copy.to_string_lossy().into_owned());
+    assert!(run(o).unwrap_err().to_string().contains("--replay"));
+}
+
+#[test]
+fn seed_request_settings_are_stored_and_missing_secrets_skip_the_feed() {
+    let root = temp_root("secrets");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    recorder.fetch(&FetchRequest { url: OK_URL.to_string(), ..FetchRequest::default() }).unwrap();
+    let seeds = root.join("feeds.json");
+    std::fs::write(
+        &seeds,
+        format!(r#"[{{"url": "{OK_URL}", "request": {{"cookie": {{"secret": "absent"}}}}}}]"#),
+    )
+    .unwrap();
+    let store = root.join("data");
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    let repo = FsRepo::open(&store);
+    let feed = FeedRepo::get(&repo, None, &FeedId::new(OK_URL)).unwrap();
+    assert!(feed.settings.request.cookie.is_some());
+    assert!(feed.active);
+    assert_eq!(FeedRepo::last_blob(&repo, None, &feed.id).unwrap(), None);
+}
+
+#[test]
+fn one_fetches_a_single_ad_hoc_or_deactivated_feed_without_seeds() {
+    let root = temp_root("one");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let store = root.join("data");
+    let one = |url: &str| FetchOpts {
+        one: Some(url.to_string()),
+        store: Some(format!("fs:{}", store.display())),
+        replay: Some(cassette.to_string_lossy().into_owned()),
+        ..FetchOpts::default()
+    };
+
+    run(one(OK_URL)).expect("ad hoc feed");
+    let repo = FsRepo::open(&store);
+    assert!(FeedRepo::last_blob(&repo, None, &FeedId::new(OK_URL)).unwrap().is_some());
+    assert_eq!(FeedRepo::list(&repo, None).unwrap().len(), 1, "only the one feed is touched");
+
+    run(one(GONE_URL)).expect("gone feed");
+    run(one(GONE_URL)).expect("deactivated feeds still run with --one");
+    let streak = rssify_core::ScheduleRepo::failure_streak(&repo, None, &FeedId::new(GONE_URL)).unwrap();
+    assert_eq!(streak.permanent, 2);
+}
+
+#[test]
+fn entries_since_filters_and_orders_by_time() {
+    use bin_main::cmd_fetch::entries_since;
+    use rssify_core::{Entry, EntryRepo};
+    let repo = FsRepo::open(temp_root("since"));
+    let feed = FeedId::new(OK_URL);
+    let entry = |id: &str, published: Option<i64>, updated: Option<i64>| Entry { published_ts: published, updated_ts: updated, ..support::entry(&feed, id) };
+    for e in [
+        entry("old", Some(100), None),
+        entry("new", Some(300), None),
+        entry("updated-only", None, Some(250)),
+        entry("undated", None, None),
+    ] {
+        EntryRepo::upsert(&repo, None, &e).unwrap();
+    }
+    let got: Vec<String> = entries_since(&repo, &[feed], 200)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.id.as_str().to_string())
+        .collect();
+    assert_eq!(got, ["updated-only", "new"]);
+}
+
+#[test]
+fn dry_run_writes_nothing() {
+    let root = temp_root("dry");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\", \"{GONE_URL}\"]")).unwrap();
+    let store = root.join("data");
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    o.dry_run = true;
+    run(o).expect("dry run");
+    assert!(!store.exists(), "dry run must not create the repo");
+}
+
+#[test]
+fn preview_reports_meta_deltas_and_would_be_deactivation() {
+    use bin_main::dry_run::preview_feed;
+    use bin_main::fetch_feed::{new_feed, FetchCtx};
+    use bin_main::log::{LogLevel, Logger};
+    use rssify_fetch::{RateLimiter, ReplayFetcher, Secrets};
+
+    let root = temp_root("preview");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let repo = FsRepo::open(root.join("data"));
+    let mut ok = new_feed(FeedId::new(OK_URL), OK_URL);
+    ok.etag = Some("\"old\"".to_string());
+    FeedRepo::put(&repo, None, &ok).unwrap();
+    let snapshot = || {
+        let dir = root.join("data/feeds").read_dir().unwrap().next().unwrap().unwrap().path();
+        std::fs::read(dir.join("feed.json")).unwrap()
+    };
+    let before = snapshot();
+
+    let replay = ReplayFetcher::open(&cassette).unwrap();
+    let secrets = Secrets::default();
+    let limiter = RateLimiter::new(Default::default());
+    let log = Logger::new(LogLevel::Error);
+    let ctx = FetchCtx {
+        repo: &repo,
+        tx: None,
+        fetcher: &replay,
+        limits: Default::default(),
+        secrets: &secrets,
+        limiter: &limiter,
+        warc: None,
+        log: &log,
+        policy: Default::default(),
+        now: 1_000,
+    };
+    let p = preview_feed(&ctx, &ok, true).unwrap();
+    assert_eq!(p.outcome, "new_content");
+    assert!(!p.would_register);
+    let etag = &p.meta["etag"];
+    assert_eq!((etag.from.as_deref(), etag.to.as_deref()), (Some("\"old\""), Some("\"abc\"")));
+    assert!(!p.meta.contains_key("last_modified"));
+
+    let gone = new_feed(FeedId::new(GONE_URL), GONE_URL);
+    let p = preview_feed(&ctx, &gone, false).unwrap();
+    assert!(p.would_register);
+    assert_eq!(p.would_deactivate.as_deref(), Some("gone"));
+
+    assert_eq!(before, snapshot());
+    assert_eq!(FeedRepo::list(&repo, None).unwrap().len(), 1);
+    assert_eq!(FeedRepo::last_blob(&repo, None, &ok.id).unwrap(), None);
+}
+
+#[test]
+fn preview_counts_entries_the_fetch_would_update_without_storing_them() {
+    use bin_main::dry_run::preview_feed;
+    use bin_main::fetch_feed::{new_feed, FetchCtx};
+    use bin_main::log::{LogLevel, Logger};
+    use rssify_core::{EntryDiff, EntryId, EntryRepo};
+    use rssify_fetch::{RateLimiter, ReplayFetcher, Secrets};
+
+    let root = temp_root("preview-entries");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, ARTICLE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let repo = FsRepo::open(root.join("data"));
+    let mut ok = new_feed(FeedId::new(OK_URL), OK_URL);
+    ok.settings.full_content = true;
+    FeedRepo::put(&repo, None, &ok).unwrap();
+    let entry = |id: &str, url: Option<&str>| rssify_core::Entry { url: url.map(str::to_string), published_ts: Some(1), summary: Some("Teaser".into()), ..support::entry(&ok.id, id) };
+    EntryRepo::upsert(&repo, None, &entry("truncated", Some(ARTICLE_URL))).unwrap();
+    EntryRepo::upsert(&repo, None, &entry("linkless", None)).unwrap();
+
+    let replay = ReplayFetcher::open(&cassette).unwrap();
+    let secrets = Secrets::default();
+    let limiter = RateLimiter::new(Default::default());
+    let log = Logger::new(LogLevel::Error);
+    let ctx = FetchCtx {
+        repo: &repo,
+        tx: None,
+        fetcher: &replay,
+        limits: Default::default(),
+        secrets: &secrets,
+        limiter: &limiter,
+        warc: None,
+        log: &log,
+        policy: Default::default(),
+        now: 1_000,
+    };
+    let p = preview_feed(&ctx, &ok, true).unwrap();
+    assert_eq!(p.entries, EntryDiff { new: 0, updated: 1, unchanged: 1 });
+    assert_eq!(EntryRepo::get(&repo, None, &EntryId::new("truncated")).unwrap().content, None, "nothing stored");
+
+    let unknown = new_feed(FeedId::new(GONE_URL), GONE_URL);
+    assert_eq!(preview_feed(&ctx, &unknown, false).unwrap().entries, EntryDiff::default());
+}
+
+#[test]
+fn failing_primary_fails_over_to_mirror_and_records_health() {
+    let root = temp_root("mirror");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!(r#"[{{"url": "{GONE_URL}", "mirrors": ["{OK_URL}"]}}]"#)).unwrap();
+    let store = root.join("data");
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    let repo = FsRepo::open(&store);
+    let feed = FeedRepo::get(&repo, None, &FeedId::new(GONE_URL)).unwrap();
+    assert!(feed.active, "a working mirror keeps the feed alive");
+    assert_eq!(feed.settings.mirrors, [OK_URL]);
+    assert_eq!(feed.etag.as_deref(), Some("\"abc\""));
+    assert!(FeedRepo::last_blob(&repo, None, &feed.id).unwrap().is_some(), "blob stored under the primary id");
+    assert_eq!(FeedRepo::list(&repo, None).unwrap().len(), 1);
+
+    let health = rssify_core::ScheduleRepo::mirror_health(&repo, None, &feed.id).unwrap();
+    assert_eq!(health.urls[GONE_URL].consecutive_failures, 1);
+    assert_eq!(health.urls[OK_URL].successes, 1);
+    assert_eq!(health.order(&feed.urls())[0], OK_URL);
+}
+
+#[test]
+fn invalid_rate_limits_file_is_an_error() {
+    let root = temp_root("limits");
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\"]")).unwrap();
+    let limits = root.join("limits.json");
+    std::fs::write(&limits, r#"{"domains": {"ok.test": {"burst": 2}}}"#).unwrap();
+    let mut o = opts(&seeds, &root.join("data"));
+    o.rate_limits = Some(limits.to_string_lossy().into_owned());
+    let err = run(o).unwrap_err().to_string();
+    assert!(err.contains("--rate-limits"), "{err}");
+}
+
+#[test]
+fn resume_visits_only_pending_feeds_of_the_interrupted_run() {
+    use rssify_core::{FeedRunStatus, RunManifest, RunRepo};
+    let root = temp_root("resume");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, GONE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!("[\"{OK_URL}\", \"{GONE_URL}\"]")).unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    // A run killed after its first feed.
+    let ids = [OK_URL.to_string(), GONE_URL.to_string()];
+    RunRepo::put_run(&repo, None, &RunManifest::new("1-1", 1, &ids)).unwrap();
+    RunRepo::record_run_feed(&repo, None, "1-1", OK_URL, FeedRunStatus::Done).unwrap();
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    o.resume = true;
+    run(o).expect("resume run");
+
+    assert_eq!(FeedRepo::last_blob(&repo, None, &FeedId::new(OK_URL)).unwrap(), None, "done feed not refetched");
+    assert!(!FeedRepo::get(&repo, None, &FeedId::new(GONE_URL)).unwrap().active);
+    let finished = RunRepo::get_run(&repo, None, "1-1").unwrap();
+    assert!(finished.is_complete());
+    assert_eq!(finished.feeds[1].status, FeedRunStatus::Failed);
+    assert!(RunRepo::latest_incomplete_run(&repo, None).unwrap().is_none());
+}
+
+#[test]
+fn full_content_feeds_store_extracted_articles_for_entries_without_content() {
+    use rssify_core::{ContentBlob, ContentKind, Entry, EntryId, EntryRepo};
+    let root = temp_root("article");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, ARTICLE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!(r#"[{{"url": "{OK_URL}", "full_content": true}}]"#)).unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    let feed = FeedId::new(OK_URL);
+    let entry = |id: &str, url: Option<&str>, content: Option<ContentBlob>| Entry {
+        url: url.map(str::to_string),
+        published_ts: Some(1),
+        summary: Some("Teaser".into()),
+        content,
+        ..support::entry(&feed, id)
+    };
+    let shipped = ContentBlob { kind: ContentKind::Html, bytes: b"<p>from the feed</p>".to_vec() };
+    EntryRepo::upsert(&repo, None, &entry("truncated", Some(ARTICLE_URL), None)).unwrap();
+    EntryRepo::upsert(&repo, None, &entry("full", Some(ARTICLE_URL), Some(shipped.clone()))).unwrap();
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    assert!(FeedRepo::get(&repo, None, &feed).unwrap().settings.full_content);
+    let got = EntryRepo::get(&repo, None, &EntryId::new("truncated")).unwrap();
+    let content = got.content.expect("article stored");
+    assert_eq!(content.kind, ContentKind::Html);
+    assert!(!String::from_utf8(content.bytes).unwrap().contains("Menu"));
+    assert_eq!(got.content_text.as_deref(), Some("The whole post, not just the teaser, with enough words to count as prose."));
+    assert_eq!(got.summary.as_deref(), Some("Teaser"));
+    let kept = EntryRepo::get(&repo, None, &EntryId::new("full")).unwrap();
+    assert_eq!((kept.content, kept.content_text), (Some(shipped), None));
+}
+
+#[test]
+fn a_feeds_article_and_archive_writes_commit_together() {
+    use rssify_core::{ArchiveRepo, Entry, EntryRepo};
+    let root = temp_root("feed-tx");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, ARTICLE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!(r#"[{{"url": "{OK_URL}", "full_content": true, "archive": {{}}}}]"#)).unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    let entry = Entry { url: Some(ARTICLE_URL.to_string()), published_ts: Some(1), ..support::entry(&FeedId::new(OK_URL), "post") };
+    EntryRepo::upsert(&repo, None, &entry).unwrap();
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    let archived = ArchiveRepo::archive(&repo, None, &entry.id).unwrap().expect("entry archived");
+    let html = String::from_utf8(archived.content.expect("archived content").bytes).unwrap();
+    assert!(html.contains("The whole post"), "the archive pass sees the article fetched in the same tx");
+    assert!(EntryRepo::get(&repo, None, &entry.id).unwrap().content.is_some());
+    let journals = std::fs::read_dir(store.join(".journal")).map_or(0, |rd| rd.count());
+    assert_eq!(journals, 0, "every feed tx committed");
+}
+
+#[test]
+fn archive_feeds_store_images_and_skip_disallowed_or_oversized_enclosures() {
+    use rssify_core::{
+        blob_path, ArchiveRepo, BlobRepo, ContentBlob, ContentKind, Enclosure, Entry, EntryRepo,
+    };
+    let root = temp_root("archive");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, IMAGE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(
+        &seeds,
+        format!(r#"[{{"url": "{OK_URL}", "archive": {{"quota_bytes": 1000, "mime_allow": ["image/*"]}}}}]"#),
+    )
+    .unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    let enclosure = |url: &str, mime: &str, length: u64| Enclosure {
+        url: url.to_string(),
+        mime: Some(mime.to_string()),
+        length: Some(length),
+    };
+    let entry = Entry {
+        url: Some(ARTICLE_URL.to_string()),
+        published_ts: Some(1),
+        content: Some(ContentBlob {
+            kind: ContentKind::Html,
+            bytes: b"<p>Look <img src=\"/img/a.png\" srcset=\"/img/a@2x.png 2x\"></p>".to_vec(),
+        }),
+        enclosures: vec![
+            enclosure("https://cdn.test/ep.mp3", "audio/mpeg", 10),
+            enclosure("https://cdn.test/huge.png", "image/png", 5_000_000),
+        ],
+        ..support::entry(&FeedId::new(OK_URL), "post")
+    };
+    EntryRepo::upsert(&repo, None, &entry).unwrap();
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    let archived = ArchiveRepo::archive(&repo, None, &entry.id).unwrap().expect("entry archived");
+    assert_eq!(archived.attachments.len(), 1);
+    let image = &archived.attachments[0];
+    assert_eq!((image.url.as_str(), image.mime.as_str(), image.size), (IMAGE_URL, "image/png", IMAGE.len() as u64));
+    assert_eq!(BlobRepo::get_blob(&repo, None, &image.sha256).unwrap().as_deref(), Some(IMAGE));
+    assert!(store.join(blob_path(&image.sha256)).is_file());
+    let html = String::from_utf8(archived.content.expect("archived content").bytes).unwrap();
+    assert_eq!(html, format!("<p>Look <img src=\"{}\"></p>", blob_path(&image.sha256)));
+    let reasons: Vec<&str> = archived.skipped.iter().map(|s| s.reason.as_str()).collect();
+    assert_eq!(reasons, ["mime not allowed: audio/mpeg", "quota exceeded"]);
+    assert_eq!(EntryRepo::get(&repo, None, &entry.id).unwrap(), entry, "stored entry untouched");
+}
+
+#[test]
+fn attachments_over_quota_by_read_size_are_never_stored() {
+    use rssify_core::{ArchiveRepo, Enclosure, Entry, EntryRepo};
+    let root = temp_root("archive-quota");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, IMAGE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    let quota = IMAGE.len() - 1;
+    std::fs::write(&seeds, format!(r#"[{{"url": "{OK_URL}", "archive": {{"quota_bytes": {quota}}}}}]"#)).unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    let entry = Entry {
+        published_ts: Some(1),
+        // Declared small enough to pass the pre-fetch check; the body is larger.
+        enclosures: vec![Enclosure { url: IMAGE_URL.to_string(), mime: Some("image/png".into()), length: Some(1) }],
+        ..support::entry(&FeedId::new(OK_URL), "post")
+    };
+    EntryRepo::upsert(&repo, None, &entry).unwrap();
+
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    let archived = ArchiveRepo::archive(&repo, None, &entry.id).unwrap().expect("entry archived");
+    assert!(archived.attachments.is_empty());
+    assert_eq!(archived.skipped[0].reason, "quota exceeded");
+    assert!(!store.join("blobs").exists(), "nothing over quota reaches the blob store");
+}
+
+#[test]
+fn warc_output_records_exchanges_and_links_them_from_entries_and_blobs() {
+    use rssify_core::{Entry, EntryRepo};
+    let root = temp_root("warc");
+    let cassette = root.join("cassette");
+    let recorder = RecordFetcher::new(Upstream, &cassette).unwrap();
+    for url in [OK_URL, ARTICLE_URL] {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!(r#"[{{"url": "{OK_URL}", "full_content": true}}]"#)).unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    let entry = Entry { url: Some(ARTICLE_URL.to_string()), published_ts: Some(1), ..support::entry(&FeedId::new(OK_URL), "post") };
+    EntryRepo::upsert(&repo, None, &entry).unwrap();
+
+    let warc_dir = root.join("warc");
+    let mut o = opts(&seeds, &store);
+    o.replay = Some(cassette.to_string_lossy().into_owned());
+    o.warc = Some(warc_dir.to_string_lossy().into_owned());
+    run(o).expect("run");
+
+    let files: Vec<_> = std::fs::read_dir(&warc_dir).unwrap().map(|e| e.unwrap().path()).collect();
+    assert_eq!(files.len(), 1);
+    let mut text = String::new();
+    let mut gz = flate2::read::MultiGzDecoder::new(std::fs::File::open(&files[0]).unwrap());
+    std::io::Read::read_to_string(&mut gz, &mut text).unwrap();
+    let record_of = |url: &str| {
+        text.split("WARC/1.1\r\n")
+            .find(|r| r.starts_with("WARC-Type: response") && r.contains(&format!("WARC-Target-URI: {url}\r\n")))
+            .and_then(|r| r.lines().find_map(|l| l.strip_prefix("WARC-Record-ID: ")))
+            .map(str::to_string)
+            .expect("response record")
+    };
+
+    let stored = EntryRepo::get(&repo, None, &entry.id).unwrap();
+    assert_eq!(stored.warc_records, [record_of(ARTICLE_URL)]);
+    let last = FeedRepo::last_blob(&repo, None, &FeedId::new(OK_URL)).unwrap().unwrap();
+    assert_eq!(last.warc_record, Some(record_of(OK_URL)));
+}
@@ -0,0 +1,189 @@
+/*
+Module: rssify_cli::tests::fetch_policy
+Purpose: Validate the fetch policies `fetch --replay` applies to every response: size limits,
+         the opt-in SSRF guard and robots.txt for the configured user agent (feeds and articles)
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/main.rs"]
+mod bin_main;
+
+mod support;
+
+use bin_main::cmd_fetch::{run, FetchOpts};
+use bin_main::fetch_feed::{fetch_feed, FetchCtx};
+use bin_main::log::{LogLevel, Logger};
+use rssify_core::{Entry, EntryId, EntryRepo, FeedId, FeedRepo, FetchOutcome, FetchRequest, FetchResponse, Fetcher, ScheduleRepo};
+use rssify_fetch::{RateLimiter, RecordFetcher, ReplayFetcher, Secrets, HINT_TOO_LARGE};
+use rssify_repo_fs::FsRepo;
+use std::path::Path;
+use support::temp_root;
+
+const SMALL_URL: &str = "https://small.test/rss";
+const CAPPED_URL: &str = "https://capped.test/rss";
+const DECLARED_URL: &str = "https://declared.test/rss";
+const LOCAL_URL: &str = "http://127.0.0.1:8080/rss";
+const OPEN_URL: &str = "https://robots.test/rss";
+const PRIVATE_URL: &str = "https://robots.test/private/rss";
+const ROBOTS_URL: &str = "https://robots.test/robots.txt";
+const OPEN_POST: &str = "https://robots.test/posts/1";
+const PRIVATE_POST: &str = "https://robots.test/private/posts/2";
+const ARTICLE: &str = "<html><body><article><p>The whole post, with enough words to count as prose.</p></article></body></html>";
+const ROBOTS: &str = "User-agent: *\nDisallow: /private/\n\nUser-agent: picky\nDisallow: /\n";
+const FEED: &[u8] = b"<rss><channel><title>A feed well over sixteen bytes</title></channel></rss>";
+
+struct Upstream;
+
+impl Fetcher for Upstream {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        if req.url == ROBOTS_URL {
+            let headers = vec![("Content-Type".to_string(), "text/plain".to_string())];
+            return Ok(FetchResponse { status: 200, headers, body: ROBOTS.as_bytes().to_vec(), elapsed_ms: 1 });
+        }
+        if req.url.contains("/posts/") {
+            let headers = vec![("Content-Type".to_string(), "text/html".to_string())];
+            return Ok(FetchResponse { status: 200, headers, body: ARTICLE.as_bytes().to_vec(), elapsed_ms: 1 });
+        }
+        let mut headers = vec![("Content-Type".to_string(), "application/rss+xml".to_string())];
+        if req.url == DECLARED_URL {
+            // Announces more than the 10 MiB default cap; the short body must never be used.
+            headers.push(("Content-Length".to_string(), (11 * 1024 * 1024).to_string()));
+        }
+        Ok(FetchResponse { status: 200, headers, body: FEED.to_vec(), elapsed_ms: 1 })
+    }
+}
+
+fn record(cassette: &Path, urls: &[&str]) {
+    let recorder = RecordFetcher::new(Upstream, cassette).unwrap();
+    for url in urls {
+        recorder.fetch(&FetchRequest { url: url.to_string(), ..FetchRequest::default() }).unwrap();
+    }
+}
+
+#[test]
+fn oversize_responses_fail_permanently_as_too_large() {
+    let root = temp_root("limits");
+    let cassette = root.join("cassette");
+    record(&cassette, &[SMALL_URL, CAPPED_URL, DECLARED_URL]);
+    let seeds = root.join("feeds.json");
+    std::fs::write(
+        &seeds,
+        format!(r#"["{SMALL_URL}", {{"url": "{CAPPED_URL}", "max_bytes": 16}}, "{DECLARED_URL}"]"#),
+    )
+    .unwrap();
+    let store = root.join("data");
+    run(FetchOpts {
+        from: Some(seeds.to_string_lossy().into_owned()),
+        store: Some(format!("fs:{}", store.display())),
+        replay: Some(cassette.to_string_lossy().into_owned()),
+        ..FetchOpts::default()
+    })
+    .expect("run");
+
+    let repo = FsRepo::open(&store);
+    let small = FeedRepo::last_blob(&repo, None, &FeedId::new(SMALL_URL)).unwrap().expect("within limits");
+    assert_eq!(small.blob.bytes, FEED);
+    for url in [CAPPED_URL, DECLARED_URL] {
+        let id = FeedId::new(url);
+        assert_eq!(FeedRepo::last_blob(&repo, None, &id).unwrap(), None, "{url}");
+        assert_eq!(ScheduleRepo::failure_streak(&repo, None, &id).unwrap().permanent, 1, "{url}");
+    }
+    assert_eq!(FeedRepo::get(&repo, None, &FeedId::new(CAPPED_URL)).unwrap().settings.max_bytes, Some(16));
+
+    let replay = ReplayFetcher::open(&cassette).unwrap();
+    let (secrets, limiter, log) = (Secrets::default(), RateLimiter::new(Default::default()), Logger::new(LogLevel::Error));
+    let ctx = FetchCtx {
+        repo: &repo,
+        tx: None,
+        fetcher: &replay,
+        limits: Default::default(),
+        secrets: &secrets,
+        limiter: &limiter,
+        warc: None,
+        log: &log,
+        policy: Default::default(),
+        now: 1_000,
+    };
+    let mut capped = FeedRepo::get(&repo, None, &FeedId::new(CAPPED_URL)).unwrap();
+    let (stats, _) = fetch_feed(&ctx, &mut capped).unwrap();
+    assert_eq!(stats.failure_hint.as_deref(), Some(HINT_TOO_LARGE));
+}
+
+#[test]
+fn ssrf_guard_blocks_private_addresses_unless_allowed() {
+    let root = temp_root("ssrf");
+    let cassette = root.join("cassette");
+    record(&cassette, &[LOCAL_URL]);
+    let store = root.join("data");
+    let one = |allow: &[&str]| FetchOpts {
+        one: Some(LOCAL_URL.to_string()),
+        store: Some(format!("fs:{}", store.display())),
+        replay: Some(cassette.to_string_lossy().into_owned()),
+        ssrf_guard: true,
+        ssrf_allow: allow.iter().map(|a| a.to_string()).collect(),
+        ..FetchOpts::default()
+    };
+    let repo = FsRepo::open(&store);
+    let id = FeedId::new(LOCAL_URL);
+
+    run(one(&[])).expect("blocked run");
+    assert_eq!(FeedRepo::last_blob(&repo, None, &id).unwrap(), None);
+    assert_eq!(ScheduleRepo::failure_streak(&repo, None, &id).unwrap().permanent, 1);
+
+    run(one(&["127.0.0.1:8080"])).expect("allowed run");
+    assert!(FeedRepo::last_blob(&repo, None, &id).unwrap().is_some());
+}
+
+#[test]
+fn robots_disallowed_feeds_are_skipped_for_the_configured_user_agent() {
+    let root = temp_root("robots");
+    let cassette = root.join("cassette");
+    record(&cassette, &[ROBOTS_URL, OPEN_URL, PRIVATE_URL]);
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!(r#"["{OPEN_URL}", "{PRIVATE_URL}"]"#)).unwrap();
+    let store = root.join("data");
+    let opts = |user_agent: Option<&str>| FetchOpts {
+        from: Some(seeds.to_string_lossy().into_owned()),
+        store: Some(format!("fs:{}", store.display())),
+        replay: Some(cassette.to_string_lossy().into_owned()),
+        user_agent: user_agent.map(str::to_string),
+        ..FetchOpts::default()
+    };
+    let repo = FsRepo::open(&store);
+    let (open, private) = (FeedId::new(OPEN_URL), FeedId::new(PRIVATE_URL));
+
+    run(opts(None)).expect("default user agent");
+    assert!(FeedRepo::last_blob(&repo, None, &open).unwrap().is_some());
+    assert_eq!(FeedRepo::last_blob(&repo, None, &private).unwrap(), None, "disallowed feed never fetched");
+    assert_eq!(ScheduleRepo::failure_streak(&repo, None, &private).unwrap().permanent, 1);
+
+    run(opts(Some("Picky/2.0 (+https://picky.example)"))).expect("picky user agent");
+    assert_eq!(ScheduleRepo::failure_streak(&repo, None, &open).unwrap().permanent, 1, "picky may fetch nothing");
+}
+
+#[test]
+fn articles_disallowed_by_robots_are_not_fetched() {
+    let root = temp_root("robots-articles");
+    let cassette = root.join("cassette");
+    record(&cassette, &[ROBOTS_URL, OPEN_URL, OPEN_POST, PRIVATE_POST]);
+    let seeds = root.join("feeds.json");
+    std::fs::write(&seeds, format!(r#"[{{"url": "{OPEN_URL}", "full_content": true}}]"#)).unwrap();
+    let store = root.join("data");
+    let repo = FsRepo::open(&store);
+    let entry = |id: &str, url: &str| Entry { url: Some(url.to_string()), published_ts: Some(1), ..support::entry(&FeedId::new(OPEN_URL), id) };
+    EntryRepo::upsert(&repo, None, &entry("open", OPEN_POST)).unwrap();
+    EntryRepo::upsert(&repo, None, &entry("private", PRIVATE_POST)).unwrap();
+
+    run(FetchOpts {
+        from: Some(seeds.to_string_lossy().into_owned()),
+        store: Some(format!("fs:{}", store.display())),
+        replay: Some(cassette.to_string_lossy().into_owned()),
+        ..FetchOpts::default()
+    })
+    .expect("run");
+
+    assert!(EntryRepo::get(&repo, None, &EntryId::new("open")).unwrap().content.is_some());
+    let private = EntryRepo::get(&repo, None, &EntryId::new("private")).unwrap();
+    assert_eq!(private.content, None, "recorded, but robots.txt disallows /private/");
+}
@@ -4,6 +4,8 @@ Purpose: End-to-end shape test for `rssify fetch` with fixtures (ignored)
 Notes: #[ignore] until Phase wiring is complete.
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/main.rs"]
 mod bin_main; // exposes Cli, Command, parse_from
 
@@ -29,7 +31,7 @@ fn fetch_invocation_parses_and_will_wire_pipeline() {
         "-v",
     ]);
     match cli.command {
-        Command::Fetch { from, store, json, verbose } => {
+        Command::Fetch { from, store, json, verbose, .. } => {
             assert!(from.is_some());
             assert!(store.is_some());
             assert!(json);
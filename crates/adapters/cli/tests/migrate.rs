@@ -0,0 +1,96 @@
+/*
+Module: rssify_cli::tests::migrate
+Purpose: Validate `migrate` backs up and upgrades version-1 fs repos so stats, FsPaths and FsRepo
+agree again, and leaves current sqlite and mem stores alone
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/main.rs"]
+mod bin_main;
+
+mod support;
+
+use bin_main::cmd_migrate::{run, MigrateOpts};
+use bin_main::repo_fs::FsPaths;
+use bin_main::stats::stats_fs;
+use rssify_core::{EntryId, EntryRepo, FeedRepo};
+use rssify_repo_fs::FsRepo;
+use support::{entry, feed, temp_root};
+
+#[test]
+fn stats_and_paths_match_what_the_repo_writes() {
+    let root = temp_root("migrate");
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    for id in ["guid:1", "guid:2", "link:https://ex.com/3"] {
+        EntryRepo::upsert(&repo, None, &entry(&f.id, id)).unwrap();
+    }
+    let s = stats_fs(&root.to_string_lossy()).unwrap();
+    assert_eq!((s.feeds, s.entries), (1, 3));
+    let root_s = root.to_string_lossy();
+    assert!(std::path::Path::new(&FsPaths::feed_json(&root_s, &f.id)).is_file());
+    assert!(std::path::Path::new(&FsPaths::entry_json(&root_s, &f.id, &EntryId::new("link:https://ex.com/3"))).is_file());
+}
+
+#[test]
+fn migrate_backs_up_and_upgrades_a_version_one_repo_and_is_idempotent() {
+    let root = temp_root("migrate").join("repo");
+    let f = feed("https://ex.com/rss");
+    let dir = root.join("feeds").join("url_3ahttps_3a_2f_2fex_2ecom_2frss");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("feed.json"), serde_json::to_vec(&f).unwrap()).unwrap();
+    for (sub, name) in [("by_id", ""), ("by_feed", "url_3ahttps_3a_2f_2fex_2ecom_2frss")] {
+        let d = root.join("entries").join(sub).join(name);
+        std::fs::create_dir_all(&d).unwrap();
+        std::fs::write(d.join("guid_3a1.json"), serde_json::to_vec(&entry(&f.id, "guid:1")).unwrap()).unwrap();
+    }
+    let repo = FsRepo::open(&root);
+    assert!(FeedRepo::list(&repo, None).is_err(), "version 1 is refused before migrating");
+
+    let backups = root.with_file_name("backups");
+    let opts = || MigrateOpts {
+        store: Some(format!("fs:{}", root.display())),
+        json: true,
+        backup_dir: Some(backups.to_string_lossy().into_owned()),
+    };
+    run(opts()).expect("migrate");
+    run(opts()).expect("second run is a no-op");
+    let taken: Vec<_> = std::fs::read_dir(&backups).unwrap().map(|e| e.unwrap().path()).collect();
+    assert_eq!(taken.len(), 1, "one backup, taken only while steps were pending");
+    assert!(taken[0].file_name().unwrap().to_string_lossy().starts_with("repo.backup-v1-"));
+    assert!(taken[0].join("entries/by_id/guid_3a1.json").is_file(), "backup keeps the old layout");
+
+    let repo = FsRepo::open(&root);
+    assert_eq!(FeedRepo::list(&repo, None).unwrap(), vec![f.clone()]);
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap(), vec![entry(&f.id, "guid:1")]);
+    let s = stats_fs(&root.to_string_lossy()).unwrap();
+    assert_eq!((s.feeds, s.entries), (1, 1));
+}
+
+#[test]
+fn unreadable_legacy_entries_stop_the_migration() {
+    let root = temp_root("migrate");
+    let d = root.join("entries").join("by_id");
+    std::fs::create_dir_all(&d).unwrap();
+    std::fs::write(d.join("torn.json"), "{").unwrap();
+    let opts = MigrateOpts { store: Some(format!("fs:{}", root.display())), json: false, backup_dir: None };
+    let err = run(opts).expect_err("partial");
+    assert!(err.to_string().contains("unreadable"), "{err}");
+    assert!(err.to_string().contains("backup at"), "{err}");
+    assert!(d.join("torn.json").is_file());
+    assert!(!root.join("meta.json").exists());
+}
+
+#[test]
+fn current_sqlite_and_mem_stores_need_no_backup() {
+    let root = temp_root("migrate");
+    let db = root.join("rssify.db");
+    drop(rssify_repo_sqlite::SqliteRepo::open(&db).unwrap());
+    let opts = MigrateOpts { store: Some(format!("sqlite:{}", db.display())), json: true, backup_dir: None };
+    run(opts).expect("sqlite");
+    run(MigrateOpts { store: Some("mem:".into()), json: false, backup_dir: None }).expect("mem");
+    let names: Vec<_> = std::fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert!(names.iter().all(|n| !n.to_string_lossy().contains(".backup-")), "{names:?}");
+}
@@ -0,0 +1,87 @@
+/*
+Module: rssify_cli::tests::outcome
+Purpose: Validate apply_outcome persists streaks and deactivates feeds, and reactivate undoes it, through the FS repo
+*/
+
+#[path = "../src/outcome.rs"]
+mod outcome;
+
+mod support;
+
+use outcome::{apply_outcome, reactivate};
+use rssify_core::{
+    DeactivationPolicy, DeactivationReason, FailureStreak, FeedRepo, FetchOutcome,
+    ScheduleRepo,
+};
+use rssify_repo_fs::FsRepo;
+use support::{feed, temp_root};
+
+#[test]
+fn gone_deactivates_and_persists() {
+    let repo = FsRepo::new(temp_root("outcome"));
+    let mut f = feed("https://gone.test/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+
+    let d = apply_outcome(&repo, None, &mut f, &FetchOutcome::Gone, &DeactivationPolicy::default(), 500)
+        .expect("apply")
+        .expect("deactivated");
+    assert_eq!(d.reason, DeactivationReason::Gone);
+    assert_eq!(d.ts, 500);
+
+    let stored = FeedRepo::get(&repo, None, &f.id).unwrap();
+    assert!(!stored.active);
+    assert_eq!(stored.deactivated, Some(d));
+}
+
+#[test]
+fn permanent_failures_accumulate_across_calls() {
+    let repo = FsRepo::new(temp_root("outcome"));
+    let mut f = feed("https://broken.test/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    let policy = DeactivationPolicy { max_permanent_failures: 2, max_days_without_success: 30 };
+    let fail = FetchOutcome::PermanentFailure { hint: Some("404".into()) };
+
+    assert_eq!(apply_outcome(&repo, None, &mut f, &fail, &policy, 10).unwrap(), None);
+    assert!(f.active);
+    let d = apply_outcome(&repo, None, &mut f, &fail, &policy, 20).unwrap();
+    assert_eq!(
+        d.map(|d| d.reason),
+        Some(DeactivationReason::PermanentFailures { count: 2 })
+    );
+    assert!(!FeedRepo::get(&repo, None, &f.id).unwrap().active);
+}
+
+#[test]
+fn success_resets_streak_and_records_last_ok() {
+    let repo = FsRepo::new(temp_root("outcome"));
+    let mut f = feed("https://ok.test/rss");
+    let policy = DeactivationPolicy::default();
+    let fail = FetchOutcome::PermanentFailure { hint: None };
+
+    apply_outcome(&repo, None, &mut f, &fail, &policy, 10).unwrap();
+    assert_eq!(ScheduleRepo::failure_streak(&repo, None, &f.id).unwrap().permanent, 1);
+
+    apply_outcome(&repo, None, &mut f, &FetchOutcome::NotModified, &policy, 20).unwrap();
+    assert_eq!(
+        ScheduleRepo::failure_streak(&repo, None, &f.id).unwrap(),
+        FailureStreak::default()
+    );
+    assert_eq!(ScheduleRepo::last_ok_fetch_ts(&repo, None, &f.id).unwrap(), Some(20));
+}
+
+#[test]
+fn reactivate_by_url_clears_deactivation_and_streak() {
+    let repo = FsRepo::new(temp_root("outcome"));
+    let mut f = feed("https://back.test/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    apply_outcome(&repo, None, &mut f, &FetchOutcome::Gone, &DeactivationPolicy::default(), 10).unwrap();
+
+    let (id, was_active) = reactivate(&repo, "https://back.test/rss").expect("reactivate");
+    assert_eq!(id, f.id);
+    assert!(!was_active);
+    let stored = FeedRepo::get(&repo, None, &f.id).unwrap();
+    assert!(stored.active);
+    assert_eq!(stored.deactivated, None);
+    assert_eq!(ScheduleRepo::failure_streak(&repo, None, &f.id).unwrap(), FailureStreak::default());
+    assert!(reactivate(&repo, "https://missing.test/rss").is_err());
+}
@@ -3,6 +3,8 @@ Module: rssify_cli::test::pipeline
 Purpose: Ensure the pipeline skeleton types exist and are public
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/pipeline.rs"]
 mod pipeline;
 
@@ -16,6 +18,9 @@ fn pipeline_types_are_constructible() {
         feed: rssify_core::FeedId::from_url("https://ex.com/feed"),
         items_written: 0,
         elapsed_ms: 0,
+        wait_ms: 0,
+        articles_written: 0,
+        attachments_archived: 0,
         not_modified: false,
         failure_hint: None,
     };
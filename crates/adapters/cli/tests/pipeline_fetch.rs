@@ -3,6 +3,8 @@ Module: rssify_cli::test::pipeline_fetch
 Purpose: Validate load_feed_seeds() and fetch_from_file() without network or writes.
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/pipeline.rs"]
 mod pipeline;
 
@@ -3,6 +3,8 @@ Module: rssify_cli::tests::pipeline_load
 Purpose: Validate pipeline::load_feed_seeds supports arrays, objects, and {seeds: [...]}
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/pipeline.rs"]
 mod pipeline;
 
@@ -57,3 +59,26 @@ fn rejects_empty_array() {
     assert!(msg.contains("seeds array is empty"));
     let _ = fs::remove_file(&file);
 }
+
+#[test]
+fn loads_request_settings_keyed_like_seeds() {
+    let file = tf("json");
+    fs::write(
+        &file,
+        r#"{"seeds": [
+            "https://plain.test/rss",
+            {"url": "https://private.test/rss",
+             "request": {"auth": {"type": "bearer", "token": {"env": "PRIVATE_TOKEN"}},
+                         "user_agent": "Mozilla/5.0 rssify"}}
+        ]}"#,
+    )
+    .unwrap();
+    let reqs = pipeline::load_seed_requests(&file).expect("parse");
+    assert_eq!(reqs.len(), 1);
+    let r = &reqs["https://private.test/rss"];
+    assert_eq!(r.user_agent.as_deref(), Some("Mozilla/5.0 rssify"));
+    assert!(r.auth.is_some());
+
+    fs::write(&file, r#"[{"url": "u", "request": {"auth": {"type": "bearer", "token": "inline"}}}]"#).unwrap();
+    assert!(pipeline::load_seed_requests(&file).is_err(), "inline credentials are rejected");
+}
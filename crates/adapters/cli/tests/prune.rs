@@ -0,0 +1,130 @@
+/*
+Module: rssify_cli::tests::prune
+Purpose: Validate `remove` drops a feed with its entries and `prune` applies keep-last/max-age
+per feed without ever deleting starred entries, then collects orphaned blobs; --dry-run and mem
+snapshots included
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/main.rs"]
+mod bin_main;
+
+mod support;
+
+use bin_main::cmd_prune::{self, prune_and_collect, prune_repo, PruneOpts};
+use bin_main::cmd_remove::{self, RemoveOpts};
+use bin_main::stats::stats_fs;
+use rssify_core::{BlobRepo, ContentBlob, ContentKind, Entry, EntryRepo, Feed, FeedId, FeedRepo, RetentionPolicy};
+use rssify_repo_fs::FsRepo;
+use support::{feed, temp_root};
+
+const DAY: i64 = 86_400;
+
+fn entry(feed: &FeedId, id: &str, published_ts: i64, starred: bool) -> Entry {
+    Entry { published_ts: Some(published_ts), starred, ..support::entry(feed, id) }
+}
+
+/// Two feeds of four daily entries each (ids `<n>:0` newest .. `<n>:3` oldest), `a:3` starred.
+fn seed<R: FeedRepo + EntryRepo>(repo: &R, now: i64) -> (Feed, Feed) {
+    let (a, b) = (feed("https://a.example/rss"), feed("https://b.example/rss"));
+    for (f, tag) in [(&a, "a"), (&b, "b")] {
+        FeedRepo::put(repo, None, f).unwrap();
+        for age in 0..4 {
+            let starred = tag == "a" && age == 3;
+            EntryRepo::upsert(repo, None, &entry(&f.id, &format!("{tag}:{age}"), now - age * DAY, starred)).unwrap();
+        }
+    }
+    (a, b)
+}
+
+fn ids<R: EntryRepo>(repo: &R, feed: &FeedId) -> Vec<String> {
+    let mut ids: Vec<String> =
+        EntryRepo::list_by_feed(repo, None, feed).unwrap().into_iter().map(|e| e.id.as_str().to_string()).collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn remove_by_url_drops_the_feed_and_its_entries_only() {
+    let root = temp_root("prune");
+    let repo = FsRepo::open(&root);
+    let (a, b) = seed(&repo, 1_700_000_000);
+    let store = Some(format!("fs:{}", root.display()));
+    cmd_remove::run(RemoveOpts { feed: a.url.clone(), store: store.clone(), json: true }).expect("remove");
+
+    assert!(FeedRepo::get(&repo, None, &a.id).is_err());
+    assert!(ids(&repo, &a.id).is_empty());
+    assert_eq!(ids(&repo, &b.id).len(), 4);
+    let s = stats_fs(&root.to_string_lossy()).unwrap();
+    assert_eq!((s.feeds, s.entries), (1, 4));
+
+    let again = cmd_remove::run(RemoveOpts { feed: a.url, store, json: false });
+    assert!(again.expect_err("already gone").to_string().contains("feed not found"));
+}
+
+#[test]
+fn keep_last_and_max_age_keep_an_entry_if_either_does_and_spare_starred_ones() {
+    let now = 1_700_000_000;
+    let repo = FsRepo::open(temp_root("prune"));
+    let (a, b) = seed(&repo, now);
+    let policy = RetentionPolicy { keep_last: Some(1), max_age_days: Some(1) };
+
+    let dry = prune_repo(&repo, None, &policy, now, true).unwrap();
+    assert_eq!(dry.iter().map(|f| f.pruned).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(ids(&repo, &a.id).len(), 4, "dry run only reads");
+
+    let done = prune_repo(&repo, None, &policy, now, false).unwrap();
+    assert_eq!(done.iter().map(|f| (f.pruned, f.kept)).collect::<Vec<_>>(), [(1, 3), (2, 2)]);
+    assert_eq!(ids(&repo, &a.id), ["a:0", "a:1", "a:3"], "a:1 is within a day, a:3 is starred");
+    assert_eq!(ids(&repo, &b.id), ["b:0", "b:1"]);
+}
+
+#[test]
+fn prune_needs_a_bound_and_can_target_one_feed() {
+    let unbounded = PruneOpts { store: Some("mem:".into()), json: false, keep_last: None, max_age_days: None, feed: None, dry_run: false };
+    assert!(cmd_prune::run(unbounded).is_err());
+
+    let repo = FsRepo::open(temp_root("prune"));
+    let (a, b) = seed(&repo, 1_700_000_000);
+    let policy = RetentionPolicy { keep_last: Some(0), max_age_days: None };
+    let done = prune_repo(&repo, Some(b.url.as_str()), &policy, 1_700_000_000, false).unwrap();
+    assert_eq!(done.len(), 1);
+    assert!(ids(&repo, &b.id).is_empty());
+    assert_eq!(ids(&repo, &a.id).len(), 4);
+}
+
+#[test]
+fn prune_collects_the_content_blobs_only_pruned_entries_referenced() {
+    let now = 1_700_000_000;
+    let repo = FsRepo::open(temp_root("prune"));
+    let (a, _) = seed(&repo, now);
+    let old = Entry { content: Some(ContentBlob { kind: ContentKind::Html, bytes: b"<p>old</p>".to_vec() }), ..entry(&a.id, "a:2", now - 2 * DAY, false) };
+    EntryRepo::upsert(&repo, None, &old).unwrap();
+    let sha = BlobRepo::put_blob(&repo, None, b"<p>old</p>").unwrap();
+    let policy = RetentionPolicy { keep_last: Some(2), max_age_days: None };
+
+    let (_, removed) = prune_and_collect(&repo, Some(a.url.as_str()), &policy, now, true).unwrap();
+    assert_eq!(removed, 0, "dry run collects nothing");
+    let (done, removed) = prune_and_collect(&repo, Some(a.url.as_str()), &policy, now, false).unwrap();
+    assert_eq!((done[0].pruned, removed), (1, 1));
+    assert_eq!(BlobRepo::get_blob(&repo, None, &sha).unwrap(), None);
+}
+
+#[test]
+fn mem_snapshots_persist_removals_and_prunes() {
+    let snap = temp_root("prune").join("mem.json");
+    let spec = bin_main::spec::RepoSpec { kind: bin_main::spec::RepoKind::Mem, target: snap.to_string_lossy().into_owned(), options: Vec::new() };
+    let repo = bin_main::backend::open_mem(&spec).unwrap();
+    let (a, b) = seed(&repo, bin_main::fetch_feed::now_unix());
+    bin_main::backend::save_mem(&spec, &repo).unwrap();
+    let store = Some(format!("mem:{}", snap.display()));
+
+    cmd_remove::run(RemoveOpts { feed: a.id.as_str().to_string(), store: store.clone(), json: false }).unwrap();
+    let opts = PruneOpts { store, json: true, keep_last: Some(2), max_age_days: None, feed: None, dry_run: false };
+    cmd_prune::run(opts).unwrap();
+
+    let repo = bin_main::backend::open_mem(&spec).unwrap();
+    assert!(FeedRepo::get(&repo, None, &a.id).is_err());
+    assert_eq!(ids(&repo, &b.id), ["b:0", "b:1"]);
+}
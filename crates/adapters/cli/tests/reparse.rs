@@ -0,0 +1,64 @@
+/*
+Module: rssify_cli::tests::reparse
+Purpose: Validate reparse re-reads stored last_blob payloads without network
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/main.rs"]
+mod bin_main;
+
+mod support;
+
+use bin_main::cmd_reparse::reparse_repo;
+use rssify_core::{ContentBlob, ContentKind, FeedRepo, LastBlob};
+use rssify_repo_fs::FsRepo;
+use support::{feed, temp_root};
+
+#[test]
+fn reports_feeds_with_and_without_blobs() {
+    let repo = FsRepo::new(temp_root("reparse"));
+    let a = feed("https://a.test/rss");
+    let b = feed("https://b.test/rss");
+    FeedRepo::put(&repo, None, &a).unwrap();
+    FeedRepo::put(&repo, None, &b).unwrap();
+    let last = LastBlob {
+        blob: ContentBlob { kind: ContentKind::Xml, bytes: b"<rss/>".to_vec() },
+        headers: vec![("Content-Type".into(), "application/rss+xml; charset=utf-8".into())],
+        fetched_ts: 7,
+        warc_record: None,
+    };
+    FeedRepo::put_last_blob(&repo, None, &a.id, &last).unwrap();
+
+    let (all, missing) = reparse_repo(&repo, None).unwrap();
+    assert_eq!(missing, 1);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].feed_id, a.id.as_str());
+    assert_eq!((all[0].kind.as_str(), all[0].bytes, all[0].fetched_ts), ("xml", 6, 7));
+    assert_eq!(all[0].detected, "xml");
+    assert!(all[0].decodes && !all[0].mismatch());
+
+    let (one, missing) = reparse_repo(&repo, Some("https://b.test/rss")).unwrap();
+    assert!(one.is_empty());
+    assert_eq!(missing, 1);
+}
+
+#[test]
+fn flags_blobs_whose_headers_or_bytes_disagree() {
+    let repo = FsRepo::new(temp_root("reparse"));
+    let a = feed("https://a.test/rss");
+    FeedRepo::put(&repo, None, &a).unwrap();
+    let last = LastBlob {
+        blob: ContentBlob { kind: ContentKind::Xml, bytes: vec![0xff, 0xfe, b'<'] },
+        headers: vec![("content-type".into(), "text/html".into())],
+        fetched_ts: 9,
+        warc_record: None,
+    };
+    FeedRepo::put_last_blob(&repo, None, &a.id, &last).unwrap();
+
+    let (all, missing) = reparse_repo(&repo, Some(a.id.as_str())).unwrap();
+    assert_eq!(missing, 0);
+    assert_eq!((all[0].kind.as_str(), all[0].detected.as_str()), ("xml", "html"));
+    assert!(!all[0].decodes);
+    assert!(all[0].mismatch());
+}
@@ -1,6 +1,6 @@
 /*
 Module: rssify_cli::test::repo_fs_path
-Purpose: Validate FsPaths builders and urlsafe encoding shapes (no I/O)
+Purpose: Validate FsPaths builders and percent-encoding shapes (no I/O)
 */
 
 #[path = "../src/repo_fs.rs"]
@@ -0,0 +1,99 @@
+/*
+Module: rssify_cli::tests::search
+Purpose: Validate `search` ranks stored entries with their titles and URLs on every backend,
+rejects unusable queries, and `reindex` rebuilds an index that lost its files (compressed when the
+fs spec asks for it)
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/main.rs"]
+mod bin_main;
+
+mod support;
+
+use bin_main::cmd_search::{self, search_repo, ReindexOpts, SearchOpts};
+use rssify_core::{Entry, EntryRepo, Feed, FeedId, FeedRepo};
+use rssify_repo_fs::FsRepo;
+use rssify_repo_sqlite::SqliteRepo;
+use support::{feed, temp_root};
+
+fn entry(feed: &FeedId, id: &str, title: &str, authors: &[&str]) -> Entry {
+    Entry {
+        url: Some(format!("https://ex.com/{id}")),
+        title: Some(title.into()),
+        published_ts: Some(1_700_000_000),
+        authors: authors.iter().map(|a| a.to_string()).collect(),
+        ..support::entry(feed, id)
+    }
+}
+
+fn seed<R: FeedRepo + EntryRepo>(repo: &R) -> Feed {
+    let f = feed("https://ex.com/rss");
+    FeedRepo::put(repo, None, &f).unwrap();
+    EntryRepo::upsert(repo, None, &entry(&f.id, "e1", "Rust release notes", &["Jane Doe"])).unwrap();
+    EntryRepo::upsert(repo, None, &entry(&f.id, "e2", "Rust and more rust", &[])).unwrap();
+    EntryRepo::upsert(repo, None, &entry(&f.id, "e3", "Gardening", &[])).unwrap();
+    f
+}
+
+fn hit_ids<R: rssify_core::SearchRepo + EntryRepo>(repo: &R, query: &str) -> Vec<String> {
+    search_repo(repo, query, 10).unwrap().into_iter().map(|r| r.entry_id).collect()
+}
+
+#[test]
+fn hits_carry_title_and_url_and_rank_alike_on_fs_and_sqlite() {
+    let fs_repo = FsRepo::open(temp_root("search"));
+    let sql_repo = SqliteRepo::open(temp_root("search").join("rssify.db")).unwrap();
+    seed(&fs_repo);
+    seed(&sql_repo);
+    for query in ["rust", "rust author:jane", "\"release notes\"", "gardening after:2024-01-01"] {
+        assert_eq!(hit_ids(&fs_repo, query), hit_ids(&sql_repo, query), "{query}");
+    }
+    assert_eq!(hit_ids(&fs_repo, "rust"), ["e2", "e1"]);
+    assert_eq!(hit_ids(&fs_repo, "rust author:jane"), ["e1"]);
+
+    let rows = search_repo(&fs_repo, "gardening", 10).unwrap();
+    assert_eq!(rows[0].title.as_deref(), Some("Gardening"));
+    assert_eq!(rows[0].url.as_deref(), Some("https://ex.com/e3"));
+    assert!(search_repo(&fs_repo, "rust", 1).unwrap().len() == 1, "--limit caps the hits");
+}
+
+#[test]
+fn queries_without_words_are_rejected() {
+    let err = search_repo(&FsRepo::open(temp_root("search")), "author:jane", 10).unwrap_err();
+    assert!(err.contains("at least one word"), "{err}");
+    let opts = SearchOpts { query: "after:someday x".into(), limit: 5, store: Some("mem:".into()), json: true };
+    assert!(cmd_search::run_search(opts).is_err());
+}
+
+#[test]
+fn reindex_rebuilds_a_lost_fs_index() {
+    let root = temp_root("search");
+    let repo = FsRepo::open(&root);
+    seed(&repo);
+    std::fs::remove_dir_all(root.join("index")).unwrap();
+    assert!(hit_ids(&repo, "rust").is_empty());
+
+    let store = Some(format!("fs:{}", root.display()));
+    cmd_search::run_reindex(ReindexOpts { store: store.clone(), json: true }).expect("reindex");
+    assert_eq!(hit_ids(&repo, "rust"), ["e2", "e1"]);
+    let opts = SearchOpts { query: "rust".into(), limit: 5, store, json: false };
+    cmd_search::run_search(opts).expect("search");
+}
+
+#[test]
+fn fs_store_options_compress_rebuilt_records_and_reject_unknown_keys() {
+    let root = temp_root("search");
+    seed(&FsRepo::open(&root));
+    let store = |query: &str| Some(format!("fs:{}?{}", root.display(), query));
+    cmd_search::run_reindex(ReindexOpts { store: store("compress=zstd"), json: true }).expect("reindex");
+    let stats = std::fs::read(root.join("index/stats.json")).unwrap();
+    assert!(stats.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]), "stored as a zstd frame");
+    assert_eq!(hit_ids(&FsRepo::open(&root), "rust"), ["e2", "e1"]);
+
+    for bad in ["compress=gzip", "level=3"] {
+        let err = cmd_search::run_reindex(ReindexOpts { store: store(bad), json: true }).unwrap_err();
+        assert!(err.to_string().contains("invalid --store"), "{err}");
+    }
+}
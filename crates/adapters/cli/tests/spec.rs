@@ -3,6 +3,8 @@ Module: rssify_cli::test::spec
 Purpose: Validate repo spec parsing (no I/O, no backend behavior)
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/spec.rs"]
 mod spec;
 
@@ -32,3 +34,30 @@ fn rejects_bad_specs() {
     assert!(RepoSpec::from_str("nocolon").is_err());
 }
 
+
+#[test]
+fn mem_specs_take_an_optional_snapshot_path() {
+    let bare = RepoSpec::from_str("mem:").unwrap();
+    assert_eq!(bare.kind, RepoKind::Mem);
+    assert_eq!(bare.target, "");
+    assert_eq!(bare.to_string(), "mem:");
+
+    let snap = RepoSpec::from_str("MEM:./run.json").unwrap();
+    assert_eq!(snap.kind, RepoKind::Mem);
+    assert_eq!(snap.target, "./run.json");
+    assert!(RepoSpec::from_str("mem").is_err());
+}
+
+#[test]
+fn fs_specs_take_options_after_a_question_mark() {
+    let fs = RepoSpec::from_str("fs:./data?compress=zstd").unwrap();
+    assert_eq!(fs.target, "./data");
+    assert_eq!(fs.option("compress"), Some("zstd"));
+    assert_eq!(fs.to_string(), "fs:./data?compress=zstd");
+    assert_eq!(RepoSpec::from_str("fs:./data").unwrap().options, Vec::new());
+
+    assert!(RepoSpec::from_str("fs:./data?compress").is_err());
+    assert!(RepoSpec::from_str("fs:?compress=zstd").is_err());
+    let db = RepoSpec::from_str("sqlite:./what?.db").unwrap();
+    assert_eq!((db.target.as_str(), db.options.len()), ("./what?.db", 0), "only fs targets take options");
+}
@@ -1,11 +1,18 @@
 /*
 Module: rssify_cli::test::stats_fs
-Purpose: Validate stats_fs counts feeds and per-feed entries (and stays compatible with legacy layout)
+Purpose: Validate stats_fs counts feeds and per-feed entries (and stays compatible with legacy layout),
+reads compressed records and reports on-disk versus logical size; stats_repo counts through the repo traits
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/stats.rs"]
 mod stats;
 
+mod support;
+
+use rssify_core::{EntryRepo, FeedRepo};
+use rssify_repo_sqlite::SqliteRepo;
 use std::fs;
 use std::path::PathBuf;
 
@@ -47,3 +54,70 @@ fn counts_per_feed_entries_and_feeds() {
     assert_eq!(s.feeds, 2, "should count feeds that have feed.json");
     assert_eq!(s.entries, 4, "3 per-feed + 1 legacy");
 }
+
+#[test]
+fn lists_deactivated_feeds_with_reason() {
+    let root = td();
+
+    let active = root.join("feeds").join("active");
+    fs::create_dir_all(&active).unwrap();
+    fs::write(active.join("feed.json"), r#"{"id":"url:https://a.test","active":true}"#).unwrap();
+
+    let gone = root.join("feeds").join("gone");
+    fs::create_dir_all(&gone).unwrap();
+    fs::write(
+        gone.join("feed.json"),
+        r#"{"id":"url:https://gone.test","active":false,"deactivated":{"reason":"Gone","ts":1700000000}}"#,
+    )
+    .unwrap();
+
+    let s = stats::stats_fs(root.to_string_lossy().as_ref()).expect("stats");
+    assert_eq!(s.feeds, 2);
+    assert_eq!(s.deactivated.len(), 1);
+    assert_eq!(s.deactivated[0].id, "url:https://gone.test");
+    assert_eq!(s.deactivated[0].reason.as_deref(), Some("gone"));
+    assert_eq!(s.deactivated[0].ts, Some(1_700_000_000));
+}
+
+#[test]
+fn reads_compressed_records_and_reports_storage_sizes() {
+    use rssify_core::{Deactivation, DeactivationReason, Feed, FeedId, FeedRepo};
+    use rssify_repo_fs::{Compression, FsRepo};
+
+    let root = td();
+    let repo = FsRepo::open(&root).with_compression(Compression::Zstd);
+    let url = "https://gone.test/rss";
+    let feed = Feed {
+        id: FeedId::from_url(url),
+        url: url.into(),
+        title: Some("a title long enough to be worth compressing, compressing, compressing".into()),
+        site_url: None,
+        etag: None,
+        last_modified: None,
+        active: false,
+        deactivated: Some(Deactivation { reason: DeactivationReason::Gone, ts: 1_700_000_000 }),
+        settings: Default::default(),
+    };
+    FeedRepo::put(&repo, None, &feed).unwrap();
+
+    let s = stats::stats_fs(root.to_string_lossy().as_ref()).expect("stats");
+    assert_eq!(s.feeds, 1);
+    assert_eq!(s.deactivated.len(), 1, "a compressed feed.json still reads");
+    let size = s.storage.expect("fs stats carry sizes");
+    assert!(size.on_disk_bytes < size.logical_bytes, "{size:?}");
+}
+
+#[test]
+fn repo_stats_count_entries_per_feed() {
+    let repo = SqliteRepo::open(td().join("rssify.db")).unwrap();
+    let (a, b) = (support::feed("https://a.test/rss"), support::feed("https://b.test/rss"));
+    for (f, n) in [(&a, 2), (&b, 1)] {
+        FeedRepo::put(&repo, None, f).unwrap();
+        for i in 0..n {
+            EntryRepo::upsert(&repo, None, &support::entry(&f.id, &format!("{}-{i}", f.url))).unwrap();
+        }
+    }
+    let s = stats::stats_repo(&repo).expect("stats");
+    assert_eq!((s.feeds, s.entries), (2, 3));
+    assert!(s.storage.is_none());
+}
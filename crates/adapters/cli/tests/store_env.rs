@@ -3,6 +3,8 @@ Module: rssify_cli::tests::store_env
 Purpose: Verify precedence for repo resolution without mutating the process environment.
 */
 
+#![allow(dead_code)]
+
 #[path = "../src/store.rs"]
 mod store;
 
@@ -1,10 +1,58 @@
 /*
 Module: rssify_cli::test::support
-Purpose: Small helpers for tests (path building, temp dirs later)
+Purpose: Small helpers for tests (path building, temp dirs, domain fixtures)
 */
 
-pub fn td(p: &str) -> std::path::PathBuf {
+#![allow(dead_code)]
+
+use rssify_core::{Entry, EntryId, Feed, FeedId};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn td(p: &str) -> PathBuf {
     let here = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
     here.join("test").join("testdata").join(p)
 }
 
+/// A fresh directory under the system temp dir, named by `tag`, pid and nanos (not deleted).
+pub fn temp_root(tag: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    let pid = std::process::id();
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+    p.push(format!("rssify-{}-{}-{}", tag, pid, nanos));
+    std::fs::create_dir_all(&p).unwrap();
+    p
+}
+
+pub fn feed(url: &str) -> Feed {
+    Feed {
+        id: FeedId::from_url(url),
+        url: url.into(),
+        title: None,
+        site_url: None,
+        etag: None,
+        last_modified: None,
+        active: true,
+        deactivated: None,
+        settings: Default::default(),
+    }
+}
+
+/// A bare entry of `feed`; tests override what they exercise with struct update syntax.
+pub fn entry(feed: &FeedId, id: &str) -> Entry {
+    Entry {
+        id: EntryId::new(id),
+        feed: feed.clone(),
+        url: None,
+        title: None,
+        published_ts: None,
+        updated_ts: None,
+        summary: None,
+        content: None,
+        content_text: None,
+        enclosures: Vec::new(),
+        warc_records: Vec::new(),
+        starred: false,
+        authors: Vec::new(),
+    }
+}
@@ -0,0 +1,28 @@
+/*
+Module: rssify_cli::tests::timefmt
+Purpose: Validate timestamp parsing used by --since
+*/
+
+#![allow(dead_code)]
+
+#[path = "../src/timefmt.rs"]
+mod timefmt;
+
+use timefmt::parse_ts;
+
+#[test]
+fn accepts_unix_seconds_and_rfc3339() {
+    assert_eq!(parse_ts("1700000000"), Ok(1_700_000_000));
+    assert_eq!(parse_ts("1970-01-01"), Ok(0));
+    assert_eq!(parse_ts("2023-11-14T22:13:20Z"), Ok(1_700_000_000));
+    assert_eq!(parse_ts("2023-11-14T23:13:20+01:00"), Ok(1_700_000_000));
+    assert_eq!(parse_ts("2023-11-14T22:13:20.123Z"), Ok(1_700_000_000));
+    assert_eq!(parse_ts("2000-03-01"), Ok(951_868_800));
+}
+
+#[test]
+fn rejects_garbage() {
+    for bad in ["yesterday", "2023-13-01", "2023-11-14T25:00:00Z", "2023-11"] {
+        assert!(parse_ts(bad).is_err(), "{bad}");
+    }
+}
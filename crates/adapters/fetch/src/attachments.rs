@@ -0,0 +1,158 @@
+/*
+Module: rssify_fetch::attachments
+Purpose: Find image references in entry HTML and rewrite them to archived local paths
+Public API surface: image_refs, rewrite_image_refs
+Invariants:
+ - Only `<img src>` values that resolve to absolute http(s) URLs are reported; data: URIs and
+   unparseable values are ignored.
+ - Rewriting touches nothing but the `src` (and drops `srcset`) of images found in the map;
+   all other bytes are copied through unchanged.
+*/
+
+use std::collections::BTreeMap;
+use url::Url;
+
+/// One attribute inside a tag: byte ranges of the whole attribute and of its value.
+struct Attr {
+    name: String,
+    whole: (usize, usize),
+    value: (usize, usize),
+}
+
+/// Absolute http(s) URLs of `<img src>` in `html`, in document order without duplicates.
+/// Relative references are resolved against `base` (usually the entry URL).
+pub fn image_refs(html: &str, base: Option<&str>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for (start, end) in img_tags(html) {
+        let tag = &html[start..end];
+        if let Some(url) = attrs(tag).iter().find(|a| a.name == "src").and_then(|a| resolve(&tag[a.value.0..a.value.1], base))
+            && !out.contains(&url)
+        {
+            out.push(url);
+        }
+    }
+    out
+}
+
+/// Copy of `html` with every `<img>` whose resolved src is in `local` pointing at the mapped
+/// path instead; `srcset` is removed from those tags so browsers do not fall back to remote copies.
+pub fn rewrite_image_refs(html: &str, base: Option<&str>, local: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut copied = 0;
+    for (start, end) in img_tags(html) {
+        let tag = &html[start..end];
+        let attrs = attrs(tag);
+        let Some(path) = attrs
+            .iter()
+            .find(|a| a.name == "src")
+            .and_then(|a| resolve(&tag[a.value.0..a.value.1], base))
+            .and_then(|u| local.get(&u))
+        else {
+            continue;
+        };
+        out.push_str(&html[copied..start]);
+        let mut pos = 0;
+        for a in &attrs {
+            match a.name.as_str() {
+                "src" => {
+                    out.push_str(&tag[pos..a.whole.0]);
+                    out.push_str(&format!("src=\"{}\"", path.replace('"', "&quot;")));
+                    pos = a.whole.1;
+                }
+                "srcset" => {
+                    out.push_str(tag[pos..a.whole.0].trim_end());
+                    pos = a.whole.1;
+                }
+                _ => {}
+            }
+        }
+        out.push_str(&tag[pos..]);
+        copied = end;
+    }
+    out.push_str(&html[copied..]);
+    out
+}
+
+fn resolve(raw: &str, base: Option<&str>) -> Option<String> {
+    let raw = raw.trim().replace("&amp;", "&");
+    if raw.is_empty() || raw.starts_with("data:") {
+        return None;
+    }
+    let url = match base.and_then(|b| Url::parse(b).ok()) {
+        Some(b) => b.join(&raw).ok()?,
+        None => Url::parse(&raw).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+/// Byte ranges of `<img ...>` tags (case-insensitive), skipping comments.
+fn img_tags(html: &str) -> Vec<(usize, usize)> {
+    let lower = html.to_ascii_lowercase();
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(rel) = lower[from..].find('<') {
+        let start = from + rel;
+        if lower[start..].starts_with("<!--") {
+            from = lower[start..].find("-->").map_or(lower.len(), |e| start + e + 3);
+            continue;
+        }
+        let Some(len) = lower[start..].find('>') else { break };
+        let end = start + len + 1;
+        let after = lower.as_bytes().get(start + 4).copied();
+        if lower[start..].starts_with("<img") && after.is_some_and(|b| b.is_ascii_whitespace() || b == b'/' || b == b'>') {
+            out.push((start, end));
+        }
+        from = end;
+    }
+    out
+}
+
+/// Attributes of a start tag (`<name a="1" b=2 c>`), names lowercased.
+fn attrs(tag: &str) -> Vec<Attr> {
+    let b = tag.as_bytes();
+    let mut i = 1;
+    while i < b.len() && !b[i].is_ascii_whitespace() && b[i] != b'>' && b[i] != b'/' {
+        i += 1;
+    }
+    let mut out = Vec::new();
+    loop {
+        while i < b.len() && (b[i].is_ascii_whitespace() || b[i] == b'/') {
+            i += 1;
+        }
+        if i >= b.len() || b[i] == b'>' {
+            return out;
+        }
+        let name_start = i;
+        while i < b.len() && !b[i].is_ascii_whitespace() && !matches!(b[i], b'=' | b'>' | b'/') {
+            i += 1;
+        }
+        let name = tag[name_start..i].to_ascii_lowercase();
+        let mut j = i;
+        while j < b.len() && b[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        if j >= b.len() || b[j] != b'=' {
+            out.push(Attr { name, whole: (name_start, i), value: (i, i) });
+            continue;
+        }
+        j += 1;
+        while j < b.len() && b[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        let (value, end) = match b.get(j) {
+            Some(&q) if q == b'"' || q == b'\'' => {
+                let close = tag[j + 1..].find(q as char).map_or(b.len(), |k| j + 1 + k);
+                ((j + 1, close), (close + 1).min(b.len()))
+            }
+            _ => {
+                let mut k = j;
+                while k < b.len() && !b[k].is_ascii_whitespace() && b[k] != b'>' {
+                    k += 1;
+                }
+                ((j, k), k)
+            }
+        };
+        out.push(Attr { name, whole: (name_start, end), value });
+        i = end;
+    }
+}
@@ -0,0 +1,117 @@
+/*
+Module: rssify_fetch::body
+Purpose: Stream a response body through Content-Encoding decoders with size caps
+Public API surface: read_body, HINT_TOO_LARGE, HINT_UNSUPPORTED_ENCODING
+Invariants:
+ - Never buffers more than the caps allow: raw bytes are counted before decoding,
+   decoded bytes after, and reading stops at the first byte over either cap.
+ - Failures map onto FetchOutcome so transports can return them unchanged.
+*/
+
+use crate::limits::SizeLimits;
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use rssify_core::FetchOutcome;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Hint carried by `PermanentFailure` when a body exceeds a cap.
+pub const HINT_TOO_LARGE: &str = "too large";
+/// Hint carried by `PermanentFailure` for encodings we cannot undo.
+pub const HINT_UNSUPPORTED_ENCODING: &str = "unsupported content-encoding";
+
+/// Read `raw` to the end, undoing `content_encoding` (gzip, deflate, br, identity;
+/// comma-separated lists are undone last-first) while enforcing `limits`.
+pub fn read_body<'a, R: Read + 'a>(
+    raw: R,
+    content_encoding: Option<&str>,
+    limits: &SizeLimits,
+) -> Result<Vec<u8>, FetchOutcome> {
+    let mut reader: Box<dyn Read + 'a> = Box::new(Capped::new(raw, limits.max_bytes));
+    if let Some(enc) = content_encoding {
+        for token in enc.split(',').map(str::trim).rev() {
+            reader = match token.to_ascii_lowercase().as_str() {
+                "" | "identity" => reader,
+                "gzip" | "x-gzip" => Box::new(MultiGzDecoder::new(reader)),
+                "deflate" => deflate_decoder(reader),
+                "br" => Box::new(brotli::Decompressor::new(reader, 4096)),
+                _ => {
+                    return Err(FetchOutcome::PermanentFailure {
+                        hint: Some(HINT_UNSUPPORTED_ENCODING.to_string()),
+                    });
+                }
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    match Capped::new(reader, limits.max_decoded_bytes).read_to_end(&mut out) {
+        Ok(_) => Ok(out),
+        Err(e) if is_too_large(&e) => Err(too_large()),
+        Err(e) if matches!(e.kind(), io::ErrorKind::InvalidData | io::ErrorKind::InvalidInput) => {
+            Err(FetchOutcome::PermanentFailure {
+                hint: Some(format!("bad body encoding: {e}")),
+            })
+        }
+        Err(e) => Err(FetchOutcome::TransientFailure {
+            hint: Some(e.to_string()),
+        }),
+    }
+}
+
+pub(crate) fn too_large() -> FetchOutcome {
+    FetchOutcome::PermanentFailure {
+        hint: Some(HINT_TOO_LARGE.to_string()),
+    }
+}
+
+/// HTTP "deflate" should be zlib-wrapped, but some servers send raw deflate.
+fn deflate_decoder<'a>(reader: Box<dyn Read + 'a>) -> Box<dyn Read + 'a> {
+    let mut buf = BufReader::new(reader);
+    let zlib = match buf.fill_buf() {
+        Ok(h) if h.len() >= 2 => h[0] & 0x0F == 8 && (u16::from(h[0]) << 8 | u16::from(h[1])) % 31 == 0,
+        _ => true,
+    };
+    if zlib {
+        Box::new(ZlibDecoder::new(buf))
+    } else {
+        Box::new(DeflateDecoder::new(buf))
+    }
+}
+
+/// Reader that fails with `TooLarge` once more than `left` bytes have passed through.
+struct Capped<R> {
+    inner: R,
+    left: u64,
+}
+
+impl<R> Capped<R> {
+    fn new(inner: R, cap: u64) -> Self {
+        Self { inner, left: cap }
+    }
+}
+
+impl<R: Read> Read for Capped<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.left = self
+            .left
+            .checked_sub(n as u64)
+            .ok_or_else(|| io::Error::other(TooLarge))?;
+        Ok(n)
+    }
+}
+
+#[derive(Debug)]
+struct TooLarge;
+
+impl fmt::Display for TooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(HINT_TOO_LARGE)
+    }
+}
+
+impl std::error::Error for TooLarge {}
+
+fn is_too_large(e: &io::Error) -> bool {
+    e.get_ref().is_some_and(|inner| inner.is::<TooLarge>())
+}
@@ -0,0 +1,164 @@
+/*
+Module: rssify_fetch::cassette
+Purpose: Record fetcher responses into a cassette directory and replay them offline
+Public API surface: RecordFetcher::new, ReplayFetcher::open, cassette_key, HINT_NOT_RECORDED
+Invariants:
+ - One interaction per URL: <key>.json holds status/headers/timing (or the error outcome),
+   <key>.body holds the exact body bytes. Re-recording a URL overwrites it.
+ - Keys are FNV-1a 64 of the URL, stable across Rust versions and platforms; replay checks the
+   stored URL, so a key collision fails instead of serving another URL's response.
+ - Credential headers (Set-Cookie, Cookie, Authorization, Proxy-Authorization) are stored as
+   "[redacted]", the same set the WARC writer redacts.
+ - Replay never touches the network; unknown URLs fail permanently, except an unrecorded
+   /robots.txt, which replays as 404 (no rules) so cassettes taken before robots checks, or
+   by hand, keep working behind RobotsFetcher.
+*/
+
+use crate::warc::redact;
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Hint carried by `PermanentFailure` when replay has no interaction for a URL.
+pub const HINT_NOT_RECORDED: &str = "not in cassette";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    #[serde(default)]
+    elapsed_ms: u64,
+    /// Set when the transport failed before producing a response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<FetchOutcome>,
+}
+
+/// File stem used for `url` inside a cassette directory.
+pub fn cassette_key(url: &str) -> String {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in url.bytes() {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{h:016x}")
+}
+
+/// Passes requests through and writes every result into the cassette.
+pub struct RecordFetcher<F> {
+    inner: F,
+    dir: PathBuf,
+}
+
+impl<F: Fetcher> RecordFetcher<F> {
+    pub fn new(inner: F, dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { inner, dir })
+    }
+
+    fn write(&self, url: &str, result: &Result<FetchResponse, FetchOutcome>) -> io::Result<()> {
+        let key = cassette_key(url);
+        let interaction = match result {
+            Ok(resp) => {
+                write_atomic(&self.dir.join(format!("{key}.body")), &resp.body)?;
+                Interaction {
+                    url: url.to_string(),
+                    status: Some(resp.status),
+                    headers: resp.headers.iter().map(|(k, v)| (k.clone(), redact(k, v).to_string())).collect(),
+                    elapsed_ms: resp.elapsed_ms,
+                    error: None,
+                }
+            }
+            Err(outcome) => Interaction {
+                url: url.to_string(),
+                status: None,
+                headers: Vec::new(),
+                elapsed_ms: 0,
+                error: Some(outcome.clone()),
+            },
+        };
+        let json = serde_json::to_vec_pretty(&interaction).map_err(io::Error::other)?;
+        write_atomic(&self.dir.join(format!("{key}.json")), &json)
+    }
+}
+
+impl<F: Fetcher> Fetcher for RecordFetcher<F> {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        let result = self.inner.fetch(req);
+        self.write(&req.url, &result).map_err(|e| FetchOutcome::TransientFailure {
+            hint: Some(format!("cassette write: {e}")),
+        })?;
+        result
+    }
+}
+
+/// Serves interactions from a cassette directory.
+pub struct ReplayFetcher {
+    dir: PathBuf,
+}
+
+impl ReplayFetcher {
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("cassette dir not found: {}", dir.display()),
+            ));
+        }
+        Ok(Self { dir })
+    }
+}
+
+impl Fetcher for ReplayFetcher {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        let key = cassette_key(&req.url);
+        let broken = |e: String| FetchOutcome::PermanentFailure {
+            hint: Some(format!("bad cassette entry {key}: {e}")),
+        };
+        let json = match fs::read(self.dir.join(format!("{key}.json"))) {
+            Ok(b) => b,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && is_robots_txt(&req.url) => {
+                return Ok(FetchResponse { status: 404, headers: Vec::new(), body: Vec::new(), elapsed_ms: 0 });
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(FetchOutcome::PermanentFailure {
+                    hint: Some(HINT_NOT_RECORDED.to_string()),
+                });
+            }
+            Err(e) => return Err(broken(e.to_string())),
+        };
+        let rec: Interaction = serde_json::from_slice(&json).map_err(|e| broken(e.to_string()))?;
+        if rec.url != req.url {
+            return Err(broken(format!("recorded for {}", rec.url)));
+        }
+        if let Some(err) = rec.error {
+            return Err(err);
+        }
+        let status = rec.status.ok_or_else(|| broken("missing status".to_string()))?;
+        let body = fs::read(self.dir.join(format!("{key}.body"))).map_err(|e| broken(e.to_string()))?;
+        Ok(FetchResponse {
+            status,
+            headers: rec.headers,
+            body,
+            elapsed_ms: rec.elapsed_ms,
+        })
+    }
+}
+
+fn is_robots_txt(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|u| u.path() == "/robots.txt")
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
@@ -0,0 +1,46 @@
+/*
+Module: rssify_fetch::clock
+Purpose: Millisecond clock and sleep seam so pacing policies are testable without waiting
+Public API surface: Clock, SystemClock
+Invariants: now_ms is monotonic for a given clock; sleep_ms(0) returns immediately.
+*/
+
+use std::time::{Duration, Instant};
+
+/// Time source used by pacing wrappers (crawl-delay, rate limits).
+pub trait Clock {
+    fn now_ms(&self) -> u64;
+    fn sleep_ms(&self, ms: u64);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now_ms(&self) -> u64 {
+        (**self).now_ms()
+    }
+    fn sleep_ms(&self, ms: u64) {
+        (**self).sleep_ms(ms);
+    }
+}
+
+/// Monotonic wall clock backed by `Instant` and `thread::sleep`.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self { origin: Instant::now() }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        u64::try_from(self.origin.elapsed().as_millis()).unwrap_or(u64::MAX)
+    }
+    fn sleep_ms(&self, ms: u64) {
+        if ms > 0 {
+            std::thread::sleep(Duration::from_millis(ms));
+        }
+    }
+}
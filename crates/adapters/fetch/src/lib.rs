@@ -0,0 +1,42 @@
+/*
+Module: rssify_fetch::lib
+Purpose: Module glue and public re-exports for the fetch adapter.
+Public API surface: SizeLimits, read_body, RedirectFetcher, SsrfPolicy, is_blocked_ip,
+                    RobotsFetcher, RobotsRules, Clock, SystemClock,
+                    RecordFetcher, ReplayFetcher, cassette_key, Secrets, RateLimiter, RateLimits,
+                    extract_article, Article, image_refs, rewrite_image_refs, Warc, WarcFetcher,
+                    HINT_*
+Invariants:
+ - No transport lives here yet; helpers and wrappers sit on rssify_core::Fetcher.
+ - Keep this file minimal and under 200 LOC.
+*/
+
+mod attachments;
+mod body;
+mod cassette;
+mod clock;
+mod limits;
+mod polite;
+mod ratelimit;
+mod readability;
+mod redirect;
+mod robots;
+mod secrets;
+mod ssrf;
+mod warc;
+
+pub use attachments::{image_refs, rewrite_image_refs};
+pub use body::{read_body, HINT_TOO_LARGE, HINT_UNSUPPORTED_ENCODING};
+pub use cassette::{cassette_key, RecordFetcher, ReplayFetcher, HINT_NOT_RECORDED};
+pub use clock::{Clock, SystemClock};
+pub use limits::SizeLimits;
+pub use polite::{
+    RobotsFetcher, DEFAULT_ROBOTS_TTL_MS, HINT_ROBOTS_DISALLOWED, HINT_ROBOTS_UNAVAILABLE,
+};
+pub use ratelimit::{registrable_domain, Rate, RateLimiter, RateLimits};
+pub use readability::{extract_article, Article};
+pub use redirect::{RedirectFetcher, HINT_TOO_MANY_REDIRECTS};
+pub use robots::{RobotsRules, MAX_CRAWL_DELAY_MS};
+pub use secrets::{Secrets, ENV_SECRETS};
+pub use ssrf::{is_blocked_ip, SsrfPolicy, HINT_BLOCKED_ADDRESS, HINT_BLOCKED_SCHEME};
+pub use warc::{Warc, WarcFetcher, DEFAULT_WARC_FILE_BYTES, HINT_WARC_WRITE};
@@ -0,0 +1,62 @@
+/*
+Module: rssify_fetch::limits
+Purpose: Download and decompression size caps (global default plus per-feed override)
+Public API surface: SizeLimits::{default, for_feed, check_declared, apply}
+Invariants: The decoded cap is never below the download cap; pure, no I/O
+*/
+
+use crate::body::{read_body, too_large};
+use rssify_core::{Feed, FetchOutcome, FetchResponse};
+
+/// Byte caps applied while reading a response body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeLimits {
+    /// Maximum bytes read off the wire (before content decoding).
+    pub max_bytes: u64,
+    /// Maximum bytes produced by content decoding (decompression-bomb guard).
+    pub max_decoded_bytes: u64,
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: 10 * 1024 * 1024,
+            max_decoded_bytes: 50 * 1024 * 1024,
+        }
+    }
+}
+
+impl SizeLimits {
+    /// Apply the feed's `settings.max_bytes` override, if any.
+    pub fn for_feed(&self, feed: &Feed) -> Self {
+        match feed.settings.max_bytes {
+            Some(max) => Self {
+                max_bytes: max,
+                max_decoded_bytes: self.max_decoded_bytes.max(max),
+            },
+            None => *self,
+        }
+    }
+
+    /// Reject early when the server announces a Content-Length above the cap.
+    pub fn check_declared(&self, content_length: Option<u64>) -> Result<(), FetchOutcome> {
+        match content_length {
+            Some(n) if n > self.max_bytes => Err(too_large()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Vet a response from the fetcher stack: reject an oversize Content-Length, then re-read the
+    /// body through `read_body` so the caps hold and any Content-Encoding is undone. Decoded
+    /// responses lose their Content-Encoding and Content-Length headers.
+    pub fn apply(&self, mut resp: FetchResponse) -> Result<FetchResponse, FetchOutcome> {
+        self.check_declared(resp.header("content-length").and_then(|v| v.trim().parse().ok()))?;
+        let encoding = resp.header("content-encoding").map(str::to_string);
+        resp.body = read_body(resp.body.as_slice(), encoding.as_deref(), self)?;
+        if encoding.is_some() {
+            resp.headers
+                .retain(|(k, _)| !k.eq_ignore_ascii_case("content-encoding") && !k.eq_ignore_ascii_case("content-length"));
+        }
+        Ok(resp)
+    }
+}
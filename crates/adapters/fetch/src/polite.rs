@@ -0,0 +1,197 @@
+/*
+Module: rssify_fetch::polite
+Purpose: Fetcher wrapper enforcing robots.txt rules and Crawl-delay per origin
+Public API surface: RobotsFetcher::{new, with_clock, with_ttl_ms, with_guard}, HINT_ROBOTS_DISALLOWED,
+                    HINT_ROBOTS_UNAVAILABLE, DEFAULT_ROBOTS_TTL_MS
+Invariants:
+ - robots.txt is fetched through the wrapped fetcher (redirects followed, pinned to the
+   triggering request's vetted addresses) and cached per origin for its Cache-Control max-age, else the configured default TTL.
+ - Groups are matched against the User-Agent the request sends (its header, else the configured
+   one); the cached text is parsed once per agent.
+ - With a guard, every robots.txt hop is vetted like a feed hop, so a robots redirect never
+   reaches a blocked address (the rules are then unavailable).
+ - 4xx robots.txt allows everything; 5xx or transport failure blocks the request as
+   transient and is not cached (RFC 9309 "unreachable").
+ - The host lock is never held across network I/O or sleeps.
+Notes: Wrap as RedirectFetcher::new(RobotsFetcher::new(..)) so every redirect hop is checked.
+*/
+
+use crate::clock::{Clock, SystemClock};
+use crate::redirect::RedirectFetcher;
+use crate::robots::RobotsRules;
+use crate::ssrf::SsrfPolicy;
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use url::Url;
+
+/// Hint carried by `PermanentFailure` when robots.txt disallows the URL.
+pub const HINT_ROBOTS_DISALLOWED: &str = "robots disallowed";
+/// Hint carried by `TransientFailure` when robots.txt could not be retrieved.
+pub const HINT_ROBOTS_UNAVAILABLE: &str = "robots unavailable";
+/// Cache lifetime for robots.txt without a usable max-age (24h).
+pub const DEFAULT_ROBOTS_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Cap on robots.txt bytes considered (RFC 9309 asks for at least 500 KiB).
+const MAX_ROBOTS_BYTES: usize = 512 * 1024;
+
+/// robots.txt as fetched for one origin, with the rules picked from it for each user agent.
+struct Robots {
+    text: String,
+    expires_ms: u64,
+    by_agent: HashMap<String, RobotsRules>,
+}
+
+impl Robots {
+    fn rules_for(&mut self, user_agent: &str) -> RobotsRules {
+        self.by_agent
+            .entry(user_agent.to_string())
+            .or_insert_with(|| RobotsRules::parse(&self.text, user_agent))
+            .clone()
+    }
+}
+
+#[derive(Default)]
+struct HostState {
+    robots: Option<Robots>,
+    next_slot_ms: u64,
+}
+
+pub struct RobotsFetcher<F, C = SystemClock> {
+    inner: F,
+    user_agent: String,
+    clock: C,
+    ttl_ms: u64,
+    guard: Option<SsrfPolicy>,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+impl<F: Fetcher> RobotsFetcher<F, SystemClock> {
+    /// `user_agent` is matched against robots groups for requests without a User-Agent header;
+    /// transports should send the same value.
+    pub fn new(inner: F, user_agent: impl Into<String>) -> Self {
+        Self {
+            inner,
+            user_agent: user_agent.into(),
+            clock: SystemClock::default(),
+            ttl_ms: DEFAULT_ROBOTS_TTL_MS,
+            guard: None,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<F: Fetcher, C: Clock> RobotsFetcher<F, C> {
+    pub fn with_clock<C2: Clock>(self, clock: C2) -> RobotsFetcher<F, C2> {
+        RobotsFetcher {
+            inner: self.inner,
+            user_agent: self.user_agent,
+            clock,
+            ttl_ms: self.ttl_ms,
+            guard: self.guard,
+            hosts: self.hosts,
+        }
+    }
+
+    pub fn with_ttl_ms(mut self, ttl_ms: u64) -> Self {
+        self.ttl_ms = ttl_ms;
+        self
+    }
+
+    /// Vet every robots.txt hop with `policy`; give it the same policy as the outer
+    /// `RedirectFetcher`.
+    pub fn with_guard(mut self, policy: SsrfPolicy) -> Self {
+        self.guard = Some(policy);
+        self
+    }
+
+    /// Rules of `origin` for `user_agent`; `pinned` are the vetted addresses of the request
+    /// that needs them (same origin).
+    fn rules_for(&self, origin: &str, user_agent: &str, pinned: &[SocketAddr]) -> Result<RobotsRules, FetchOutcome> {
+        let now = self.clock.now_ms();
+        if let Some(robots) = self.lock().get_mut(origin).and_then(|h| h.robots.as_mut())
+            && robots.expires_ms > now
+        {
+            return Ok(robots.rules_for(user_agent));
+        }
+        let req = FetchRequest {
+            url: format!("{origin}/robots.txt"),
+            headers: vec![("User-Agent".to_string(), user_agent.to_string())],
+            pinned_addrs: pinned.to_vec(),
+            ..FetchRequest::default()
+        };
+        let unavailable = || FetchOutcome::TransientFailure {
+            hint: Some(HINT_ROBOTS_UNAVAILABLE.to_string()),
+        };
+        let redirects = match &self.guard {
+            Some(policy) => RedirectFetcher::new(&self.inner).with_guard(policy.clone()),
+            None => RedirectFetcher::new(&self.inner),
+        };
+        let resp = redirects.fetch(&req).map_err(|_| unavailable())?;
+        // An empty file has no groups, so 4xx parses to allow-all for every agent.
+        let (text, ttl) = match resp.status {
+            200..=299 => {
+                let body = &resp.body[..resp.body.len().min(MAX_ROBOTS_BYTES)];
+                (String::from_utf8_lossy(body).into_owned(), max_age_ms(&resp).unwrap_or(self.ttl_ms))
+            }
+            400..=499 => (String::new(), self.ttl_ms),
+            _ => return Err(unavailable()),
+        };
+        let expires_ms = self.clock.now_ms().saturating_add(ttl);
+        let mut robots = Robots { text, expires_ms, by_agent: HashMap::new() };
+        let rules = robots.rules_for(user_agent);
+        self.lock().entry(origin.to_string()).or_default().robots = Some(robots);
+        Ok(rules)
+    }
+
+    /// Reserve the next request slot for `origin` and return how long to wait for it.
+    fn reserve_slot(&self, origin: &str, delay_ms: u64) -> u64 {
+        let now = self.clock.now_ms();
+        let mut hosts = self.lock();
+        let host = hosts.entry(origin.to_string()).or_default();
+        let start = host.next_slot_ms.max(now);
+        host.next_slot_ms = start.saturating_add(delay_ms);
+        start - now
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, HostState>> {
+        self.hosts.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<F: Fetcher, C: Clock> Fetcher for RobotsFetcher<F, C> {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        let url = Url::parse(&req.url).map_err(|e| FetchOutcome::PermanentFailure {
+            hint: Some(format!("invalid url: {e}")),
+        })?;
+        let origin = url.origin().ascii_serialization();
+        let path = match url.query() {
+            Some(q) => format!("{}?{q}", url.path()),
+            None => url.path().to_string(),
+        };
+        let user_agent = req
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("user-agent"))
+            .map_or(self.user_agent.as_str(), |(_, v)| v.as_str());
+        let rules = self.rules_for(&origin, user_agent, &req.pinned_addrs)?;
+        if !rules.is_allowed(&path) {
+            return Err(FetchOutcome::PermanentFailure {
+                hint: Some(HINT_ROBOTS_DISALLOWED.to_string()),
+            });
+        }
+        if let Some(delay) = rules.crawl_delay_ms() {
+            self.clock.sleep_ms(self.reserve_slot(&origin, delay));
+        }
+        self.inner.fetch(req)
+    }
+}
+
+fn max_age_ms(resp: &FetchResponse) -> Option<u64> {
+    resp.header("cache-control")?
+        .split(',')
+        .filter_map(|d| d.trim().strip_prefix("max-age="))
+        .find_map(|v| v.trim().parse::<u64>().ok())
+        .map(|s| s.saturating_mul(1000))
+}
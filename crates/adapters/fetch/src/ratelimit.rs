@@ -0,0 +1,143 @@
+/*
+Module: rssify_fetch::ratelimit
+Purpose: Per-domain token-bucket request ceilings shared by every fetch in a run
+Public API surface: Rate, RateLimits::{load, rate_for}, RateLimiter::{new, with_clock, acquire},
+                    registrable_domain
+Invariants:
+ - Buckets are keyed by registrable domain, so a.substack.com and b.substack.com share one.
+ - acquire reserves a token under the lock and sleeps after releasing it; concurrent callers
+   queue behind each other's reservations instead of bursting together.
+ - Domains without a configured or default rate are never delayed.
+Notes: registrable_domain uses a small built-in list of multi-label public suffixes, not the
+       full Public Suffix List; list more specific domains explicitly in the config if needed.
+*/
+
+use crate::clock::{Clock, SystemClock};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use url::Url;
+
+/// Multi-label public suffixes under which the registrable domain has three labels.
+const MULTI_LABEL_SUFFIXES: &[&str] = &[
+    "co.uk", "org.uk", "ac.uk", "gov.uk", "me.uk", "com.au", "net.au", "org.au", "co.nz", "co.jp",
+    "ne.jp", "or.jp", "com.br", "com.cn", "com.tw", "co.in", "co.kr", "co.za", "com.mx", "github.io",
+    "gitlab.io", "blogspot.com", "wordpress.com", "tumblr.com", "netlify.app", "pages.dev",
+];
+
+/// Request ceiling for one domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Rate {
+    pub per_minute: u32,
+    /// Requests allowed back to back before spacing kicks in.
+    #[serde(default = "one")]
+    pub burst: u32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+/// `{"default": {"per_minute": 60}, "domains": {"substack.com": {"per_minute": 20, "burst": 2}}}`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct RateLimits {
+    #[serde(default)]
+    pub default: Option<Rate>,
+    #[serde(default)]
+    pub domains: BTreeMap<String, Rate>,
+}
+
+impl RateLimits {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = std::fs::read(path.as_ref())?;
+        serde_json::from_slice(&data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("invalid rate limits: {e}")))
+    }
+
+    /// Configured rate for a registrable domain; keys are matched after normalizing them too.
+    pub fn rate_for(&self, domain: &str) -> Option<Rate> {
+        self.domains
+            .iter()
+            .find(|(k, _)| registrable_domain(k) == domain)
+            .map(|(_, r)| *r)
+            .or(self.default)
+            .filter(|r| r.per_minute > 0)
+    }
+}
+
+/// Registrable domain ("eTLD+1") of a host: foo.substack.com -> substack.com,
+/// news.bbc.co.uk -> bbc.co.uk. IP literals and single labels are returned as is.
+pub fn registrable_domain(host: &str) -> String {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    if host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[') {
+        return host;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let keep = if labels.len() >= 3 && MULTI_LABEL_SUFFIXES.contains(&labels[labels.len() - 2..].join(".").as_str()) {
+        3
+    } else {
+        2
+    };
+    labels[labels.len().saturating_sub(keep)..].join(".")
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// May go negative: outstanding reservations not yet refilled.
+    tokens: f64,
+    last_ms: u64,
+}
+
+pub struct RateLimiter<C = SystemClock> {
+    limits: RateLimits,
+    clock: C,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter<SystemClock> {
+    pub fn new(limits: RateLimits) -> Self {
+        Self { limits, clock: SystemClock::default(), buckets: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl<C: Clock> RateLimiter<C> {
+    pub fn with_clock<D: Clock>(self, clock: D) -> RateLimiter<D> {
+        RateLimiter { limits: self.limits, clock, buckets: self.buckets }
+    }
+
+    /// Wait until `url`'s domain has a token, then take it. Returns the milliseconds waited.
+    pub fn acquire(&self, url: &str) -> u64 {
+        let Some(host) = Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string)) else {
+            return 0;
+        };
+        let domain = registrable_domain(&host);
+        let Some(rate) = self.limits.rate_for(&domain) else {
+            return 0;
+        };
+        let wait = self.reserve(domain, rate);
+        self.clock.sleep_ms(wait);
+        wait
+    }
+
+    fn reserve(&self, domain: String, rate: Rate) -> u64 {
+        let per_ms = f64::from(rate.per_minute) / 60_000.0;
+        let cap = f64::from(rate.burst.max(1));
+        let now = self.clock.now_ms();
+        let mut buckets = self.buckets.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let b = buckets.entry(domain).or_insert(Bucket { tokens: cap, last_ms: now });
+        #[allow(clippy::cast_precision_loss)]
+        let refill = now.saturating_sub(b.last_ms) as f64 * per_ms;
+        b.tokens = (b.tokens + refill).min(cap);
+        b.last_ms = now;
+        b.tokens -= 1.0;
+        if b.tokens >= 0.0 {
+            0
+        } else {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let wait = (-b.tokens / per_ms).ceil() as u64;
+            wait
+        }
+    }
+}
@@ -0,0 +1,249 @@
+/*
+Module: rssify_fetch::readability
+Purpose: Readability-style main-content extraction from article HTML (no DOM dependency)
+Public API surface: extract_article, Article
+Invariants:
+ - Pure and deterministic; never executes or keeps scripts, styles, forms or page chrome.
+ - Output HTML is rebuilt from extracted text (escaped), so it carries no attributes or markup
+   from the source beyond headings and paragraphs.
+Notes: Heuristic, after Mozilla Readability: text blocks of 25+ chars score their container
+       (1 + commas + length/100, capped at 3) and half of that to the grandparent; the winner is
+       the container with the best score discounted by link density.
+*/
+
+/// Readable content of one page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Article {
+    pub title: Option<String>,
+    /// Cleaned HTML: `<article>` with `<h2>`..`<h6>` and `<p>` children.
+    pub html: String,
+    /// Plain text, paragraphs separated by blank lines.
+    pub text: String,
+}
+
+const SKIP: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside", "form",
+    "button", "select", "textarea",
+];
+const VOID: &[&str] = &["br", "img", "hr", "meta", "link", "input", "source", "wbr", "col", "area", "base", "embed"];
+const INLINE: &[&str] = &["a", "span", "em", "strong", "b", "i", "u", "small", "code", "sup", "sub", "abbr", "mark", "time", "cite", "q", "s"];
+const MIN_BLOCK_CHARS: usize = 25;
+
+struct Node {
+    tag: String,
+    parent: Option<usize>,
+    score: f64,
+    text_len: usize,
+    link_len: usize,
+}
+
+struct Block {
+    heading: Option<u8>,
+    text: String,
+    container: usize,
+}
+
+/// Extract the main content of `html`; `None` when nothing article-like was found.
+pub fn extract_article(html: &str) -> Option<Article> {
+    let mut nodes = vec![Node { tag: "#root".into(), parent: None, score: 0.0, text_len: 0, link_len: 0 }];
+    let mut stack: Vec<(usize, String, usize)> = vec![(0, String::new(), 0)]; // (node, own text, link chars)
+    let mut blocks: Vec<Block> = Vec::new();
+    let (mut title, mut in_title, mut skip_depth, mut in_link) = (None::<String>, false, 0usize, 0usize);
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut stack, rest, in_link > 0, skip_depth, in_title, &mut title);
+            break;
+        };
+        push_text(&mut stack, &rest[..lt], in_link > 0, skip_depth, in_title, &mut title);
+        rest = &rest[lt..];
+        if let Some(body) = rest.strip_prefix("<!--") {
+            rest = body.find("-->").map_or("", |e| &body[e + 3..]);
+            continue;
+        }
+        let Some(gt) = rest.find('>') else { break };
+        let raw = &rest[1..gt];
+        rest = &rest[gt + 1..];
+        let closing = raw.starts_with('/');
+        let name: String = raw
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if name.is_empty() {
+            continue; // doctype, processing instructions, stray '<'
+        }
+        if SKIP.contains(&name.as_str()) {
+            skip_depth = if closing { skip_depth.saturating_sub(1) } else { skip_depth + usize::from(!raw.ends_with('/')) };
+            continue;
+        }
+        if name == "title" {
+            in_title = !closing;
+            continue;
+        }
+        if skip_depth > 0 || VOID.contains(&name.as_str()) || raw.ends_with('/') {
+            if name == "br" && skip_depth == 0 {
+                push_text(&mut stack, " ", false, 0, false, &mut title);
+            }
+            continue;
+        }
+        if INLINE.contains(&name.as_str()) {
+            if name == "a" {
+                in_link = if closing { in_link.saturating_sub(1) } else { in_link + 1 };
+            }
+            continue;
+        }
+        if closing {
+            if let Some(pos) = stack.iter().rposition(|(n, _, _)| nodes[*n].tag == name) {
+                while stack.len() > pos.max(1) {
+                    close(&mut nodes, &mut stack, &mut blocks);
+                }
+            }
+            continue;
+        }
+        // A new block implicitly ends an open paragraph.
+        if stack.last().is_some_and(|(n, _, _)| nodes[*n].tag == "p") {
+            close(&mut nodes, &mut stack, &mut blocks);
+        }
+        let parent = stack.last().map(|(n, _, _)| *n);
+        nodes.push(Node { tag: name, parent, score: 0.0, text_len: 0, link_len: 0 });
+        stack.push((nodes.len() - 1, String::new(), 0));
+    }
+    while stack.len() > 1 {
+        close(&mut nodes, &mut stack, &mut blocks);
+    }
+    let best = (1..nodes.len())
+        .filter(|&i| nodes[i].score > 0.0)
+        .map(|i| {
+            #[allow(clippy::cast_precision_loss)]
+            let density = nodes[i].link_len as f64 / nodes[i].text_len.max(1) as f64;
+            (i, nodes[i].score * (1.0 - density))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))?
+        .0;
+    let inside = |mut n: usize| loop {
+        if n == best {
+            return true;
+        }
+        match nodes[n].parent {
+            Some(p) => n = p,
+            None => return false,
+        }
+    };
+    let kept: Vec<&Block> = blocks.iter().filter(|b| inside(b.container)).collect();
+    let mut out_html = String::from("<article>");
+    let mut text = Vec::new();
+    for b in kept.iter().filter(|b| b.heading.is_some() || b.text.chars().count() >= MIN_BLOCK_CHARS) {
+        match b.heading {
+            Some(level) => out_html.push_str(&format!("<h{0}>{1}</h{0}>", level.max(2), escape(&b.text))),
+            None => out_html.push_str(&format!("<p>{}</p>", escape(&b.text))),
+        }
+        text.push(b.text.clone());
+    }
+    out_html.push_str("</article>");
+    Some(Article { title: title.map(|t| collapse(&t)).filter(|t| !t.is_empty()), html: out_html, text: text.join("\n\n") })
+}
+
+fn push_text(
+    stack: &mut [(usize, String, usize)],
+    raw: &str,
+    in_link: bool,
+    skip_depth: usize,
+    in_title: bool,
+    title: &mut Option<String>,
+) {
+    if raw.is_empty() || skip_depth > 0 {
+        return;
+    }
+    let decoded = decode_entities(raw);
+    if in_title {
+        title.get_or_insert_with(String::new).push_str(&decoded);
+        return;
+    }
+    if let Some((_, own, links)) = stack.last_mut() {
+        if in_link {
+            *links += decoded.trim().chars().count();
+        }
+        own.push_str(&decoded);
+    }
+}
+
+/// Pop the innermost element; its own text becomes a block scored into its container.
+fn close(nodes: &mut [Node], stack: &mut Vec<(usize, String, usize)>, blocks: &mut Vec<Block>) {
+    let Some((n, own, links)) = stack.pop() else { return };
+    let text = collapse(&own);
+    if text.is_empty() {
+        return;
+    }
+    let tag = nodes[n].tag.as_str();
+    let heading = tag.strip_prefix('h').and_then(|d| d.parse::<u8>().ok()).filter(|d| (1..=6).contains(d));
+    let paragraph_like = matches!(tag, "p" | "pre" | "blockquote" | "li" | "dd" | "figcaption") || heading.is_some();
+    // Paragraphs score their parent; loose text scores the element holding it.
+    let container = if paragraph_like { nodes[n].parent.unwrap_or(0) } else { n };
+    let len = text.chars().count();
+    if heading.is_none() && len >= MIN_BLOCK_CHARS {
+        #[allow(clippy::cast_precision_loss)]
+        let score = 1.0 + text.matches(',').count() as f64 + (len as f64 / 100.0).min(3.0);
+        nodes[container].score += score;
+        nodes[container].text_len += len;
+        nodes[container].link_len += links;
+        if let Some(gp) = nodes[container].parent {
+            nodes[gp].score += score / 2.0;
+            nodes[gp].text_len += len;
+            nodes[gp].link_len += links;
+        }
+    }
+    blocks.push(Block { heading, text, container });
+}
+
+fn collapse(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&semi| semi <= 10).and_then(|semi| {
+            let name = &rest[1..semi];
+            let c = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => name.strip_prefix('#').and_then(|num| {
+                    let code = match num.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => num.parse().ok(),
+                    };
+                    code.and_then(char::from_u32)
+                }),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
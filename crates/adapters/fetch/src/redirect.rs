@@ -0,0 +1,87 @@
+/*
+Module: rssify_fetch::redirect
+Purpose: Follow redirects on top of a single-request transport, vetting every hop
+Public API surface: RedirectFetcher::{new, with_guard, max_redirects}, HINT_TOO_MANY_REDIRECTS
+Invariants:
+ - The optional SsrfPolicy runs before the first request and before each redirect hop, and
+   the addresses it vetted are pinned into that hop's request (no second DNS lookup).
+ - Pins never outlive the hop they were made for: a redirect clears them.
+ - Relative Location headers resolve against the URL that produced them.
+ - Credentials (Authorization, Cookie, Proxy-Authorization) are dropped once a hop leaves
+   the original origin.
+*/
+
+use crate::ssrf::SsrfPolicy;
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use url::Url;
+
+/// Hint carried by `PermanentFailure` when the redirect budget runs out.
+pub const HINT_TOO_MANY_REDIRECTS: &str = "too many redirects";
+
+const CREDENTIAL_HEADERS: [&str; 3] = ["authorization", "cookie", "proxy-authorization"];
+
+/// Wraps a transport that returns 3xx responses as-is and follows them.
+pub struct RedirectFetcher<F> {
+    inner: F,
+    max_redirects: u8,
+    guard: Option<SsrfPolicy>,
+}
+
+impl<F: Fetcher> RedirectFetcher<F> {
+    pub fn new(inner: F) -> Self {
+        Self {
+            inner,
+            max_redirects: 5,
+            guard: None,
+        }
+    }
+
+    /// Opt into address checks for the initial URL and every redirect target.
+    pub fn with_guard(mut self, policy: SsrfPolicy) -> Self {
+        self.guard = Some(policy);
+        self
+    }
+
+    pub fn max_redirects(mut self, n: u8) -> Self {
+        self.max_redirects = n;
+        self
+    }
+}
+
+impl<F: Fetcher> Fetcher for RedirectFetcher<F> {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        let mut current = req.clone();
+        for _ in 0..=self.max_redirects {
+            if let Some(guard) = &self.guard {
+                current.pinned_addrs = guard.resolve_checked(&current.url)?;
+            }
+            let resp = self.inner.fetch(&current)?;
+            if !resp.is_redirect() {
+                return Ok(resp);
+            }
+            let location = resp.header("location").unwrap_or_default();
+            current.url = Url::parse(&current.url)
+                .and_then(|base| base.join(location))
+                .map_err(|e| FetchOutcome::PermanentFailure {
+                    hint: Some(format!("bad redirect: {e}")),
+                })?
+                .to_string();
+            current.pinned_addrs.clear();
+            if !same_origin(&req.url, &current.url) {
+                current
+                    .headers
+                    .retain(|(k, _)| !CREDENTIAL_HEADERS.iter().any(|c| k.eq_ignore_ascii_case(c)));
+            }
+        }
+        Err(FetchOutcome::PermanentFailure {
+            hint: Some(HINT_TOO_MANY_REDIRECTS.to_string()),
+        })
+    }
+}
+
+fn same_origin(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    }
+}
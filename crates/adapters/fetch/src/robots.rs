@@ -0,0 +1,139 @@
+/*
+Module: rssify_fetch::robots
+Purpose: Parse robots.txt and answer allow/disallow and Crawl-delay for one user agent
+Public API surface: RobotsRules::{parse, allow_all, is_allowed, crawl_delay_ms}, MAX_CRAWL_DELAY_MS
+Invariants:
+ - Group selection follows RFC 9309: groups naming our product token win; otherwise `*`.
+ - The longest matching pattern decides; Allow wins ties. `*` and a trailing `$` are supported.
+ - /robots.txt itself is always allowed.
+*/
+
+/// Upper bound applied to Crawl-delay so a hostile file cannot stall a run.
+pub const MAX_CRAWL_DELAY_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    /// (allow, pattern) pairs from the selected groups.
+    rules: Vec<(bool, String)>,
+    crawl_delay_ms: Option<u64>,
+}
+
+#[derive(Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<(bool, String)>,
+    crawl_delay_ms: Option<u64>,
+}
+
+impl RobotsRules {
+    /// Rules that allow everything (missing or 4xx robots.txt).
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Parse `text` and keep only the groups that apply to `user_agent`
+    /// (e.g. `rssify/0.1 (+https://example.org)` matches `User-agent: rssify`).
+    pub fn parse(text: &str, user_agent: &str) -> Self {
+        let token = product_token(user_agent);
+        let mut groups: Vec<Group> = Vec::new();
+        let mut open_agents = false;
+        for raw in text.lines() {
+            let line = raw.split('#').next().unwrap_or("").trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (key, value) = (key.trim().to_ascii_lowercase(), value.trim());
+            match key.as_str() {
+                "user-agent" => {
+                    if !open_agents {
+                        groups.push(Group::default());
+                        open_agents = true;
+                    }
+                    if let Some(g) = groups.last_mut() {
+                        g.agents.push(value.to_ascii_lowercase());
+                    }
+                }
+                "allow" | "disallow" | "crawl-delay" => {
+                    open_agents = false;
+                    let Some(g) = groups.last_mut() else { continue };
+                    if key == "crawl-delay" {
+                        g.crawl_delay_ms = value
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|s| s.is_finite() && *s >= 0.0)
+                            .map(|s| ((s * 1000.0) as u64).min(MAX_CRAWL_DELAY_MS));
+                    } else if !value.is_empty() {
+                        g.rules.push((key == "allow", value.to_string()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let specific = |g: &Group| g.agents.iter().any(|a| a != "*" && *a == token);
+        let chosen: Vec<&Group> = if groups.iter().any(specific) {
+            groups.iter().filter(|g| specific(g)).collect()
+        } else {
+            groups.iter().filter(|g| g.agents.iter().any(|a| a == "*")).collect()
+        };
+        Self {
+            rules: chosen.iter().flat_map(|g| g.rules.iter().cloned()).collect(),
+            crawl_delay_ms: chosen.iter().filter_map(|g| g.crawl_delay_ms).max(),
+        }
+    }
+
+    /// Whether `path` (path plus optional `?query`) may be fetched.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+        let mut best: Option<(usize, bool)> = None;
+        for (allow, pattern) in &self.rules {
+            if !matches(pattern, path) {
+                continue;
+            }
+            let len = pattern.len();
+            best = match best {
+                Some((l, a)) if l > len || (l == len && a) => Some((l, a)),
+                _ => Some((len, *allow)),
+            };
+        }
+        best.is_none_or(|(_, allow)| allow)
+    }
+
+    pub fn crawl_delay_ms(&self) -> Option<u64> {
+        self.crawl_delay_ms
+    }
+}
+
+fn product_token(user_agent: &str) -> String {
+    user_agent
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// Prefix match with `*` wildcards and an optional `$` end anchor.
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pat, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pat.split('*').collect();
+    let Some(mut rest) = path.strip_prefix(parts[0]) else {
+        return false;
+    };
+    if parts.len() == 1 {
+        return !anchored || rest.is_empty();
+    }
+    for (i, part) in parts.iter().enumerate().skip(1) {
+        if anchored && i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
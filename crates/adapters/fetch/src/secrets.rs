@@ -0,0 +1,52 @@
+/*
+Module: rssify_fetch::secrets
+Purpose: Resolve SecretRef credentials from the environment or a separate secrets file
+Public API surface: Secrets::{load, from_map, lookup, lookup_with}, ENV_SECRETS
+Invariants:
+ - The secrets file is a flat JSON object of string values, kept outside the repo root.
+ - Values never appear in errors; only the reference (env:NAME / secret:key) does.
+*/
+
+use rssify_core::SecretRef;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// Environment variable naming the default secrets file.
+pub const ENV_SECRETS: &str = "RSSIFY_SECRETS";
+
+#[derive(Debug, Clone, Default)]
+pub struct Secrets {
+    file: BTreeMap<String, String>,
+}
+
+impl Secrets {
+    /// Read `{"key": "value", ...}` from `path`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = std::fs::read(path.as_ref())?;
+        let file = serde_json::from_slice(&data).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("secrets file must be a JSON object of strings: {e}"))
+        })?;
+        Ok(Self { file })
+    }
+
+    pub fn from_map(file: BTreeMap<String, String>) -> Self {
+        Self { file }
+    }
+
+    /// Resolve against the process environment and the loaded file.
+    pub fn lookup(&self, r: &SecretRef) -> Option<String> {
+        self.lookup_with(r, |name| std::env::var(name).ok())
+    }
+
+    /// Same as `lookup` with an injected environment (tests).
+    pub fn lookup_with<E>(&self, r: &SecretRef, env: E) -> Option<String>
+    where
+        E: Fn(&str) -> Option<String>,
+    {
+        match r {
+            SecretRef::Env(name) => env(name).filter(|v| !v.is_empty()),
+            SecretRef::Secret(key) => self.file.get(key).cloned(),
+        }
+    }
+}
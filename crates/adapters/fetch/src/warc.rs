@@ -0,0 +1,249 @@
+/*
+Module: rssify_fetch::warc
+Purpose: Write every request/response pair a fetcher sees into rotating WARC 1.1 files
+Public API surface: Warc, WarcFetcher, DEFAULT_WARC_FILE_BYTES, HINT_WARC_WRITE
+Invariants:
+ - Files are `rssify-<UTC yyyymmddhhmmss>-<pid>-<seq>.warc.gz`, each record its own gzip member;
+   every file starts with a warcinfo record. A file is closed once it reaches the size limit.
+ - A response record is followed by its request record (WARC-Concurrent-To the response).
+ - Bodies are stored as handed to rssify (Content-Encoding undone), so Content-Encoding and
+   Transfer-Encoding are dropped and Content-Length is rewritten to match.
+ - Credential headers (Authorization, Proxy-Authorization, Cookie, Set-Cookie) are redacted.
+ - Transport failures without a response are not recorded.
+*/
+
+use flate2::{write::GzEncoder, Compression};
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, RandomState};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Rotation threshold used when none is given (the customary 1 GB).
+pub const DEFAULT_WARC_FILE_BYTES: u64 = 1_000_000_000;
+/// Hint prefix of the `TransientFailure` returned when a pair cannot be archived.
+pub const HINT_WARC_WRITE: &str = "warc write failed";
+
+pub(crate) const REDACTED_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie", "set-cookie"];
+const DROPPED_HEADERS: &[&str] = &["content-encoding", "transfer-encoding", "content-length"];
+
+struct OpenFile {
+    file: File,
+    size: u64,
+    warcinfo_id: String,
+}
+
+struct State {
+    dir: PathBuf,
+    max_bytes: u64,
+    seq: u32,
+    current: Option<OpenFile>,
+    files: Vec<PathBuf>,
+    /// Response record ids written since the last `take_responses`.
+    pending: Vec<String>,
+}
+
+/// Shared handle to a WARC output directory; clones write to the same files.
+#[derive(Clone)]
+pub struct Warc(Arc<Mutex<State>>);
+
+impl Warc {
+    /// Write into `dir` (created if missing), starting a new file past `max_file_bytes`.
+    pub fn open(dir: impl AsRef<Path>, max_file_bytes: u64) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        let state = State {
+            dir: dir.as_ref().to_path_buf(),
+            max_bytes: max_file_bytes.max(1),
+            seq: 0,
+            current: None,
+            files: Vec::new(),
+            pending: Vec::new(),
+        };
+        Ok(Self(Arc::new(Mutex::new(state))))
+    }
+
+    /// Archive one exchange; returns the response record id.
+    pub fn record(&self, req: &FetchRequest, resp: &FetchResponse) -> io::Result<String> {
+        let mut st = self.lock();
+        let date = warc_date(unix_now());
+        let warcinfo_id = st.file(&date)?;
+        let response_id = record_id();
+        let mut block = format!("HTTP/1.1 {} {}\r\n", resp.status, reason(resp.status)).into_bytes();
+        for (k, v) in resp.headers.iter().filter(|(k, _)| !DROPPED_HEADERS.contains(&k.to_ascii_lowercase().as_str())) {
+            block.extend_from_slice(format!("{k}: {}\r\n", redact(k, v)).as_bytes());
+        }
+        block.extend_from_slice(format!("Content-Length: {}\r\n\r\n", resp.body.len()).as_bytes());
+        block.extend_from_slice(&resp.body);
+        let fields = [("WARC-Target-URI", req.url.as_str()), ("WARC-Warcinfo-ID", &warcinfo_id)];
+        st.append("response", &response_id, &date, "application/http;msgtype=response", &fields, &block)?;
+        let fields = [
+            ("WARC-Target-URI", req.url.as_str()),
+            ("WARC-Warcinfo-ID", &warcinfo_id),
+            ("WARC-Concurrent-To", &response_id),
+        ];
+        st.append("request", &record_id(), &date, "application/http;msgtype=request", &fields, request_block(req).as_bytes())?;
+        st.pending.push(response_id.clone());
+        Ok(response_id)
+    }
+
+    /// Response record ids written since the previous call, oldest first.
+    pub fn take_responses(&self) -> Vec<String> {
+        std::mem::take(&mut self.lock().pending)
+    }
+
+    /// Files written so far, oldest first.
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.lock().files.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl State {
+    /// The file to append to (rotating when full); returns its warcinfo record id.
+    fn file(&mut self, date: &str) -> io::Result<String> {
+        if let Some(f) = self.current.as_ref().filter(|f| f.size < self.max_bytes) {
+            return Ok(f.warcinfo_id.clone());
+        }
+        self.seq += 1;
+        let stamp: String = date.chars().filter(char::is_ascii_digit).collect();
+        let name = format!("rssify-{stamp}-{}-{:05}.warc.gz", std::process::id(), self.seq);
+        let path = self.dir.join(&name);
+        let file = OpenOptions::new().create_new(true).append(true).open(&path)?;
+        let warcinfo_id = record_id();
+        self.current = Some(OpenFile { file, size: 0, warcinfo_id: warcinfo_id.clone() });
+        self.files.push(path);
+        let info = format!(
+            "software: rssify/{}\r\nformat: WARC File Format 1.1\r\nconformsTo: https://iipc.github.io/warc-specifications/specifications/warc-format/warc-1.1/\r\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        self.append("warcinfo", &warcinfo_id, date, "application/warc-fields", &[("WARC-Filename", &name)], info.as_bytes())?;
+        Ok(warcinfo_id)
+    }
+
+    fn append(&mut self, kind: &str, id: &str, date: &str, ctype: &str, fields: &[(&str, &str)], block: &[u8]) -> io::Result<()> {
+        let mut head = format!("WARC/1.1\r\nWARC-Type: {kind}\r\nWARC-Record-ID: {id}\r\nWARC-Date: {date}\r\n");
+        for (k, v) in fields {
+            head.push_str(&format!("{k}: {v}\r\n"));
+        }
+        head.push_str(&format!("Content-Type: {ctype}\r\nContent-Length: {}\r\n\r\n", block.len()));
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(head.as_bytes())?;
+        gz.write_all(block)?;
+        gz.write_all(b"\r\n\r\n")?;
+        let member = gz.finish()?;
+        let Some(f) = self.current.as_mut() else { return Err(io::Error::other("no open warc file")) };
+        f.file.write_all(&member)?;
+        f.file.flush()?;
+        f.size += member.len() as u64;
+        Ok(())
+    }
+}
+
+/// Records every exchange of the wrapped fetcher; with no `Warc` it only passes through, so
+/// callers keep one fetcher type whether archiving is on or not.
+pub struct WarcFetcher<F> {
+    inner: F,
+    warc: Option<Warc>,
+}
+
+impl<F: Fetcher> WarcFetcher<F> {
+    pub fn new(inner: F, warc: Option<Warc>) -> Self {
+        Self { inner, warc }
+    }
+}
+
+impl<F: Fetcher> Fetcher for WarcFetcher<F> {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        let resp = self.inner.fetch(req)?;
+        if let Some(warc) = &self.warc {
+            // An unarchived response would silently break the preservation record, so fail soft.
+            warc.record(req, &resp)
+                .map_err(|e| FetchOutcome::TransientFailure { hint: Some(format!("{HINT_WARC_WRITE}: {e}")) })?;
+        }
+        Ok(resp)
+    }
+}
+
+fn request_block(req: &FetchRequest) -> String {
+    let (host, target) = url::Url::parse(&req.url).map_or_else(
+        |_| (String::new(), req.url.clone()),
+        |u| {
+            let host = u.host_str().unwrap_or("").to_string() + &u.port().map(|p| format!(":{p}")).unwrap_or_default();
+            (host, u[url::Position::BeforePath..url::Position::AfterQuery].to_string())
+        },
+    );
+    let mut out = format!("GET {target} HTTP/1.1\r\nHost: {host}\r\n");
+    let validators = [("If-None-Match", &req.etag), ("If-Modified-Since", &req.last_modified)];
+    for (k, v) in validators.iter().filter_map(|(k, v)| v.as_ref().map(|v| (*k, v))).chain(req.headers.iter().map(|(k, v)| (k.as_str(), v))) {
+        out.push_str(&format!("{k}: {}\r\n", redact(k, v)));
+    }
+    out.push_str("\r\n");
+    out
+}
+
+pub(crate) fn redact<'a>(name: &str, value: &'a str) -> &'a str {
+    if REDACTED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) { "[redacted]" } else { value }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        410 => "Gone",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+/// `<urn:uuid:...>` with 122 random bits (version 4 layout).
+fn record_id() -> String {
+    let a = RandomState::new().hash_one(SystemTime::now());
+    let b = RandomState::new().hash_one(a);
+    let hi = (a & 0xffff_ffff_ffff_0fff) | 0x4000;
+    let lo = (b & 0x3fff_ffff_ffff_ffff) | 0x8000_0000_0000_0000;
+    format!(
+        "<urn:uuid:{:08x}-{:04x}-{:04x}-{:04x}-{:012x}>",
+        hi >> 32,
+        (hi >> 16) & 0xffff,
+        hi & 0xffff,
+        lo >> 48,
+        lo & 0xffff_ffff_ffff
+    )
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(i64::MAX))
+}
+
+/// `YYYY-MM-DDThh:mm:ssZ` for unix seconds (civil-from-days, proleptic Gregorian).
+fn warc_date(ts: i64) -> String {
+    let (days, secs) = (ts.div_euclid(86_400), ts.rem_euclid(86_400));
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z", secs / 3600, secs / 60 % 60, secs % 60)
+}
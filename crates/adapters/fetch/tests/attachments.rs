@@ -0,0 +1,37 @@
+/*
+Module: rssify_fetch::tests::attachments
+Purpose: Validate image reference discovery and local-path rewriting in entry HTML
+*/
+
+use rssify_fetch::{image_refs, rewrite_image_refs};
+use std::collections::BTreeMap;
+
+const HTML: &str = r#"<p>Hi</p><IMG SRC="/a.png" alt="a"><!-- <img src="hidden.png"> -->
+<img alt='b' src='https://cdn.test/b.jpg?w=1&amp;h=2' srcset="b2.jpg 2x"><img src=data:image/png;base64,xx>
+<img src="/a.png"><img src=c.gif>"#;
+
+#[test]
+fn finds_resolved_unique_http_images_in_order() {
+    let refs = image_refs(HTML, Some("https://site.test/posts/1"));
+    assert_eq!(refs, [
+        "https://site.test/a.png",
+        "https://cdn.test/b.jpg?w=1&h=2",
+        "https://site.test/posts/c.gif",
+    ]);
+    assert_eq!(image_refs("<img src=rel.png>", None), Vec::<String>::new());
+}
+
+#[test]
+fn rewrites_only_mapped_images_and_drops_their_srcset() {
+    let local = BTreeMap::from([
+        ("https://cdn.test/b.jpg?w=1&h=2".to_string(), "blobs/sha256/ab/abc".to_string()),
+        ("https://site.test/a.png".to_string(), "blobs/sha256/cd/cde".to_string()),
+    ]);
+    let out = rewrite_image_refs(HTML, Some("https://site.test/posts/1"), &local);
+    assert_eq!(
+        out,
+        r#"<p>Hi</p><IMG src="blobs/sha256/cd/cde" alt="a"><!-- <img src="hidden.png"> -->
+<img alt='b' src="blobs/sha256/ab/abc"><img src=data:image/png;base64,xx>
+<img src="blobs/sha256/cd/cde"><img src=c.gif>"#
+    );
+}
@@ -0,0 +1,150 @@
+/*
+Module: rssify_fetch::tests::body
+Purpose: Validate size caps and streaming Content-Encoding decoding (incl. decompression bombs)
+*/
+
+use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use rssify_core::{Feed, FeedId, FeedSettings, FetchOutcome, FetchResponse};
+use rssify_fetch::{read_body, SizeLimits, HINT_TOO_LARGE, HINT_UNSUPPORTED_ENCODING};
+use std::io::Write;
+
+const XML: &[u8] = b"<rss><channel><title>t</title></channel></rss>";
+
+fn limits(max_bytes: u64, max_decoded_bytes: u64) -> SizeLimits {
+    SizeLimits { max_bytes, max_decoded_bytes }
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut enc = GzEncoder::new(Vec::new(), Compression::best());
+    enc.write_all(data).unwrap();
+    enc.finish().unwrap()
+}
+
+fn brotli(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    {
+        let mut enc = brotli::CompressorWriter::new(&mut out, 4096, 9, 22);
+        enc.write_all(data).unwrap();
+    }
+    out
+}
+
+fn is_too_large(r: &Result<Vec<u8>, FetchOutcome>) -> bool {
+    matches!(r, Err(FetchOutcome::PermanentFailure { hint: Some(h) }) if h == HINT_TOO_LARGE)
+}
+
+#[test]
+fn identity_body_within_cap_is_returned() {
+    let got = read_body(XML, None, &limits(1024, 1024)).expect("read");
+    assert_eq!(got, XML);
+    let got = read_body(XML, Some("identity"), &limits(XML.len() as u64, 1024)).expect("exact cap");
+    assert_eq!(got, XML);
+}
+
+#[test]
+fn raw_body_over_cap_is_too_large() {
+    let big = vec![b'x'; 4096];
+    assert!(is_too_large(&read_body(&big[..], None, &limits(4095, 1 << 20))));
+}
+
+#[test]
+fn gzip_and_brotli_decode() {
+    let gz = gzip(XML);
+    assert_eq!(read_body(&gz[..], Some("gzip"), &limits(1024, 1024)).unwrap(), XML);
+    let br = brotli(XML);
+    assert_eq!(read_body(&br[..], Some("br"), &limits(1024, 1024)).unwrap(), XML);
+}
+
+#[test]
+fn deflate_accepts_zlib_and_raw_streams() {
+    let mut z = ZlibEncoder::new(Vec::new(), Compression::default());
+    z.write_all(XML).unwrap();
+    let z = z.finish().unwrap();
+    assert_eq!(read_body(&z[..], Some("deflate"), &limits(1024, 1024)).unwrap(), XML);
+
+    let mut d = DeflateEncoder::new(Vec::new(), Compression::default());
+    d.write_all(XML).unwrap();
+    let d = d.finish().unwrap();
+    assert_eq!(read_body(&d[..], Some("Deflate"), &limits(1024, 1024)).unwrap(), XML);
+}
+
+#[test]
+fn decompression_bombs_stop_at_decoded_cap() {
+    let zeros = vec![0u8; 8 * 1024 * 1024];
+    let gz = gzip(&zeros);
+    let br = brotli(&zeros);
+    assert!(gz.len() < 64 * 1024 && br.len() < 64 * 1024, "bombs compress well");
+
+    let cap = limits(64 * 1024, 1024 * 1024);
+    assert!(is_too_large(&read_body(&gz[..], Some("gzip"), &cap)));
+    assert!(is_too_large(&read_body(&br[..], Some("br"), &cap)));
+}
+
+#[test]
+fn stacked_encodings_are_undone_last_first() {
+    let both = brotli(&gzip(XML));
+    let got = read_body(&both[..], Some("gzip, br"), &limits(1024, 1024)).unwrap();
+    assert_eq!(got, XML);
+}
+
+#[test]
+fn unknown_encoding_and_corrupt_bodies_fail_permanently() {
+    let r = read_body(XML, Some("compress"), &limits(1024, 1024));
+    assert!(matches!(r, Err(FetchOutcome::PermanentFailure { hint: Some(h) }) if h == HINT_UNSUPPORTED_ENCODING));
+
+    let r = read_body(&b"not gzip at all"[..], Some("gzip"), &limits(1024, 1024));
+    assert!(matches!(r, Err(FetchOutcome::PermanentFailure { .. })));
+}
+
+#[test]
+fn per_feed_override_and_declared_length() {
+    let global = SizeLimits::default();
+    let mut feed = Feed {
+        id: FeedId::from_url("https://pod.test/feed"),
+        url: "https://pod.test/feed".into(),
+        title: None,
+        site_url: None,
+        etag: None,
+        last_modified: None,
+        active: true,
+        deactivated: None,
+        settings: FeedSettings::default(),
+    };
+    assert_eq!(global.for_feed(&feed), global);
+
+    feed.settings.max_bytes = Some(200 * 1024 * 1024);
+    let per_feed = global.for_feed(&feed);
+    assert_eq!(per_feed.max_bytes, 200 * 1024 * 1024);
+    assert!(per_feed.max_decoded_bytes >= per_feed.max_bytes);
+
+    assert!(global.check_declared(None).is_ok());
+    assert!(global.check_declared(Some(global.max_bytes)).is_ok());
+    assert_eq!(
+        global.check_declared(Some(global.max_bytes + 1)),
+        Err(FetchOutcome::PermanentFailure { hint: Some(HINT_TOO_LARGE.to_string()) })
+    );
+}
+
+#[test]
+fn apply_decodes_responses_and_rejects_oversize_ones() {
+    let resp = |headers: &[(&str, &str)], body: Vec<u8>| FetchResponse {
+        status: 200,
+        headers: headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        body,
+        elapsed_ms: 1,
+    };
+    let gz = gzip(XML);
+    let len = gz.len().to_string();
+    let got = limits(1024, 1024)
+        .apply(resp(&[("Content-Encoding", "gzip"), ("Content-Length", &len), ("ETag", "\"e\"")], gz))
+        .unwrap();
+    assert_eq!(got.body, XML);
+    assert_eq!(got.headers, vec![("ETag".to_string(), "\"e\"".to_string())]);
+
+    let too_large = Err(FetchOutcome::PermanentFailure { hint: Some(HINT_TOO_LARGE.to_string()) });
+    assert_eq!(limits(10, 1024).apply(resp(&[("Content-Length", "11")], Vec::new())), too_large);
+    assert_eq!(limits(10, 1024).apply(resp(&[], vec![b'x'; 11])), too_large);
+    let bomb = gzip(&vec![0u8; 1 << 20]);
+    assert_eq!(limits(1 << 20, 4096).apply(resp(&[("Content-Encoding", "gzip")], bomb)), too_large);
+}
@@ -0,0 +1,127 @@
+/*
+Module: rssify_fetch::tests::cassette
+Purpose: Validate byte-exact record/replay of responses and failures through a cassette dir
+*/
+
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use rssify_fetch::{cassette_key, RecordFetcher, ReplayFetcher, HINT_NOT_RECORDED};
+use std::path::PathBuf;
+
+fn temp_dir() -> PathBuf {
+    let mut p = std::env::temp_dir();
+    let pid = std::process::id();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    p.push(format!("rssify-cassette-{}-{}", pid, nanos));
+    p
+}
+
+/// Upstream stand-in: fixed response for /feed, transient failure for anything else.
+struct Upstream;
+
+const BODY: &[u8] = b"<rss>\xff\xfe not utf-8 \r\n</rss>";
+
+impl Fetcher for Upstream {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        if req.url.ends_with("/feed") {
+            Ok(FetchResponse {
+                status: 200,
+                headers: vec![
+                    ("Content-Type".to_string(), "application/rss+xml".to_string()),
+                    ("ETag".to_string(), "\"v1\"".to_string()),
+                ],
+                body: BODY.to_vec(),
+                elapsed_ms: 42,
+            })
+        } else {
+            Err(FetchOutcome::TransientFailure { hint: Some("connect timeout".to_string()) })
+        }
+    }
+}
+
+fn get(url: &str) -> FetchRequest {
+    FetchRequest { url: url.to_string(), ..FetchRequest::default() }
+}
+
+#[test]
+fn replays_recorded_response_byte_for_byte() {
+    let dir = temp_dir();
+    let recorder = RecordFetcher::new(Upstream, &dir).unwrap();
+    let live = recorder.fetch(&get("https://a.example/feed")).unwrap();
+    let live_err = recorder.fetch(&get("https://a.example/down")).unwrap_err();
+
+    let key = cassette_key("https://a.example/feed");
+    assert_eq!(std::fs::read(dir.join(format!("{key}.body"))).unwrap(), BODY);
+
+    let replay = ReplayFetcher::open(&dir).unwrap();
+    assert_eq!(replay.fetch(&get("https://a.example/feed")).unwrap(), live);
+    assert_eq!(replay.fetch(&get("https://a.example/down")).unwrap_err(), live_err);
+}
+
+#[test]
+fn replay_misses_are_permanent_and_missing_dir_errors() {
+    let dir = temp_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+    let replay = ReplayFetcher::open(&dir).unwrap();
+    assert_eq!(
+        replay.fetch(&get("https://unknown.example/rss")).unwrap_err(),
+        FetchOutcome::PermanentFailure { hint: Some(HINT_NOT_RECORDED.to_string()) }
+    );
+    assert!(ReplayFetcher::open(dir.join("nope")).is_err());
+
+    let robots = replay.fetch(&get("https://unknown.example/robots.txt")).unwrap();
+    assert_eq!((robots.status, robots.body.len()), (404, 0), "unrecorded robots.txt means no rules");
+}
+
+#[test]
+fn keys_are_stable() {
+    assert_eq!(cassette_key(""), "cbf29ce484222325");
+    assert_ne!(cassette_key("https://a/1"), cassette_key("https://a/2"));
+}
+
+/// Upstream that sets a session cookie.
+struct Login;
+
+impl Fetcher for Login {
+    fn fetch(&self, _req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        Ok(FetchResponse {
+            status: 200,
+            headers: vec![
+                ("Set-Cookie".to_string(), "sid=s3cr3t; HttpOnly".to_string()),
+                ("ETag".to_string(), "\"v1\"".to_string()),
+            ],
+            body: BODY.to_vec(),
+            elapsed_ms: 1,
+        })
+    }
+}
+
+#[test]
+fn credential_headers_are_redacted_in_the_cassette() {
+    let dir = temp_dir();
+    let live = RecordFetcher::new(Login, &dir).unwrap().fetch(&get("https://a.example/feed")).unwrap();
+    assert!(live.header("set-cookie").unwrap().contains("s3cr3t"), "the caller still sees the cookie");
+
+    let json = std::fs::read_to_string(dir.join(format!("{}.json", cassette_key("https://a.example/feed")))).unwrap();
+    assert!(!json.contains("s3cr3t"), "{json}");
+    let replayed = ReplayFetcher::open(&dir).unwrap().fetch(&get("https://a.example/feed")).unwrap();
+    assert_eq!(replayed.header("set-cookie"), Some("[redacted]"));
+    assert_eq!(replayed.header("etag"), Some("\"v1\""));
+}
+
+#[test]
+fn replay_refuses_an_entry_recorded_for_another_url() {
+    let dir = temp_dir();
+    RecordFetcher::new(Upstream, &dir).unwrap().fetch(&get("https://a.example/feed")).unwrap();
+    let (from, to) = (cassette_key("https://a.example/feed"), cassette_key("https://b.example/feed"));
+    for ext in ["json", "body"] {
+        std::fs::copy(dir.join(format!("{from}.{ext}")), dir.join(format!("{to}.{ext}"))).unwrap();
+    }
+    let err = ReplayFetcher::open(&dir).unwrap().fetch(&get("https://b.example/feed")).unwrap_err();
+    match err {
+        FetchOutcome::PermanentFailure { hint: Some(h) } => assert!(h.contains("recorded for https://a.example/feed"), "{h}"),
+        other => panic!("expected permanent failure, got {other:?}"),
+    }
+}
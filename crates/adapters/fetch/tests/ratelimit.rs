@@ -0,0 +1,62 @@
+/*
+Module: rssify_fetch::tests::ratelimit
+Purpose: Validate registrable-domain keying and token-bucket waits per domain
+*/
+
+use rssify_fetch::{registrable_domain, Clock, Rate, RateLimiter, RateLimits};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+
+/// Clock whose sleeps advance time instantly and are recorded.
+#[derive(Default)]
+struct FakeClock {
+    now: Cell<u64>,
+    slept: RefCell<Vec<u64>>,
+}
+
+impl Clock for FakeClock {
+    fn now_ms(&self) -> u64 {
+        self.now.get()
+    }
+    fn sleep_ms(&self, ms: u64) {
+        self.slept.borrow_mut().push(ms);
+        self.now.set(self.now.get() + ms);
+    }
+}
+
+#[test]
+fn registrable_domain_groups_subdomains() {
+    assert_eq!(registrable_domain("alice.substack.com"), "substack.com");
+    assert_eq!(registrable_domain("Feeds.News.BBC.co.uk."), "bbc.co.uk");
+    assert_eq!(registrable_domain("someone.github.io"), "someone.github.io");
+    assert_eq!(registrable_domain("localhost"), "localhost");
+    assert_eq!(registrable_domain("192.0.2.7"), "192.0.2.7");
+}
+
+#[test]
+fn bucket_spaces_requests_after_burst_and_shares_across_subdomains() {
+    let limits = RateLimits {
+        default: None,
+        domains: BTreeMap::from([("substack.com".to_string(), Rate { per_minute: 60, burst: 2 })]),
+    };
+    let clock = FakeClock::default();
+    let limiter = RateLimiter::new(limits).with_clock(&clock);
+
+    assert_eq!(limiter.acquire("https://a.substack.com/feed"), 0);
+    assert_eq!(limiter.acquire("https://b.substack.com/feed"), 0);
+    assert_eq!(limiter.acquire("https://c.substack.com/feed"), 1_000);
+    assert_eq!(limiter.acquire("https://unlimited.example/rss"), 0);
+
+    clock.now.set(clock.now.get() + 5_000);
+    assert_eq!(limiter.acquire("https://a.substack.com/feed"), 0, "bucket refilled");
+    assert_eq!(*clock.slept.borrow(), [0, 0, 1_000, 0]);
+}
+
+#[test]
+fn default_rate_applies_to_unlisted_domains() {
+    let limits: RateLimits = serde_json::from_str(r#"{"default": {"per_minute": 30}}"#).unwrap();
+    let clock = FakeClock::default();
+    let limiter = RateLimiter::new(limits).with_clock(&clock);
+    assert_eq!(limiter.acquire("https://x.example/a"), 0);
+    assert_eq!(limiter.acquire("https://y.x.example/b"), 2_000);
+}
@@ -0,0 +1,43 @@
+/*
+Module: rssify_fetch::tests::readability
+Purpose: Validate main-content extraction picks the article body and drops page chrome
+*/
+
+use rssify_fetch::extract_article;
+
+const PAGE: &str = r#"<!doctype html>
+<html><head><title>Why Rust &amp; feeds</title><style>p { color: red }</style></head>
+<body>
+<nav><a href="/">Home</a> <a href="/about">About us and everything else we do</a></nav>
+<div class="sidebar"><ul><li><a href="/a">A very long related link title, number one</a></li>
+<li><a href="/b">A very long related link title, number two</a></li></ul></div>
+<div class="content"><article>
+  <h2>Background</h2>
+  <p>Feeds are small, but there are many of them, and each one changes on its own schedule.</p>
+  <p>Fetching them politely means caching validators, honoring robots.txt, and spacing requests.<br>
+     It also means <a href="/x">retrying</a> with care &#8212; never hammering a host.</p>
+  <script>trackEverything()</script>
+  <p>Short.</p>
+</article></div>
+<footer><p>Copyright 2024, all rights reserved, no matter what anyone says.</p></footer>
+</body></html>"#;
+
+#[test]
+fn keeps_article_paragraphs_and_headings_only() {
+    let a = extract_article(PAGE).expect("article");
+    assert_eq!(a.title.as_deref(), Some("Why Rust & feeds"));
+    assert!(a.text.starts_with("Background\n\nFeeds are small"), "{}", a.text);
+    assert!(a.text.contains("retrying with care \u{2014} never hammering"));
+    assert!(!a.text.contains("related link"));
+    assert!(!a.text.contains("Copyright"));
+    assert!(!a.text.contains("trackEverything"));
+    assert!(!a.text.contains("Short."));
+    assert!(a.html.starts_with("<article><h2>Background</h2><p>Feeds are small"));
+}
+
+#[test]
+fn pages_without_prose_yield_nothing_and_output_is_escaped() {
+    assert_eq!(extract_article("<html><body><a href='/'>home</a></body></html>"), None);
+    let a = extract_article("<div><p>Compare a &lt;b&gt; tag with 1 &lt; 2, which is longer text.</p></div>").unwrap();
+    assert_eq!(a.html, "<article><p>Compare a &lt;b&gt; tag with 1 &lt; 2, which is longer text.</p></article>");
+}
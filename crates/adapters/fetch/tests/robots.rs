@@ -0,0 +1,221 @@
+/*
+Module: rssify_fetch::tests::robots
+Purpose: Validate robots.txt group selection (per sent User-Agent), path matching, caching and Crawl-delay pacing
+*/
+
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use rssify_fetch::{
+    Clock, RobotsFetcher, RobotsRules, SsrfPolicy, HINT_ROBOTS_DISALLOWED, HINT_ROBOTS_UNAVAILABLE,
+};
+use std::cell::{Cell, RefCell};
+
+const UA: &str = "rssify/0.1 (+https://example.org/bot)";
+
+const ROBOTS: &str = "\
+# comment
+User-agent: *
+Disallow: /private/
+Crawl-delay: 1
+
+User-agent: rssify
+User-agent: otherbot
+Disallow: /articles/
+Allow: /articles/public
+Disallow: /*.pdf$
+Crawl-delay: 2.5
+";
+
+#[test]
+fn selects_named_group_over_wildcard() {
+    let rules = RobotsRules::parse(ROBOTS, UA);
+    assert!(rules.is_allowed("/private/x"), "named group replaces the * group");
+    assert!(!rules.is_allowed("/articles/2024/post"));
+    assert!(rules.is_allowed("/articles/public/post"), "longer Allow wins");
+    assert!(!rules.is_allowed("/files/report.pdf"));
+    assert!(rules.is_allowed("/files/report.pdf?download=1"), "$ anchors the end");
+    assert!(rules.is_allowed("/robots.txt"));
+    assert_eq!(rules.crawl_delay_ms(), Some(2500));
+
+    let other = RobotsRules::parse(ROBOTS, "somebot/1.0");
+    assert!(!other.is_allowed("/private/x"));
+    assert!(other.is_allowed("/articles/2024/post"));
+    assert_eq!(other.crawl_delay_ms(), Some(1000));
+}
+
+#[test]
+fn allow_wins_ties_and_empty_disallow_allows_all() {
+    let rules = RobotsRules::parse("User-agent: *\nDisallow: /a\nAllow: /a\n", UA);
+    assert!(rules.is_allowed("/a/b"));
+    let open = RobotsRules::parse("User-agent: *\nDisallow:\n", UA);
+    assert!(open.is_allowed("/anything"));
+    assert_eq!(open.crawl_delay_ms(), None);
+}
+
+/// Clock whose sleeps advance time instantly and are recorded.
+#[derive(Default)]
+struct FakeClock {
+    now: Cell<u64>,
+    slept: RefCell<Vec<u64>>,
+}
+
+impl Clock for FakeClock {
+    fn now_ms(&self) -> u64 {
+        self.now.get()
+    }
+    fn sleep_ms(&self, ms: u64) {
+        self.slept.borrow_mut().push(ms);
+        self.now.set(self.now.get() + ms);
+    }
+}
+
+/// Serves robots.txt with a fixed status/body and 200 for everything else.
+struct Site {
+    robots_status: u16,
+    robots_body: &'static str,
+    cache_control: Option<&'static str>,
+    seen: RefCell<Vec<String>>,
+}
+
+impl Site {
+    fn new(robots_status: u16, robots_body: &'static str) -> Self {
+        Self { robots_status, robots_body, cache_control: None, seen: RefCell::new(Vec::new()) }
+    }
+    fn robots_hits(&self) -> usize {
+        self.seen.borrow().iter().filter(|u| u.ends_with("/robots.txt")).count()
+    }
+}
+
+impl Fetcher for Site {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        self.seen.borrow_mut().push(req.url.clone());
+        let robots = req.url.ends_with("/robots.txt");
+        let mut headers = Vec::new();
+        if robots && let Some(cc) = self.cache_control {
+            headers.push(("Cache-Control".to_string(), cc.to_string()));
+        }
+        Ok(FetchResponse {
+            status: if robots { self.robots_status } else { 200 },
+            headers,
+            body: if robots { self.robots_body.as_bytes().to_vec() } else { b"<rss/>".to_vec() },
+            elapsed_ms: 1,
+        })
+    }
+}
+
+fn get(url: &str) -> FetchRequest {
+    FetchRequest { url: url.to_string(), ..FetchRequest::default() }
+}
+
+fn permanent_hint(err: FetchOutcome) -> String {
+    match err {
+        FetchOutcome::PermanentFailure { hint } => hint.unwrap_or_default(),
+        other => panic!("expected permanent failure, got {other:?}"),
+    }
+}
+
+#[test]
+fn blocks_disallowed_paths_and_caches_per_origin() {
+    let site = Site::new(200, ROBOTS);
+    let clock = FakeClock::default();
+    let fetcher = RobotsFetcher::new(&site, UA).with_clock(&clock);
+
+    let err = fetcher.fetch(&get("https://news.example/articles/1")).unwrap_err();
+    assert_eq!(permanent_hint(err), HINT_ROBOTS_DISALLOWED);
+    assert!(fetcher.fetch(&get("https://news.example/feed.xml")).is_ok());
+    assert_eq!(site.robots_hits(), 1, "second request served from cache");
+
+    assert!(fetcher.fetch(&get("https://other.example/feed.xml")).is_ok());
+    assert_eq!(site.robots_hits(), 2, "each origin has its own entry");
+}
+
+#[test]
+fn groups_follow_the_user_agent_each_request_sends() {
+    let site = Site::new(200, ROBOTS);
+    let clock = FakeClock::default();
+    let fetcher = RobotsFetcher::new(&site, UA).with_clock(&clock);
+    let as_agent = |url: &str, ua: &str| FetchRequest { headers: vec![("user-agent".into(), ua.into())], ..get(url) };
+
+    assert!(fetcher.fetch(&get("https://news.example/private/x")).is_ok(), "configured agent: rssify group");
+    let err = fetcher.fetch(&as_agent("https://news.example/private/x", "Mozilla/5.0")).unwrap_err();
+    assert_eq!(permanent_hint(err), HINT_ROBOTS_DISALLOWED, "a browser agent gets the * group");
+    assert!(fetcher.fetch(&as_agent("https://news.example/articles/1", "Mozilla/5.0")).is_ok());
+    let err = fetcher.fetch(&as_agent("https://news.example/articles/1", "OtherBot/2.0")).unwrap_err();
+    assert_eq!(permanent_hint(err), HINT_ROBOTS_DISALLOWED);
+    assert_eq!(site.robots_hits(), 1, "one robots.txt per origin, whatever the agent");
+}
+
+#[test]
+fn refetches_after_ttl_honoring_max_age() {
+    let mut site = Site::new(200, "User-agent: *\nDisallow:\n");
+    site.cache_control = Some("public, max-age=60");
+    let clock = FakeClock::default();
+    let fetcher = RobotsFetcher::new(&site, UA).with_clock(&clock).with_ttl_ms(1);
+
+    fetcher.fetch(&get("https://a.example/feed")).unwrap();
+    clock.now.set(59_000);
+    fetcher.fetch(&get("https://a.example/feed")).unwrap();
+    assert_eq!(site.robots_hits(), 1);
+    clock.now.set(60_001);
+    fetcher.fetch(&get("https://a.example/feed")).unwrap();
+    assert_eq!(site.robots_hits(), 2);
+}
+
+#[test]
+fn crawl_delay_spaces_requests_to_same_host() {
+    let site = Site::new(200, ROBOTS);
+    let clock = FakeClock::default();
+    let fetcher = RobotsFetcher::new(&site, UA).with_clock(&clock);
+
+    fetcher.fetch(&get("https://news.example/feed.xml")).unwrap();
+    clock.now.set(1000);
+    fetcher.fetch(&get("https://news.example/feed.xml")).unwrap();
+    fetcher.fetch(&get("https://news.example/feed.xml")).unwrap();
+    assert_eq!(*clock.slept.borrow(), vec![0, 1500, 2500]);
+}
+
+#[test]
+fn missing_robots_allows_and_server_error_is_transient() {
+    let missing = Site::new(404, "");
+    let clock = FakeClock::default();
+    let fetcher = RobotsFetcher::new(&missing, UA).with_clock(&clock);
+    assert!(fetcher.fetch(&get("https://a.example/private/feed")).is_ok());
+
+    let broken = Site::new(503, "");
+    let fetcher = RobotsFetcher::new(&broken, UA).with_clock(&clock);
+    let err = fetcher.fetch(&get("https://a.example/feed")).unwrap_err();
+    assert_eq!(
+        err,
+        FetchOutcome::TransientFailure { hint: Some(HINT_ROBOTS_UNAVAILABLE.to_string()) }
+    );
+    let _ = fetcher.fetch(&get("https://a.example/feed"));
+    assert_eq!(broken.robots_hits(), 2, "failures are not cached");
+}
+
+/// Answers robots.txt with a redirect to the cloud metadata address.
+#[derive(Default)]
+struct MetadataRedirect {
+    seen: RefCell<Vec<String>>,
+}
+
+impl Fetcher for MetadataRedirect {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        self.seen.borrow_mut().push(req.url.clone());
+        let (status, headers) = if req.url.ends_with("/robots.txt") {
+            (301, vec![("Location".to_string(), "http://169.254.169.254/robots.txt".to_string())])
+        } else {
+            (200, Vec::new())
+        };
+        Ok(FetchResponse { status, headers, body: Vec::new(), elapsed_ms: 1 })
+    }
+}
+
+#[test]
+fn guarded_robots_redirects_never_reach_blocked_addresses() {
+    let site = MetadataRedirect::default();
+    let clock = FakeClock::default();
+    let fetcher = RobotsFetcher::new(&site, UA).with_clock(&clock).with_guard(SsrfPolicy::new());
+
+    let err = fetcher.fetch(&get("http://93.184.216.34/feed")).unwrap_err();
+    assert_eq!(err, FetchOutcome::TransientFailure { hint: Some(HINT_ROBOTS_UNAVAILABLE.to_string()) });
+    assert_eq!(*site.seen.borrow(), ["http://93.184.216.34/robots.txt"], "the metadata address is never requested");
+}
@@ -0,0 +1,71 @@
+/*
+Module: rssify_fetch::tests::secrets
+Purpose: Validate secret lookup from env/secrets file and credential stripping on cross-origin redirects
+*/
+
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher, SecretRef};
+use rssify_fetch::{RedirectFetcher, Secrets};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+#[test]
+fn looks_up_env_and_file_secrets() {
+    let secrets = Secrets::from_map(BTreeMap::from([("pw".to_string(), "hunter2".to_string())]));
+    let env = |name: &str| (name == "TOKEN").then(|| "t-1".to_string());
+    assert_eq!(secrets.lookup_with(&SecretRef::Secret("pw".into()), env).as_deref(), Some("hunter2"));
+    assert_eq!(secrets.lookup_with(&SecretRef::Env("TOKEN".into()), env).as_deref(), Some("t-1"));
+    assert_eq!(secrets.lookup_with(&SecretRef::Env("OTHER".into()), env), None);
+    assert_eq!(secrets.lookup_with(&SecretRef::Secret("nope".into()), env), None);
+}
+
+#[test]
+fn load_rejects_non_string_values() {
+    let mut p = std::env::temp_dir();
+    p.push(format!("rssify-secrets-{}.json", std::process::id()));
+    std::fs::write(&p, r#"{"a": "1"}"#).unwrap();
+    assert!(Secrets::load(&p).is_ok());
+    std::fs::write(&p, r#"{"a": 1}"#).unwrap();
+    assert!(Secrets::load(&p).is_err());
+}
+
+/// Redirects once to `target`, then records the headers it was called with.
+struct Hop {
+    target: &'static str,
+    seen: RefCell<Vec<Vec<(String, String)>>>,
+}
+
+impl Fetcher for Hop {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        self.seen.borrow_mut().push(req.headers.clone());
+        let status = if self.seen.borrow().len() == 1 { 302 } else { 200 };
+        Ok(FetchResponse {
+            status,
+            headers: vec![("Location".to_string(), self.target.to_string())],
+            body: Vec::new(),
+            elapsed_ms: 1,
+        })
+    }
+}
+
+fn authed(url: &str) -> FetchRequest {
+    FetchRequest {
+        url: url.to_string(),
+        headers: vec![
+            ("Authorization".to_string(), "Bearer t-1".to_string()),
+            ("Cookie".to_string(), "sid=1".to_string()),
+            ("Accept".to_string(), "*/*".to_string()),
+        ],
+        ..FetchRequest::default()
+    }
+}
+
+#[test]
+fn credentials_survive_same_origin_and_drop_cross_origin() {
+    let same = Hop { target: "/moved", seen: RefCell::new(Vec::new()) };
+    RedirectFetcher::new(&same).fetch(&authed("https://a.example/feed")).unwrap();
+    assert_eq!(same.seen.borrow()[1].len(), 3);
+
+    let cross = Hop { target: "https://cdn.example/feed", seen: RefCell::new(Vec::new()) };
+    RedirectFetcher::new(&cross).fetch(&authed("https://a.example/feed")).unwrap();
+    assert_eq!(cross.seen.borrow()[1], vec![("Accept".to_string(), "*/*".to_string())]);
+}
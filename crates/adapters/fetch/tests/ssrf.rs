@@ -0,0 +1,177 @@
+/*
+Module: rssify_fetch::tests::ssrf
+Purpose: Validate address classification, allowlisting and per-hop redirect vetting
+*/
+
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use rssify_fetch::{
+    is_blocked_ip, RedirectFetcher, SsrfPolicy, HINT_BLOCKED_ADDRESS, HINT_BLOCKED_SCHEME,
+    HINT_TOO_MANY_REDIRECTS,
+};
+use std::cell::RefCell;
+use std::net::{IpAddr, SocketAddr};
+
+fn hint(err: FetchOutcome) -> String {
+    match err {
+        FetchOutcome::PermanentFailure { hint } => hint.unwrap_or_default(),
+        other => panic!("expected permanent failure, got {other:?}"),
+    }
+}
+
+fn resolves_to(ip: &str) -> impl FnOnce(&str, u16) -> std::io::Result<Vec<SocketAddr>> {
+    let ip: IpAddr = ip.parse().unwrap();
+    move |_, port| Ok(vec![SocketAddr::new(ip, port)])
+}
+
+#[test]
+fn classifies_private_and_public_addresses() {
+    for ip in [
+        "127.0.0.1", "10.1.2.3", "172.16.0.1", "172.31.255.255", "192.168.1.1",
+        "169.254.169.254", "100.64.0.1", "100.100.100.200", "0.0.0.0", "192.0.0.192",
+        "224.0.0.1", "255.255.255.255", "::1", "::", "fe80::1", "fd00:ec2::254",
+        "ff02::1", "::ffff:127.0.0.1", "::127.0.0.1", "::a9fe:a9fe", "64:ff9b::a9fe:a9fe",
+        "2002:0a00:0001::1",
+    ] {
+        assert!(is_blocked_ip(ip.parse().unwrap()), "{ip} should be blocked");
+    }
+    for ip in ["93.184.216.34", "172.32.0.1", "100.128.0.1", "2606:4700::1111", "::ffff:8.8.8.8", "::8.8.8.8"] {
+        assert!(!is_blocked_ip(ip.parse().unwrap()), "{ip} should be allowed");
+    }
+}
+
+#[test]
+fn blocks_literal_and_obfuscated_hosts() {
+    let policy = SsrfPolicy::new();
+    for url in [
+        "http://127.0.0.1/feed",
+        "http://0x7f.1/feed",
+        "http://2130706433/feed",
+        "http://[::1]:8080/feed",
+        "http://169.254.169.254/latest/meta-data/",
+    ] {
+        let err = policy.resolve_checked(url).unwrap_err();
+        assert_eq!(hint(err), HINT_BLOCKED_ADDRESS, "{url}");
+    }
+    let err = policy.resolve_checked("file:///etc/passwd").unwrap_err();
+    assert_eq!(hint(err), HINT_BLOCKED_SCHEME);
+}
+
+#[test]
+fn blocks_names_resolving_to_private_ranges() {
+    let policy = SsrfPolicy::new();
+    let err = policy
+        .resolve_with("https://feeds.internal.example/rss", resolves_to("10.0.0.5"))
+        .unwrap_err();
+    assert_eq!(hint(err), HINT_BLOCKED_ADDRESS);
+
+    let ok = policy
+        .resolve_with("https://example.com/rss", resolves_to("93.184.216.34"))
+        .unwrap();
+    assert_eq!(ok, vec!["93.184.216.34:443".parse::<SocketAddr>().unwrap()]);
+
+    let dns = policy
+        .resolve_with("https://nx.example/rss", |_, _| Err(std::io::Error::other("nxdomain")))
+        .unwrap_err();
+    assert!(matches!(dns, FetchOutcome::TransientFailure { .. }));
+}
+
+#[test]
+fn allowlist_exempts_host_or_host_port() {
+    let policy = SsrfPolicy::new().allow("127.0.0.1:8080").allow("intranet.local");
+    assert!(policy.resolve_checked("http://127.0.0.1:8080/feed").is_ok());
+    assert!(policy.resolve_checked("http://127.0.0.1:9090/feed").is_err());
+    assert!(
+        policy
+            .resolve_with("http://intranet.local/rss", resolves_to("192.168.0.10"))
+            .is_ok()
+    );
+}
+
+/// Serves scripted responses in order and records requested URLs.
+struct Scripted {
+    responses: RefCell<Vec<FetchResponse>>,
+    seen: RefCell<Vec<String>>,
+    pins: RefCell<Vec<Vec<SocketAddr>>>,
+}
+
+impl Scripted {
+    fn new(mut responses: Vec<FetchResponse>) -> Self {
+        responses.reverse();
+        Self { responses: RefCell::new(responses), seen: RefCell::new(Vec::new()), pins: RefCell::new(Vec::new()) }
+    }
+}
+
+impl Fetcher for Scripted {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        self.seen.borrow_mut().push(req.url.clone());
+        self.pins.borrow_mut().push(req.pinned_addrs.clone());
+        Ok(self.responses.borrow_mut().pop().expect("unexpected request"))
+    }
+}
+
+fn redirect(location: &str) -> FetchResponse {
+    FetchResponse {
+        status: 302,
+        headers: vec![("Location".to_string(), location.to_string())],
+        body: Vec::new(),
+        elapsed_ms: 1,
+    }
+}
+
+fn ok() -> FetchResponse {
+    FetchResponse { status: 200, headers: Vec::new(), body: b"<rss/>".to_vec(), elapsed_ms: 1 }
+}
+
+fn req(url: &str) -> FetchRequest {
+    FetchRequest { url: url.to_string(), ..FetchRequest::default() }
+}
+
+#[test]
+fn follows_relative_redirects() {
+    let inner = Scripted::new(vec![redirect("/moved"), redirect("https://93.184.216.34/final"), ok()]);
+    let fetcher = RedirectFetcher::new(&inner).with_guard(SsrfPolicy::new());
+    let resp = fetcher.fetch(&req("http://93.184.216.34/feed")).unwrap();
+    assert_eq!(resp.status, 200);
+    assert_eq!(
+        *inner.seen.borrow(),
+        ["http://93.184.216.34/feed", "http://93.184.216.34/moved", "https://93.184.216.34/final"]
+    );
+}
+
+#[test]
+fn redirect_into_metadata_range_is_blocked_before_request() {
+    let inner = Scripted::new(vec![redirect("http://169.254.169.254/latest/meta-data/")]);
+    let fetcher = RedirectFetcher::new(&inner).with_guard(SsrfPolicy::new());
+    let err = fetcher.fetch(&req("http://93.184.216.34/feed")).unwrap_err();
+    assert_eq!(hint(err), HINT_BLOCKED_ADDRESS);
+    assert_eq!(inner.seen.borrow().len(), 1);
+}
+
+#[test]
+fn unguarded_fetcher_follows_anywhere_and_caps_hops() {
+    let inner = Scripted::new(vec![redirect("http://127.0.0.1/a"), ok()]);
+    assert!(RedirectFetcher::new(&inner).fetch(&req("http://93.184.216.34/")).is_ok());
+
+    let looping = Scripted::new((0..3).map(|_| redirect("/again")).collect());
+    let err = RedirectFetcher::new(&looping)
+        .max_redirects(2)
+        .fetch(&req("http://93.184.216.34/"))
+        .unwrap_err();
+    assert_eq!(hint(err), HINT_TOO_MANY_REDIRECTS);
+}
+
+#[test]
+fn guard_pins_vetted_addresses_per_hop() {
+    let inner = Scripted::new(vec![redirect("https://[2606:4700::1111]/final"), ok()]);
+    let fetcher = RedirectFetcher::new(&inner).with_guard(SsrfPolicy::new());
+    fetcher.fetch(&req("http://93.184.216.34/feed")).unwrap();
+    let pins: Vec<Vec<String>> =
+        inner.pins.borrow().iter().map(|p| p.iter().map(ToString::to_string).collect()).collect();
+    assert_eq!(pins, [vec!["93.184.216.34:80"], vec!["[2606:4700::1111]:443"]]);
+
+    let inner = Scripted::new(vec![redirect("http://93.184.216.35/elsewhere"), ok()]);
+    let mut pinned = req("http://93.184.216.34/feed");
+    pinned.pinned_addrs = vec!["93.184.216.34:80".parse().unwrap()];
+    RedirectFetcher::new(&inner).fetch(&pinned).unwrap();
+    assert_eq!(*inner.pins.borrow(), [pinned.pinned_addrs.clone(), Vec::new()], "pins do not follow a redirect");
+}
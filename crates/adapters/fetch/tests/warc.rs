@@ -0,0 +1,99 @@
+/*
+Module: rssify_fetch::tests::warc
+Purpose: Validate WARC records written by WarcFetcher: pairing, ids, redaction and rotation
+*/
+
+use flate2::read::MultiGzDecoder;
+use rssify_core::{FetchOutcome, FetchRequest, FetchResponse, Fetcher};
+use rssify_fetch::{Warc, WarcFetcher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+fn temp_dir() -> PathBuf {
+    let mut p = std::env::temp_dir();
+    let pid = std::process::id();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    p.push(format!("rssify-warc-{}-{}", pid, nanos));
+    p
+}
+
+/// Gzip-encoded-looking response for /feed, transport failure for anything else.
+struct Upstream;
+
+impl Fetcher for Upstream {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        if !req.url.contains("/feed") {
+            return Err(FetchOutcome::TransientFailure { hint: Some("connect timeout".to_string()) });
+        }
+        Ok(FetchResponse {
+            status: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "application/rss+xml".to_string()),
+                ("Content-Encoding".to_string(), "gzip".to_string()),
+                ("Set-Cookie".to_string(), "sid=secret".to_string()),
+            ],
+            body: b"<rss/>".to_vec(),
+            elapsed_ms: 1,
+        })
+    }
+}
+
+fn read_warc(path: &Path) -> String {
+    let mut out = String::new();
+    MultiGzDecoder::new(std::fs::File::open(path).unwrap()).read_to_string(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn exchanges_become_linked_response_and_request_records() {
+    let dir = temp_dir();
+    let warc = Warc::open(&dir, 1 << 20).unwrap();
+    let fetcher = WarcFetcher::new(Upstream, Some(warc.clone()));
+    let req = FetchRequest {
+        url: "https://a.test/feed?x=1".to_string(),
+        etag: Some("\"v0\"".to_string()),
+        headers: vec![("Authorization".to_string(), "Bearer t0k3n".to_string())],
+        ..FetchRequest::default()
+    };
+    fetcher.fetch(&req).unwrap();
+    assert!(fetcher.fetch(&FetchRequest { url: "https://a.test/down".into(), ..FetchRequest::default() }).is_err());
+
+    let ids = warc.take_responses();
+    assert_eq!(ids.len(), 1, "failures without a response are not recorded");
+    assert!(ids[0].starts_with("<urn:uuid:") && ids[0].ends_with('>'));
+    assert!(warc.take_responses().is_empty());
+
+    let files = warc.files();
+    assert_eq!(files.len(), 1);
+    let name = files[0].file_name().unwrap().to_string_lossy().into_owned();
+    assert!(name.starts_with("rssify-") && name.ends_with(".warc.gz"), "{name}");
+    let text = read_warc(&files[0]);
+    let types: Vec<&str> = text.lines().filter_map(|l| l.strip_prefix("WARC-Type: ")).collect();
+    assert_eq!(types, ["warcinfo", "response", "request"]);
+    assert!(text.contains(&format!("WARC-Record-ID: {}\r\n", ids[0])));
+    assert!(text.contains(&format!("WARC-Concurrent-To: {}\r\n", ids[0])));
+    assert!(text.contains("HTTP/1.1 200 OK\r\n"));
+    assert!(text.contains("Content-Length: 6\r\n\r\n<rss/>"));
+    assert!(!text.contains("Content-Encoding"), "body is stored decoded");
+    assert!(text.contains("GET /feed?x=1 HTTP/1.1\r\nHost: a.test\r\nIf-None-Match: \"v0\"\r\n"));
+    assert!(!text.contains("t0k3n") && !text.contains("sid=secret"), "credentials redacted");
+}
+
+#[test]
+fn files_rotate_past_the_size_limit() {
+    let dir = temp_dir();
+    let warc = Warc::open(&dir, 1).unwrap();
+    let fetcher = WarcFetcher::new(Upstream, Some(warc.clone()));
+    for _ in 0..3 {
+        fetcher.fetch(&FetchRequest { url: "https://a.test/feed".into(), ..FetchRequest::default() }).unwrap();
+    }
+    let files = warc.files();
+    assert_eq!(files.len(), 3);
+    for f in &files {
+        assert!(read_warc(f).starts_with("WARC/1.1\r\nWARC-Type: warcinfo\r\n"));
+    }
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+}
@@ -0,0 +1,99 @@
+/*
+Module: rssify_core::archive
+Purpose: Attachment archiving records: per-feed settings, archived attachments and the archived
+         copy of an entry's content
+Public API surface: ArchiveSettings::{allows}, Attachment, AttachmentKind, SkippedAttachment,
+                    ArchivedEntry, blob_path, DEFAULT_ARCHIVE_MIME_ALLOW
+Invariants:
+ - Attachment bytes live in a content-addressed store keyed by lowercase sha256 hex.
+ - Archived content references attachments by repo-relative `blob_path`, never by remote URL.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use crate::{ContentBlob, EntryId, FeedId};
+use serde::{Deserialize, Serialize};
+
+/// MIME patterns archived when a feed does not list its own.
+pub const DEFAULT_ARCHIVE_MIME_ALLOW: &[&str] = &["image/*", "audio/*", "video/*", "application/pdf"];
+
+/// Per-feed archiving mode; present means enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveSettings {
+    /// Total attachment bytes this feed may archive; `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_bytes: Option<u64>,
+    /// MIME patterns (`type/subtype` or `type/*`); empty uses `DEFAULT_ARCHIVE_MIME_ALLOW`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mime_allow: Vec<String>,
+}
+
+impl ArchiveSettings {
+    /// Whether `mime` (parameters ignored, case-insensitive) matches the allowlist.
+    #[must_use]
+    pub fn allows(&self, mime: &str) -> bool {
+        let mime = mime.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        let matches = |pat: &str| {
+            let pat = pat.trim().to_ascii_lowercase();
+            match pat.strip_suffix("/*") {
+                Some(top) => mime.split('/').next() == Some(top) && mime.contains('/'),
+                None => pat == mime,
+            }
+        };
+        if self.mime_allow.is_empty() {
+            DEFAULT_ARCHIVE_MIME_ALLOW.iter().any(|p| matches(p))
+        } else {
+            self.mime_allow.iter().any(|p| matches(p))
+        }
+    }
+}
+
+/// Where an attachment was referenced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Enclosure,
+    Image,
+}
+
+/// One archived attachment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub url: String,
+    pub kind: AttachmentKind,
+    pub mime: String,
+    pub size: u64,
+    /// Lowercase sha256 hex of the bytes; see `blob_path`.
+    pub sha256: String,
+    /// WARC-Record-ID of the archived download (`fetch --warc`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warc_record: Option<String>,
+}
+
+/// A referenced attachment that was not archived, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedAttachment {
+    pub url: String,
+    pub reason: String,
+}
+
+/// Archived copy of one entry: attachments plus content with references rewritten to local paths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedEntry {
+    pub entry: EntryId,
+    pub feed: FeedId,
+    /// Unix seconds.
+    pub archived_ts: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<ContentBlob>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedAttachment>,
+}
+
+/// Repo-relative path of a blob: `blobs/sha256/<first two hex>/<hex>`.
+#[must_use]
+pub fn blob_path(sha256: &str) -> String {
+    format!("blobs/sha256/{}/{}", sha256.get(..2).unwrap_or("00"), sha256)
+}
@@ -0,0 +1,134 @@
+/*
+Module: rssify_core::deactivate
+Purpose: Pure policy deciding when a feed should stop being fetched
+Public API surface: Deactivation, DeactivationReason, DeactivationPolicy, FailureStreak
+Invariants: Deterministic; time is injected as unix seconds; no I/O
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use crate::{Feed, FetchOutcome};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Why a feed was taken out of rotation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeactivationReason {
+    /// The source answered 410 Gone.
+    Gone,
+    /// N permanent failures in a row.
+    PermanentFailures { count: u32 },
+    /// No successful fetch for this many days.
+    NoSuccess { days: i64 },
+}
+
+/// Stored alongside a deactivated feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deactivation {
+    pub reason: DeactivationReason,
+    /// Unix seconds when the feed was deactivated.
+    pub ts: i64,
+}
+
+/// Failures observed since the last successful fetch.
+/// Transient failures neither grow nor reset the permanent count; only a success resets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureStreak {
+    /// Consecutive permanent failures (Gone included).
+    pub permanent: u32,
+    /// Unix seconds of the first failure of any kind since the last success.
+    pub since_ts: Option<i64>,
+}
+
+impl FailureStreak {
+    /// Fold one fetch outcome observed at `now` into the streak.
+    #[must_use]
+    pub fn observe(self, outcome: &FetchOutcome, now: i64) -> Self {
+        match outcome {
+            FetchOutcome::NotModified | FetchOutcome::NewContent { .. } => Self::default(),
+            FetchOutcome::TransientFailure { .. } => Self {
+                permanent: self.permanent,
+                since_ts: self.since_ts.or(Some(now)),
+            },
+            FetchOutcome::PermanentFailure { .. } | FetchOutcome::Gone => Self {
+                permanent: self.permanent.saturating_add(1),
+                since_ts: self.since_ts.or(Some(now)),
+            },
+        }
+    }
+}
+
+/// Thresholds for automatic deactivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeactivationPolicy {
+    /// Deactivate after this many consecutive permanent failures.
+    pub max_permanent_failures: u32,
+    /// Deactivate after this many days without a success.
+    pub max_days_without_success: i64,
+}
+
+impl Default for DeactivationPolicy {
+    fn default() -> Self {
+        Self {
+            max_permanent_failures: 5,
+            max_days_without_success: 30,
+        }
+    }
+}
+
+impl DeactivationPolicy {
+    /// Decide whether `outcome` (already folded into `streak`) should deactivate the feed.
+    /// Days without success are counted from the first failure after the last success,
+    /// so a feed that simply was not polled for a while is not penalised.
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        outcome: &FetchOutcome,
+        streak: &FailureStreak,
+        now: i64,
+    ) -> Option<DeactivationReason> {
+        match outcome {
+            FetchOutcome::Gone => return Some(DeactivationReason::Gone),
+            FetchOutcome::NotModified | FetchOutcome::NewContent { .. } => return None,
+            FetchOutcome::TransientFailure { .. } | FetchOutcome::PermanentFailure { .. } => {}
+        }
+        if streak.permanent >= self.max_permanent_failures {
+            return Some(DeactivationReason::PermanentFailures {
+                count: streak.permanent,
+            });
+        }
+        let days = streak.since_ts.map_or(0, |since| (now - since) / SECS_PER_DAY);
+        if days >= self.max_days_without_success {
+            return Some(DeactivationReason::NoSuccess { days });
+        }
+        None
+    }
+}
+
+impl Feed {
+    /// Take the feed out of rotation, recording why and when.
+    pub fn deactivate(&mut self, reason: DeactivationReason, ts: i64) {
+        self.active = false;
+        self.deactivated = Some(Deactivation { reason, ts });
+    }
+
+    /// Put the feed back into rotation and forget the stored reason.
+    pub fn reactivate(&mut self) {
+        self.active = true;
+        self.deactivated = None;
+    }
+}
+
+impl fmt::Display for DeactivationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeactivationReason::Gone => write!(f, "gone"),
+            DeactivationReason::PermanentFailures { count } => {
+                write!(f, "permanent_failures:{count}")
+            }
+            DeactivationReason::NoSuccess { days } => write!(f, "no_success_days:{days}"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+/*
+Module: rssify_core::diff
+Purpose: Classify freshly parsed entries against stored ones (new / updated / unchanged)
+Public API surface: EntryDiff, diff_entries
+Invariants: Pure; identity is EntryId, "updated" means any field differs from the stored copy,
+            ignoring content added by full-article fetching.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use crate::Entry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Counts of what persisting `parsed` would do to the stored entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryDiff {
+    pub new: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// Compare `parsed` against `stored`; stored entries absent from `parsed` are not counted.
+#[must_use]
+pub fn diff_entries(stored: &[Entry], parsed: &[Entry]) -> EntryDiff {
+    let by_id: BTreeMap<&str, &Entry> = stored.iter().map(|e| (e.id.as_str(), e)).collect();
+    let mut diff = EntryDiff::default();
+    for e in parsed {
+        match by_id.get(e.id.as_str()) {
+            None => diff.new += 1,
+            Some(old) if same_source(old, e) => diff.unchanged += 1,
+            Some(_) => diff.updated += 1,
+        }
+    }
+    diff
+}
+
+/// Equal as far as the feed is concerned: content filled in by full-article fetching
+/// (marked by `content_text`), WARC provenance and the starred flag are not something the feed
+/// can change.
+fn same_source(stored: &Entry, parsed: &Entry) -> bool {
+    let enriched = stored.content_text.is_some();
+    let strip = |e: &Entry| {
+        let mut e = Entry { warc_records: Vec::new(), starred: false, ..e.clone() };
+        if enriched {
+            e.content = None;
+            e.content_text = None;
+        }
+        e
+    };
+    strip(stored) == strip(parsed)
+}
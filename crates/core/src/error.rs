@@ -36,4 +36,7 @@ pub enum RepoError {
 
     #[error("backend failure: {0}")]
     Backend(String),
+
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
 }
@@ -0,0 +1,125 @@
+/*
+Module: rssify_core::fetch
+Purpose: Transport seam for fetching: request/response records and the Fetcher trait
+Public API surface: FetchRequest, FetchResponse, Fetcher, LastBlob, content_kind
+Invariants: No I/O here; transports live in adapters. Classification is pure.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use crate::{ContentBlob, ContentKind, FetchOutcome};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// What the pipeline asks a transport for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchRequest {
+    pub url: String,
+    /// Validator for If-None-Match.
+    pub etag: Option<String>,
+    /// Validator for If-Modified-Since.
+    pub last_modified: Option<String>,
+    /// Extra headers (resolved per-feed settings); may carry credentials.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    /// Addresses already vetted for this URL's host (SSRF guard). When set, transports connect
+    /// to one of these instead of resolving the host again; empty means resolve as usual.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_addrs: Vec<SocketAddr>,
+}
+
+/// A response as seen on the wire, before classification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FetchResponse {
+    pub status: u16,
+    /// Header names keep the server's casing; lookups are case-insensitive.
+    pub headers: Vec<(String, String)>,
+    /// Body with Content-Encoding already undone.
+    pub body: Vec<u8>,
+    pub elapsed_ms: u64,
+}
+
+/// Raw payload of the most recent successful fetch, kept so parsers can be re-run
+/// without refetching.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastBlob {
+    pub blob: ContentBlob,
+    /// Response headers as received (validators, content-type, ...).
+    pub headers: Vec<(String, String)>,
+    /// Unix seconds when the blob was fetched.
+    pub fetched_ts: i64,
+    /// WARC-Record-ID of the archived response (`fetch --warc`), if one was written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warc_record: Option<String>,
+}
+
+/// Transport seam. Implementations perform exactly one request and must not follow
+/// redirects themselves; 3xx responses are returned so wrappers can vet each hop. Network
+/// transports must honor `FetchRequest::pinned_addrs`.
+/// Failures that happen before a full response exists are returned as `Err` outcomes.
+pub trait Fetcher {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome>;
+}
+
+impl<F: Fetcher + ?Sized> Fetcher for &F {
+    fn fetch(&self, req: &FetchRequest) -> Result<FetchResponse, FetchOutcome> {
+        (**self).fetch(req)
+    }
+}
+
+impl FetchResponse {
+    /// First header value with the given name (case-insensitive).
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// True for 3xx statuses that carry a Location to follow.
+    #[must_use]
+    pub fn is_redirect(&self) -> bool {
+        matches!(self.status, 301 | 302 | 303 | 307 | 308) && self.header("location").is_some()
+    }
+
+    /// Map the status onto the boundary outcome used by the pipeline.
+    #[must_use]
+    pub fn outcome(&self) -> FetchOutcome {
+        match self.status {
+            200..=299 => FetchOutcome::NewContent {
+                blob: ContentBlob {
+                    kind: content_kind(self.header("content-type")),
+                    bytes: self.body.clone(),
+                },
+                elapsed_ms: self.elapsed_ms,
+            },
+            304 => FetchOutcome::NotModified,
+            410 => FetchOutcome::Gone,
+            408 | 425 | 429 | 500..=599 => FetchOutcome::TransientFailure {
+                hint: Some(format!("http {}", self.status)),
+            },
+            s => FetchOutcome::PermanentFailure {
+                hint: Some(format!("http {s}")),
+            },
+        }
+    }
+}
+
+/// Best-effort blob kind from a Content-Type header value.
+#[must_use]
+pub fn content_kind(content_type: Option<&str>) -> ContentKind {
+    let ct = content_type.unwrap_or("").to_ascii_lowercase();
+    let mime = ct.split(';').next().unwrap_or("").trim();
+    if mime.ends_with("xml") {
+        ContentKind::Xml
+    } else if mime.ends_with("json") {
+        ContentKind::Json
+    } else if mime == "text/html" || mime == "application/xhtml" {
+        ContentKind::Html
+    } else if mime.starts_with("text/") {
+        ContentKind::Text
+    } else {
+        ContentKind::Binary
+    }
+}
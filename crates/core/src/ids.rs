@@ -21,17 +21,20 @@ pub struct EntryId(String);
 
 impl FeedId {
     /// Access the underlying stable string form.
+    #[must_use]
     pub fn as_str(&self) -> &str {
         &self.0
     }
 
     /// Construct from an arbitrary string (caller ensures stability).
+    #[must_use]
     pub fn new<S: Into<String>>(s: S) -> Self {
         Self(s.into())
     }
 
     /// Canonical constructor from a source URL.
-    /// Policy (docs/ID_POLICY.md): "url:<original_url_trimmed>"
+    /// Policy (`docs/ID_POLICY.md`): `"url:<original_url_trimmed>"`
+    #[must_use]
     pub fn from_url(url: &str) -> Self {
         let trimmed = url.trim();
         Self(format!("url:{trimmed}"))
@@ -40,20 +43,23 @@ impl FeedId {
 
 impl EntryId {
     /// Access the underlying stable string form.
+    #[must_use]
     pub fn as_str(&self) -> &str {
         &self.0
     }
 
     /// Construct from an arbitrary string (caller ensures stability).
+    #[must_use]
     pub fn new<S: Into<String>>(s: S) -> Self {
         Self(s.into())
     }
 
     /// Canonical constructor from available entry parts.
-    /// Precedence (docs/ID_POLICY.md):
-    /// 1) GUID -> "guid:<guid>"
-    /// 2) Link -> "link:<link>"
-    /// 3) Hash(feed_id, title, published_ts) -> "hash:<u64_hex>"
+    /// Precedence (`docs/ID_POLICY.md`):
+    /// 1) GUID -> `"guid:<guid>"`
+    /// 2) Link -> `"link:<link>"`
+    /// 3) Hash(`feed_id`, `title`, `published_ts`) -> `"hash:<u64_hex>"`
+    #[must_use]
     pub fn from_parts(
         feed: &FeedId,
         guid: Option<&str>,
@@ -76,6 +82,6 @@ impl EntryId {
             ts.hash(&mut hasher);
         }
         let h = hasher.finish();
-        Self(format!("hash:{:016x}", h))
+        Self(format!("hash:{h:016x}"))
     }
 }
@@ -1,7 +1,9 @@
 /*
 Module: rssify_core::lib
 Purpose: Crate root; exposes core domain types/traits without any I/O
-Public API surface: pub use ids::*, model::*, error::*, repo::*, sched::*
+Public API surface: pub use archive::*, ids::*, model::*, error::*, repo::*, sched::*, deactivate::*, diff::*, fetch::*,
+                    migrate::*, mirror::*, query::*, request::*, retention::*, run::*, search::*,
+                    search_index::*
 Invariants: Core remains pure; all side effects live in adapters/repos
 Logging keys used: component, op, feed_id, elapsed_ms, items
 Notes: Keep file <= 200 LOC if possible; refactor at 300.
@@ -9,17 +11,41 @@ Notes: Keep file <= 200 LOC if possible; refactor at 300.
 
 #![forbid(unsafe_code)]
 #![deny(clippy::all, clippy::pedantic)]
-#![allow(clippy::module_name_repetitions)]
+#![allow(clippy::module_name_repetitions, clippy::missing_errors_doc)]
 
+pub mod archive;
+pub mod deactivate;
+pub mod diff;
 pub mod error;
+pub mod fetch;
 pub mod ids;
+pub mod migrate;
+pub mod mirror;
 pub mod model;
+pub mod query;
 pub mod repo;
+pub mod request;
+pub mod retention;
+pub mod run;
 pub mod sched;
+pub mod search;
+pub mod search_index;
 
+pub use archive::*;
+pub use deactivate::*;
+pub use diff::*;
 pub use error::*;
+pub use fetch::*;
 pub use ids::*;
+pub use migrate::*;
+pub use mirror::*;
 pub use model::*;
+pub use query::*;
 pub use repo::*;
+pub use request::*;
+pub use retention::*;
+pub use run::*;
 pub use sched::*;
+pub use search::*;
+pub use search_index::*;
 
@@ -0,0 +1,97 @@
+/*
+Module: rssify_core::mirror
+Purpose: Per-URL health for feeds with mirror URLs: scoring, ordering and failure folding
+Public API surface: UrlHealth, MirrorHealth::{record, score, order}, combine_failures
+Invariants:
+ - Pure; persistence lives behind ScheduleRepo.
+ - Ordering is deterministic: score descending, then configured order (primary first).
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use crate::FetchOutcome;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Weight of the newest sample in the latency moving average (1/N).
+const LATENCY_EWMA_N: u64 = 4;
+
+/// Observed health of one fetch URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlHealth {
+    pub successes: u32,
+    pub failures: u32,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
+    /// Exponential moving average of successful response times.
+    pub avg_latency_ms: u64,
+    pub last_ok_ts: Option<i64>,
+}
+
+impl UrlHealth {
+    /// Laplace-smoothed success rate, discounted by latency and by a current failure run.
+    /// Unknown URLs score 0.5, so a healthy known URL beats them and a failing one does not.
+    #[must_use]
+    pub fn score(&self) -> f64 {
+        let rate = f64::from(self.successes + 1) / f64::from(self.successes + self.failures + 2);
+        #[allow(clippy::cast_precision_loss)]
+        let latency = 1000.0 / (1000.0 + self.avg_latency_ms as f64);
+        rate * latency * 0.5_f64.powi(self.consecutive_failures.min(16).cast_signed())
+    }
+}
+
+/// Health of every URL a feed has been fetched from, keyed by URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MirrorHealth {
+    pub urls: BTreeMap<String, UrlHealth>,
+}
+
+impl MirrorHealth {
+    /// Fold one attempt against `url` into its health.
+    pub fn record(&mut self, url: &str, ok: bool, latency_ms: u64, now: i64) {
+        let h = self.urls.entry(url.to_string()).or_default();
+        if ok {
+            h.avg_latency_ms = if h.successes == 0 {
+                latency_ms
+            } else {
+                (h.avg_latency_ms * (LATENCY_EWMA_N - 1) + latency_ms) / LATENCY_EWMA_N
+            };
+            h.successes = h.successes.saturating_add(1);
+            h.consecutive_failures = 0;
+            h.last_ok_ts = Some(now);
+        } else {
+            h.failures = h.failures.saturating_add(1);
+            h.consecutive_failures = h.consecutive_failures.saturating_add(1);
+        }
+    }
+
+    #[must_use]
+    pub fn score(&self, url: &str) -> f64 {
+        self.urls.get(url).map_or_else(|| UrlHealth::default().score(), UrlHealth::score)
+    }
+
+    /// `candidates` (primary first) reordered healthiest first; ties keep configured order.
+    #[must_use]
+    pub fn order(&self, candidates: &[String]) -> Vec<String> {
+        let mut ranked: Vec<(usize, &String)> = candidates.iter().enumerate().collect();
+        ranked.sort_by(|(ia, a), (ib, b)| self.score(b).total_cmp(&self.score(a)).then(ia.cmp(ib)));
+        ranked.into_iter().map(|(_, u)| u.clone()).collect()
+    }
+}
+
+/// Outcome for a feed when every URL failed: Gone only if all are gone, transient if any
+/// failure may recover, otherwise the first permanent failure.
+#[must_use]
+pub fn combine_failures(failures: &[FetchOutcome]) -> FetchOutcome {
+    if !failures.is_empty() && failures.iter().all(|f| matches!(f, FetchOutcome::Gone)) {
+        return FetchOutcome::Gone;
+    }
+    if let Some(t) = failures.iter().find(|f| matches!(f, FetchOutcome::TransientFailure { .. })) {
+        return t.clone();
+    }
+    failures
+        .iter()
+        .find(|f| matches!(f, FetchOutcome::PermanentFailure { .. }))
+        .cloned()
+        .unwrap_or(FetchOutcome::PermanentFailure { hint: Some("no fetch url".to_string()) })
+}
@@ -1,13 +1,13 @@
 /*
 Module: rssify_core::model
 Purpose: Pure domain records for feeds, entries, and fetch results
-Public API surface: Feed, Entry, ContentBlob, ContentKind, FetchOutcome
+Public API surface: Feed, FeedSettings, Entry, Enclosure, ContentBlob, ContentKind, FetchOutcome
 Invariants: Records are serde-serializable and transport-friendly
 Logging keys used: component, op, feed_id, elapsed_ms, items
 Notes: Keep file <= 200 LOC if possible; refactor at 300.
 */
 
-use crate::{EntryId, FeedId};
+use crate::{ArchiveSettings, Deactivation, EntryId, FeedId, RequestSettings};
 use serde::{Deserialize, Serialize};
 
 /// Raw content captured from a source (kept as bytes; encoding may vary).
@@ -37,6 +37,54 @@ pub struct Feed {
     pub etag: Option<String>,
     pub last_modified: Option<String>,
     pub active: bool,
+    /// Why and when the feed stopped being fetched; `None` while active.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deactivated: Option<Deactivation>,
+    /// Per-feed overrides of global fetch policy.
+    #[serde(default, skip_serializing_if = "FeedSettings::is_default")]
+    pub settings: FeedSettings,
+}
+
+impl Feed {
+    /// Primary URL followed by distinct mirrors; entries from any of them share `self.id`.
+    #[must_use]
+    pub fn urls(&self) -> Vec<String> {
+        let mut out = vec![self.url.clone()];
+        for m in &self.settings.mirrors {
+            if !out.contains(m) {
+                out.push(m.clone());
+            }
+        }
+        out
+    }
+}
+
+/// Per-feed fetch knobs; unset fields fall back to the global policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedSettings {
+    /// Maximum download size in bytes for this feed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Fetch each new entry's page and store its extracted article as the entry content.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub full_content: bool,
+    /// Download enclosures and content images into the repo's blob store.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchiveSettings>,
+    /// Alternate URLs serving the same feed, in preference order after `Feed::url`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<String>,
+    /// Extra headers, auth, user agent and cookie sent with this feed's requests.
+    #[serde(default, skip_serializing_if = "RequestSettings::is_default")]
+    pub request: RequestSettings,
+}
+
+impl FeedSettings {
+    /// True when nothing is overridden (used to keep feed.json compact).
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// Canonical entry representation post-parse/normalize.
@@ -50,12 +98,39 @@ pub struct Entry {
     pub updated_ts: Option<i64>,   // unix seconds
     pub summary: Option<String>,
     pub content: Option<ContentBlob>,
+    /// Readable plain text of `content` when it came from full-article extraction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_text: Option<String>,
+    /// Media attached by the feed (RSS `<enclosure>`, Atom `rel="enclosure"`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enclosures: Vec<Enclosure>,
+    /// WARC-Record-IDs of the responses this entry's stored content came from (`fetch --warc`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warc_records: Vec<String>,
+    /// Kept by the user; retention never prunes starred entries.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub starred: bool,
+    /// Author names as the feed gives them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+}
+
+/// A media file an entry points at, as declared by the feed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Enclosure {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime: Option<String>,
+    /// Declared length in bytes (feeds often get this wrong).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
 }
 
 /// Result of a fetch attempt at the boundary (no network in core).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FetchOutcome {
     NotModified, // 304-equivalent
+    Gone,        // 410-equivalent; the source was removed for good
     NewContent { blob: ContentBlob, elapsed_ms: u64 },
     TransientFailure { hint: Option<String> },
     PermanentFailure { hint: Option<String> },
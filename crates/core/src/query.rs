@@ -0,0 +1,198 @@
+/*
+Module: rssify_core::query
+Purpose: Filtered, ordered, paginated entry queries shared by every EntryRepo backend
+Public API surface: EntryQuery::{validate, matches, sort_key, is_past_cursor, page, finish}, EntryOrder,
+                    EntryPage, Cursor::{encode, decode, position}, DEFAULT_PAGE_SIZE
+Invariants:
+ - Entries are ordered by (published_ts, id); undated entries sort as the oldest.
+ - A cursor holds the sort key of the last entry returned, so pages stay stable while entries
+   are added or removed elsewhere (keyset pagination, no offsets).
+ - A cursor is only valid for the order it was issued under.
+ - Title matching is an ASCII case-insensitive substring match; entries without a title never
+   match a title filter. Range filters skip undated entries.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Scanning backends filter with `matches`/`is_past_cursor` and call `page`; indexed backends
+       translate the same fields to their query language and call `finish` on limit + 1 rows.
+*/
+
+use crate::{Entry, EntryId, FeedId, RepoError};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Page size used when a query does not set one.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Sort direction over (`published_ts`, id).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Entry filter plus one page's worth of position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryQuery {
+    /// Only entries of these feeds; empty means every feed.
+    pub feeds: Vec<FeedId>,
+    /// Inclusive lower bound on `published_ts` (unix seconds).
+    pub published_from: Option<i64>,
+    /// Exclusive upper bound on `published_ts` (unix seconds).
+    pub published_until: Option<i64>,
+    /// Substring the title must contain (ASCII case-insensitive).
+    pub title_contains: Option<String>,
+    pub order: EntryOrder,
+    /// Maximum entries per page; must be positive.
+    pub limit: usize,
+    /// Continue after the page that returned this cursor.
+    pub cursor: Option<Cursor>,
+}
+
+impl Default for EntryQuery {
+    fn default() -> Self {
+        Self {
+            feeds: Vec::new(),
+            published_from: None,
+            published_until: None,
+            title_contains: None,
+            order: EntryOrder::default(),
+            limit: DEFAULT_PAGE_SIZE,
+            cursor: None,
+        }
+    }
+}
+
+/// One page of results; `next` is `None` on the last page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntryPage {
+    pub entries: Vec<Entry>,
+    pub next: Option<Cursor>,
+}
+
+/// Opaque position after an entry; pass `encode()` to clients and `decode` what they send back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    order: EntryOrder,
+    key: i64,
+    id: EntryId,
+}
+
+impl Cursor {
+    /// URL-safe token (lowercase hex).
+    #[must_use]
+    pub fn encode(&self) -> String {
+        let tag = match self.order {
+            EntryOrder::NewestFirst => 'n',
+            EntryOrder::OldestFirst => 'o',
+        };
+        format!("{tag}{}:{}", self.key, self.id.as_str()).bytes().fold(String::new(), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+    }
+
+    pub fn decode(token: &str) -> Result<Self, RepoError> {
+        let bad = || RepoError::InvalidQuery(format!("malformed cursor {token:?}"));
+        if !token.len().is_multiple_of(2) || !token.is_ascii() {
+            return Err(bad());
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&token[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| bad())?;
+        let raw = String::from_utf8(bytes).map_err(|_| bad())?;
+        let order = match raw.chars().next() {
+            Some('n') => EntryOrder::NewestFirst,
+            Some('o') => EntryOrder::OldestFirst,
+            _ => return Err(bad()),
+        };
+        let (key, id) = raw[1..].split_once(':').ok_or_else(bad)?;
+        Ok(Self { order, key: key.parse().map_err(|_| bad())?, id: EntryId::new(id) })
+    }
+
+    /// Sort key and id of the last entry the previous page returned.
+    #[must_use]
+    pub fn position(&self) -> (i64, &EntryId) {
+        (self.key, &self.id)
+    }
+}
+
+impl EntryQuery {
+    /// Position an entry sorts at: `published_ts`, with undated entries as the oldest.
+    #[must_use]
+    pub fn sort_key(entry: &Entry) -> i64 {
+        entry.published_ts.unwrap_or(i64::MIN)
+    }
+
+    /// Refuse queries no backend can answer: a zero limit or a cursor from another order.
+    pub fn validate(&self) -> Result<(), RepoError> {
+        if self.limit == 0 {
+            return Err(RepoError::InvalidQuery("limit must be positive".into()));
+        }
+        match &self.cursor {
+            Some(c) if c.order != self.order => {
+                Err(RepoError::InvalidQuery("cursor was issued for the other order".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// True when `entry` passes the feed, time range and title filters (the cursor aside).
+    #[must_use]
+    pub fn matches(&self, entry: &Entry) -> bool {
+        let in_feeds = self.feeds.is_empty() || self.feeds.contains(&entry.feed);
+        let ranged = self.published_from.is_some() || self.published_until.is_some();
+        let in_range = !ranged
+            || entry.published_ts.is_some_and(|ts| {
+                self.published_from.is_none_or(|from| ts >= from) && self.published_until.is_none_or(|to| ts < to)
+            });
+        let titled = self.title_contains.as_ref().is_none_or(|needle| {
+            entry.title.as_ref().is_some_and(|t| t.to_ascii_lowercase().contains(&needle.to_ascii_lowercase()))
+        });
+        in_feeds && in_range && titled
+    }
+
+    /// True when `entry` comes after the cursor in the query order (always true without one).
+    #[must_use]
+    pub fn is_past_cursor(&self, entry: &Entry) -> bool {
+        let Some(c) = &self.cursor else { return true };
+        let here = (Self::sort_key(entry), entry.id.as_str());
+        let there = (c.key, c.id.as_str());
+        match self.order {
+            EntryOrder::NewestFirst => here < there,
+            EntryOrder::OldestFirst => here > there,
+        }
+    }
+
+    /// Filter, order and cut `entries` into this query's page (for backends that scan).
+    pub fn page(&self, entries: impl IntoIterator<Item = Entry>) -> Result<EntryPage, RepoError> {
+        self.validate()?;
+        let mut rows: Vec<Entry> =
+            entries.into_iter().filter(|e| self.matches(e) && self.is_past_cursor(e)).collect();
+        rows.sort_by(|a, b| {
+            let ord = (Self::sort_key(a), a.id.as_str()).cmp(&(Self::sort_key(b), b.id.as_str()));
+            match self.order {
+                EntryOrder::NewestFirst => ord.reverse(),
+                EntryOrder::OldestFirst => ord,
+            }
+        });
+        rows.truncate(self.limit.saturating_add(1));
+        Ok(self.finish(rows))
+    }
+
+    /// Build the page from up to `limit + 1` rows already filtered and in query order; the
+    /// extra row only signals that another page exists.
+    #[must_use]
+    pub fn finish(&self, mut rows: Vec<Entry>) -> EntryPage {
+        if rows.len() <= self.limit {
+            return EntryPage { entries: rows, next: None };
+        }
+        rows.truncate(self.limit);
+        let next = rows
+            .last()
+            .map(|e| Cursor { order: self.order, key: Self::sort_key(e), id: e.id.clone() });
+        EntryPage { entries: rows, next }
+    }
+}
@@ -1,13 +1,16 @@
 /*
 Module: rssify_core::repo
 Purpose: Repository boundary contracts (traits) for feeds, entries, schedule
-Public API surface: Tx, FeedRepo, EntryRepo, ScheduleRepo
+Public API surface: Tx, TxRepo, FeedRepo, EntryRepo, ScheduleRepo, RunRepo, BlobRepo, ArchiveRepo, SearchRepo
 Invariants: No I/O in core; implement in adapters; results return RepoError
 Logging keys used: component, op, feed_id, elapsed_ms, items
 Notes: Keep file <= 200 LOC if possible; refactor at 300.
 */
 
-use crate::{Entry, EntryId, Feed, FeedId, RepoError};
+use crate::{
+    ArchivedEntry, Entry, EntryId, EntryPage, EntryQuery, FailureStreak, Feed, FeedId, FeedRunStatus, LastBlob,
+    MirrorHealth, RepoError, RetentionPolicy, RunManifest, SearchHit, SearchQuery,
+};
 
 /// Opaque transaction/context handle exposed by backends.
 /// Adapters decide whether this is real transactional state or a no-op.
@@ -16,6 +19,26 @@ pub trait Tx {
     fn is_active(&self) -> bool;
 }
 
+/// Begin and commit the transaction every repo trait of a backend accepts, so a caller can
+/// group writes made through several traits (the fetch pipeline commits each feed at once).
+pub trait TxRepo {
+    type Tx<'a>: Tx
+    where
+        Self: 'a;
+
+    /// Begin a write transaction; dropping it uncommitted discards its writes.
+    fn begin_tx(&self) -> Result<Self::Tx<'_>, RepoError>;
+
+    /// Make every write passed `tx` durable together.
+    fn commit_tx(tx: Self::Tx<'_>) -> Result<(), RepoError>;
+
+    /// `tx` for a shorter borrow. Transactions are covariant in their lifetime, which generic
+    /// callers cannot see: they need this to pass a tx to repo calls and commit it afterwards.
+    fn reborrow<'s, 'r: 's>(tx: &'s Self::Tx<'r>) -> &'s Self::Tx<'s>
+    where
+        Self: 'r;
+}
+
 /// CRUD surface for feeds.
 pub trait FeedRepo {
     type Tx<'a>: Tx
@@ -25,6 +48,25 @@ pub trait FeedRepo {
     fn get<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Feed, RepoError>;
     fn put<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &Feed) -> Result<(), RepoError>;
     fn list<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<Vec<Feed>, RepoError>;
+
+    /// Raw payload of the last successful fetch, if one was stored.
+    fn last_blob<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        id: &FeedId,
+    ) -> Result<Option<LastBlob>, RepoError>;
+
+    /// Replace the stored last payload for a feed.
+    fn put_last_blob<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        id: &FeedId,
+        blob: &LastBlob,
+    ) -> Result<(), RepoError>;
+
+    /// Unsubscribe: remove the feed with its last blob, entries, archive records and schedule
+    /// state; `NotFound` when nothing is stored for it. Run manifests and blobs stay.
+    fn delete<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<(), RepoError>;
 }
 
 /// CRUD surface for entries.
@@ -40,6 +82,26 @@ pub trait EntryRepo {
         tx: Option<&'a Self::Tx<'a>>,
         feed: &FeedId,
     ) -> Result<Vec<Entry>, RepoError>;
+
+    /// How many entries `feed` has, without loading them.
+    fn count_by_feed<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<usize, RepoError>;
+
+    /// One page of the entries matching `query`, across feeds, in `query.order`; follow
+    /// `EntryPage::next` for the rest. `InvalidQuery` for a zero limit or a foreign cursor.
+    fn query<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, query: &EntryQuery) -> Result<EntryPage, RepoError>;
+
+    /// Remove one entry and its archive record; `NotFound` when it is not stored.
+    fn delete<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<(), RepoError>;
+
+    /// Delete the feed's entries that `policy` does not keep at `now` (unix seconds), as
+    /// `RetentionPolicy::expired` picks them; returns the deleted ids.
+    fn prune<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<Vec<EntryId>, RepoError>;
 }
 
 /// Minimal scheduling persistence (future phases can expand).
@@ -62,4 +124,117 @@ pub trait ScheduleRepo {
         feed: &FeedId,
         ts: i64,
     ) -> Result<(), RepoError>;
+
+    /// Failures accumulated since the last success (default when none recorded).
+    fn failure_streak<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<FailureStreak, RepoError>;
+
+    /// Replace the stored failure streak for a feed.
+    fn record_failure_streak<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        streak: &FailureStreak,
+    ) -> Result<(), RepoError>;
+
+    /// Per-URL health for feeds with mirrors (default when none recorded).
+    fn mirror_health<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<MirrorHealth, RepoError>;
+
+    /// Replace the stored mirror health for a feed.
+    fn record_mirror_health<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        health: &MirrorHealth,
+    ) -> Result<(), RepoError>;
+}
+
+/// Fetch run manifests and their per-feed checkpoints.
+pub trait RunRepo {
+    type Tx<'a>: Tx
+    where
+        Self: 'a;
+
+    /// Create or replace a run manifest (statuses included).
+    fn put_run<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, run: &RunManifest) -> Result<(), RepoError>;
+
+    /// Durably record one feed's status within a run; cheap enough to call after every feed.
+    fn record_run_feed<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        run_id: &str,
+        feed: &str,
+        status: FeedRunStatus,
+    ) -> Result<(), RepoError>;
+
+    /// Manifest with all recorded checkpoints applied; `NotFound` for unknown ids.
+    fn get_run<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, run_id: &str) -> Result<RunManifest, RepoError>;
+
+    /// Most recently started run without `finished_at`, if any.
+    fn latest_incomplete_run<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+    ) -> Result<Option<RunManifest>, RepoError>;
+}
+
+/// Content-addressed byte store (sha256). Identical bytes are stored once.
+pub trait BlobRepo {
+    type Tx<'a>: Tx
+    where
+        Self: 'a;
+
+    /// Store `bytes` and return their lowercase sha256 hex; a no-op when already present.
+    fn put_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, bytes: &[u8]) -> Result<String, RepoError>;
+
+    fn get_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, sha256: &str) -> Result<Option<Vec<u8>>, RepoError>;
+
+    /// Delete blobs that no stored entry content or archive attachment references; returns how
+    /// many went. Run it without concurrent writers: a blob stored ahead of the record that
+    /// will reference it looks unreferenced.
+    fn gc_blobs<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError>;
+}
+
+/// Archived copies of entries (attachments live in `BlobRepo`).
+pub trait ArchiveRepo {
+    type Tx<'a>: Tx
+    where
+        Self: 'a;
+
+    /// Create or replace the archived copy of `archived.entry`.
+    fn put_archive<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, archived: &ArchivedEntry) -> Result<(), RepoError>;
+
+    fn archive<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, entry: &EntryId) -> Result<Option<ArchivedEntry>, RepoError>;
+
+    /// All archived entries of one feed (quota accounting).
+    fn archives_by_feed<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<Vec<ArchivedEntry>, RepoError>;
+}
+
+/// Full-text index over entries (title, summary, readable text, authors). Backends keep it in
+/// step with every entry write and delete.
+pub trait SearchRepo {
+    type Tx<'a>: Tx
+    where
+        Self: 'a;
+
+    /// The best `limit` matches of `query`, BM25-ranked (see `rssify_core::rank`).
+    fn search<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, RepoError>;
+
+    /// Rebuild the index from the stored entries; returns how many were indexed.
+    fn reindex<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError>;
 }
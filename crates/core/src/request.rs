@@ -0,0 +1,119 @@
+/*
+Module: rssify_core::request
+Purpose: Per-feed request customization (headers, auth, user agent, cookie) with secret references
+Public API surface: RequestSettings, HeaderValue, SecretRef, AuthSetting, RequestSettings::resolve
+Invariants:
+ - Credentials are never stored inline: auth and cookie only accept SecretRef.
+ - Resolution is pure; callers inject the lookup (env, secrets file) so core stays I/O free.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use serde::{Deserialize, Serialize};
+
+/// Where a credential comes from: `{"env": "NAME"}` or `{"secret": "key"}` (secrets file).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretRef {
+    Env(String),
+    Secret(String),
+}
+
+impl std::fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "env:{name}"),
+            Self::Secret(key) => write!(f, "secret:{key}"),
+        }
+    }
+}
+
+/// Extra header value: a literal string or a secret reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum HeaderValue {
+    Plain(String),
+    Ref(SecretRef),
+}
+
+/// Authentication scheme applied as an `Authorization` header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AuthSetting {
+    Basic { username: String, password: SecretRef },
+    Bearer { token: SecretRef },
+}
+
+/// Per-feed request knobs; all optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestSettings {
+    /// Extra headers in send order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, HeaderValue)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthSetting>,
+    /// Overrides the global user agent for this feed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cookie: Option<SecretRef>,
+}
+
+impl RequestSettings {
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Materialize headers, looking up each secret through `lookup`.
+    /// Returns the first unresolved reference as the error.
+    pub fn resolve<L>(&self, lookup: L) -> Result<Vec<(String, String)>, SecretRef>
+    where
+        L: Fn(&SecretRef) -> Option<String>,
+    {
+        let get = |r: &SecretRef| lookup(r).ok_or_else(|| r.clone());
+        let mut out = Vec::with_capacity(self.headers.len() + 3);
+        for (name, value) in &self.headers {
+            let v = match value {
+                HeaderValue::Plain(s) => s.clone(),
+                HeaderValue::Ref(r) => get(r)?,
+            };
+            out.push((name.clone(), v));
+        }
+        if let Some(ua) = &self.user_agent {
+            out.push(("User-Agent".to_string(), ua.clone()));
+        }
+        match &self.auth {
+            Some(AuthSetting::Basic { username, password }) => {
+                let pair = format!("{username}:{}", get(password)?);
+                out.push(("Authorization".to_string(), format!("Basic {}", base64(pair.as_bytes()))));
+            }
+            Some(AuthSetting::Bearer { token }) => {
+                out.push(("Authorization".to_string(), format!("Bearer {}", get(token)?)));
+            }
+            None => {}
+        }
+        if let Some(cookie) = &self.cookie {
+            out.push(("Cookie".to_string(), get(cookie)?));
+        }
+        Ok(out)
+    }
+}
+
+/// Standard base64 with padding (RFC 4648), enough for Basic auth.
+fn base64(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,78 @@
+/*
+Module: rssify_core::run
+Purpose: Manifest of one fetch run (id, start time, per-feed status) for checkpoints and resume
+Public API surface: RunManifest::{new, mark, pending, is_complete, finish}, RunFeed, FeedRunStatus
+Invariants:
+ - Feed order is the order the run visits them; marking keeps it.
+ - A run is complete once finished_at is set; resume only looks at incomplete runs.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Keep file <= 200 LOC if possible; refactor at 300.
+*/
+
+use serde::{Deserialize, Serialize};
+
+/// Where one feed stands within a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedRunStatus {
+    /// Not visited yet; resume picks these up.
+    Pending,
+    /// Fetched, not modified, or registered without fetching.
+    Done,
+    /// Visited but the fetch failed (counts as completed for this run).
+    Failed,
+    /// Not fetched on purpose, e.g. deactivated.
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunFeed {
+    pub id: String,
+    pub status: FeedRunStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub run_id: String,
+    /// Unix seconds.
+    pub started_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<i64>,
+    pub feeds: Vec<RunFeed>,
+}
+
+impl RunManifest {
+    /// A run over `feeds`, all pending.
+    #[must_use]
+    pub fn new(run_id: impl Into<String>, started_at: i64, feeds: &[String]) -> Self {
+        Self {
+            run_id: run_id.into(),
+            started_at,
+            finished_at: None,
+            feeds: feeds.iter().map(|id| RunFeed { id: id.clone(), status: FeedRunStatus::Pending }).collect(),
+        }
+    }
+
+    /// Set the status of `id`; unknown ids are appended.
+    pub fn mark(&mut self, id: &str, status: FeedRunStatus) {
+        match self.feeds.iter_mut().find(|f| f.id == id) {
+            Some(f) => f.status = status,
+            None => self.feeds.push(RunFeed { id: id.to_string(), status }),
+        }
+    }
+
+    /// Ids still to visit, in run order.
+    #[must_use]
+    pub fn pending(&self) -> Vec<String> {
+        self.feeds.iter().filter(|f| f.status == FeedRunStatus::Pending).map(|f| f.id.clone()).collect()
+    }
+
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.finished_at.is_some()
+    }
+
+    pub fn finish(&mut self, ts: i64) {
+        self.finished_at = Some(ts);
+    }
+}
@@ -0,0 +1,218 @@
+/*
+Module: rssify_core::search
+Purpose: Search query language and BM25 ranking over any PostingSource
+Public API surface: SearchQuery::{parse, words}, SearchHit, rank, BM25_K1, BM25_B
+Invariants:
+ - Every word, phrase and author filter must match (AND); BM25 only orders the matches.
+ - A phrase matches consecutive positions; bare words that tokenize to several tokens
+   ("rust-lang") are phrases too.
+ - Filters: `feed:<id or url>` (repeatable, any of them), `author:<name>` (tokens of the name as
+   a phrase within authors), `after:<unix secs | YYYY-MM-DD>` (published on or after).
+ - Ties rank newer entries first, then by id, so results are deterministic.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Token rules and what gets indexed live in search_index.rs.
+*/
+
+use crate::{tokenize, EntryId, FeedId, Posting, PostingSource, RepoError, AUTHOR_PREFIX};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// BM25 term-frequency saturation.
+pub const BM25_K1: f64 = 1.2;
+/// BM25 document-length normalization.
+pub const BM25_B: f64 = 0.75;
+
+/// A parsed search.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Single tokens.
+    pub words: Vec<String>,
+    /// Token sequences that must appear consecutively.
+    pub phrases: Vec<Vec<String>>,
+    /// Entry must belong to one of these (empty = any feed).
+    pub feeds: Vec<FeedId>,
+    /// Each is one author's tokens, matched as a phrase among the entry's authors.
+    pub authors: Vec<Vec<String>>,
+    /// Inclusive lower bound on `published_ts`; undated entries never match.
+    pub after: Option<i64>,
+}
+
+/// One ranked match.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SearchHit {
+    pub entry: EntryId,
+    pub feed: FeedId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_ts: Option<i64>,
+    pub score: f64,
+}
+
+impl SearchQuery {
+    /// Parse user input; `InvalidQuery` when it holds no word to look up or a bad `after:`.
+    pub fn parse(input: &str) -> Result<Self, RepoError> {
+        let mut q = Self::default();
+        for (key, value) in lex(input) {
+            match key.as_deref() {
+                Some("feed") => {
+                    q.feeds.push(FeedId::new(value.clone()));
+                    if value.contains("://") {
+                        q.feeds.push(FeedId::from_url(&value));
+                    }
+                }
+                Some("author") => q.authors.push(tokenize(&value)),
+                Some("after") => q.after = Some(parse_after(&value)?),
+                Some(other) => q.push_text(&format!("{other}:{value}")),
+                None => q.push_text(&value),
+            }
+        }
+        q.authors.retain(|a| !a.is_empty());
+        if q.words.is_empty() && q.phrases.is_empty() {
+            return Err(RepoError::InvalidQuery("search needs at least one word".into()));
+        }
+        Ok(q)
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut tokens = tokenize(text);
+        match tokens.len() {
+            0 => {}
+            1 => self.words.extend(tokens.pop()),
+            _ => self.phrases.push(tokens),
+        }
+    }
+
+    /// Distinct tokens that are looked up and scored: words and phrase tokens.
+    #[must_use]
+    pub fn words(&self) -> Vec<&str> {
+        let mut all: Vec<&str> = self.words.iter().chain(self.phrases.iter().flatten()).map(String::as_str).collect();
+        all.sort_unstable();
+        all.dedup();
+        all
+    }
+}
+
+/// Split into (`key`, value) pairs: `key:value`, `key:"quoted value"`, `"phrase"` or a bare word.
+fn lex(input: &str) -> Vec<(Option<String>, String)> {
+    let mut out = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut key = None;
+        let mut value = String::new();
+        if c != '"' {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                chars.next();
+                if c == ':' && key.is_none() && !value.is_empty() && chars.peek() == Some(&'"') {
+                    key = Some(std::mem::take(&mut value));
+                    break;
+                }
+                value.push(c);
+            }
+        }
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            value.extend(chars.by_ref().take_while(|&c| c != '"'));
+        }
+        if key.is_none()
+            && let Some((k, v)) = value.split_once(':')
+            && ["feed", "author", "after"].contains(&k)
+        {
+            key = Some(k.to_string());
+            value = v.to_string();
+        }
+        out.push((key, value));
+    }
+    out
+}
+
+/// `after:` value: unix seconds or a UTC calendar date.
+fn parse_after(value: &str) -> Result<i64, RepoError> {
+    if let Ok(ts) = value.parse::<i64>() {
+        return Ok(ts);
+    }
+    let bad = || RepoError::InvalidQuery(format!("after:{value} is neither unix seconds nor YYYY-MM-DD"));
+    let mut parts = value.splitn(3, '-').map(str::parse::<i64>);
+    let (Some(Ok(y)), Some(Ok(m)), Some(Ok(d))) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(bad());
+    };
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return Err(bad());
+    }
+    // Days since 1970-01-01 (Howard Hinnant's days_from_civil).
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    Ok((era * 146_097 + yoe * 365 + yoe / 4 - yoe / 100 + doy - 719_468) * 86_400)
+}
+
+/// True when `seq` occurs at consecutive positions, given each token's postings in one entry.
+fn consecutive(seq: &[&Posting]) -> bool {
+    let Some((first, rest)) = seq.split_first() else { return false };
+    first.positions.iter().any(|&p| {
+        rest.iter().zip(1u32..).all(|(next, k)| next.positions.binary_search(&(p + k)).is_ok())
+    })
+}
+
+/// The best `limit` entries for `query` from `source`, best first.
+pub fn rank(source: &impl PostingSource, query: &SearchQuery, limit: usize) -> Result<Vec<SearchHit>, RepoError> {
+    let mut by_term: BTreeMap<String, BTreeMap<EntryId, Posting>> = BTreeMap::new();
+    let authors = query.authors.iter().flatten().map(|t| format!("{AUTHOR_PREFIX}{t}"));
+    for term in query.words().into_iter().map(str::to_string).chain(authors) {
+        if let std::collections::btree_map::Entry::Vacant(slot) = by_term.entry(term) {
+            let postings = source.postings(slot.key())?.into_iter().map(|p| (p.entry.clone(), p)).collect();
+            slot.insert(postings);
+        }
+    }
+    let get = |term: &str, id: &EntryId| by_term.get(term).and_then(|m| m.get(id));
+    // Candidates: entries holding the rarest looked-up word, narrowed by everything else.
+    let words = query.words();
+    let Some(rarest) = words.iter().min_by_key(|w| by_term.get(**w).map_or(0, BTreeMap::len)) else {
+        return Ok(Vec::new());
+    };
+    let stats = source.corpus()?;
+    #[allow(clippy::cast_precision_loss)] // corpus sizes stay far below 2^52
+    let (n, avgdl) = (stats.docs as f64, stats.total_len as f64 / stats.docs.max(1) as f64);
+    let mut hits = Vec::new();
+    for (id, first) in by_term.get(*rarest).into_iter().flatten() {
+        let matched = words.iter().all(|w| get(w, id).is_some())
+            && (query.feeds.is_empty() || query.feeds.contains(&first.feed))
+            && query.after.is_none_or(|after| first.published_ts.is_some_and(|ts| ts >= after))
+            && query.phrases.iter().all(|ph| {
+                ph.iter().map(|t| get(t, id)).collect::<Option<Vec<_>>>().is_some_and(|seq| consecutive(&seq))
+            })
+            && query.authors.iter().all(|a| {
+                a.iter()
+                    .map(|t| get(&format!("{AUTHOR_PREFIX}{t}"), id))
+                    .collect::<Option<Vec<_>>>()
+                    .is_some_and(|seq| consecutive(&seq))
+            });
+        if !matched {
+            continue;
+        }
+        let score = words
+            .iter()
+            .filter_map(|w| Some((by_term.get(*w)?.len(), get(w, id)?)))
+            .map(|(df, p)| {
+                #[allow(clippy::cast_precision_loss)]
+                let df = df as f64;
+                let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+                let tf = f64::from(u32::try_from(p.positions.len()).unwrap_or(u32::MAX));
+                let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * f64::from(p.len) / avgdl.max(1.0));
+                idf * tf * (BM25_K1 + 1.0) / (tf + norm)
+            })
+            .sum();
+        hits.push(SearchHit { entry: id.clone(), feed: first.feed.clone(), published_ts: first.published_ts, score });
+    }
+    hits.sort_by(|a, b| {
+        b.score.total_cmp(&a.score).then(b.published_ts.cmp(&a.published_ts)).then(a.entry.cmp(&b.entry))
+    });
+    hits.truncate(limit);
+    Ok(hits)
+}
@@ -0,0 +1,178 @@
+/*
+Module: rssify_core::search_index
+Purpose: What the full-text index stores per entry: tokens, positions and the ranking inputs
+Public API surface: tokenize, IndexedDoc::{from_entry, postings}, Posting, CorpusStats, PostingSource, MemIndex
+Invariants:
+ - Tokens are lowercased runs of alphanumeric characters; longer than MAX_TOKEN_CHARS are dropped.
+ - Fields are indexed in order title, summary (markup stripped), readable text, authors, with a
+   one-position gap between fields so phrases never span two of them.
+ - Author tokens are also indexed under AUTHOR_PREFIX terms (for `author:` filters); those
+   terms do not count towards the document length.
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Backends persist postings however they like and hand them back through PostingSource;
+       ranking lives in search.rs.
+*/
+
+use crate::{Entry, EntryId, FeedId, RepoError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Longest token kept, in characters.
+pub const MAX_TOKEN_CHARS: usize = 64;
+/// Prefix of the terms that carry author tokens; plain tokens never contain ':'.
+pub const AUTHOR_PREFIX: &str = "author:";
+
+/// Lowercased alphanumeric runs of `text`.
+#[must_use]
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty() && t.chars().count() <= MAX_TOKEN_CHARS)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// `html` without tags and character references, which would otherwise index as words.
+fn strip_markup(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(i) = rest.find(['<', '&']) {
+        out.push_str(&rest[..i]);
+        let tail = &rest[i..];
+        let end = if tail.starts_with('<') {
+            tail.find('>')
+        } else {
+            // `&name;` or `&#123;`, short enough to be a reference rather than prose.
+            tail[1..]
+                .find(';')
+                .filter(|&j| j <= 10 && tail[1..=j].chars().all(|c| c.is_ascii_alphanumeric() || c == '#'))
+                .map(|j| j + 1)
+        };
+        if let Some(e) = end {
+            out.push(' ');
+            rest = &tail[e + 1..];
+        } else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Corpus-wide inputs to BM25.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusStats {
+    /// Indexed entries.
+    pub docs: u64,
+    /// Sum of their lengths in tokens.
+    pub total_len: u64,
+}
+
+/// One entry's occurrences of one term, with what filters and ranking need about the entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Posting {
+    pub entry: EntryId,
+    pub feed: FeedId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_ts: Option<i64>,
+    /// Document length in tokens.
+    pub len: u32,
+    /// Token positions, ascending.
+    pub positions: Vec<u32>,
+}
+
+/// An entry as the index sees it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedDoc {
+    pub entry: EntryId,
+    pub feed: FeedId,
+    pub published_ts: Option<i64>,
+    pub len: u32,
+    /// Positions of every term, author terms included.
+    pub terms: BTreeMap<String, Vec<u32>>,
+}
+
+impl IndexedDoc {
+    #[must_use]
+    pub fn from_entry(entry: &Entry) -> Self {
+        let mut terms: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        let mut pos: u32 = 0;
+        let mut add = |text: &str, author: bool| {
+            for token in tokenize(text) {
+                if author {
+                    terms.entry(format!("{AUTHOR_PREFIX}{token}")).or_default().push(pos);
+                }
+                terms.entry(token).or_default().push(pos);
+                pos += 1;
+            }
+            pos += 1;
+        };
+        add(entry.title.as_deref().unwrap_or_default(), false);
+        add(&strip_markup(entry.summary.as_deref().unwrap_or_default()), false);
+        add(entry.content_text.as_deref().unwrap_or_default(), false);
+        for author in &entry.authors {
+            add(author, true);
+        }
+        let len: usize = terms.iter().filter(|(t, _)| !t.starts_with(AUTHOR_PREFIX)).map(|(_, p)| p.len()).sum();
+        Self {
+            entry: entry.id.clone(),
+            feed: entry.feed.clone(),
+            published_ts: entry.published_ts,
+            len: u32::try_from(len).unwrap_or(u32::MAX),
+            terms,
+        }
+    }
+
+    /// One posting per term.
+    pub fn postings(&self) -> impl Iterator<Item = (&str, Posting)> + '_ {
+        self.terms.iter().map(|(term, positions)| {
+            let posting = Posting {
+                entry: self.entry.clone(),
+                feed: self.feed.clone(),
+                published_ts: self.published_ts,
+                len: self.len,
+                positions: positions.clone(),
+            };
+            (term.as_str(), posting)
+        })
+    }
+}
+
+/// Read side of an index, as ranking needs it.
+pub trait PostingSource {
+    fn corpus(&self) -> Result<CorpusStats, RepoError>;
+    /// Postings of `term` for entries currently indexed, in any order.
+    fn postings(&self, term: &str) -> Result<Vec<Posting>, RepoError>;
+}
+
+/// Index held in memory: built from entries for the mem backend and tests.
+#[derive(Debug, Clone, Default)]
+pub struct MemIndex {
+    stats: CorpusStats,
+    postings: BTreeMap<String, BTreeMap<EntryId, Posting>>,
+}
+
+impl MemIndex {
+    pub fn from_entries<'e>(entries: impl IntoIterator<Item = &'e Entry>) -> Self {
+        let mut index = Self::default();
+        for e in entries {
+            let doc = IndexedDoc::from_entry(e);
+            index.stats.docs += 1;
+            index.stats.total_len += u64::from(doc.len);
+            for (term, posting) in doc.postings() {
+                index.postings.entry(term.to_string()).or_default().insert(doc.entry.clone(), posting);
+            }
+        }
+        index
+    }
+}
+
+impl PostingSource for MemIndex {
+    fn corpus(&self) -> Result<CorpusStats, RepoError> {
+        Ok(self.stats)
+    }
+
+    fn postings(&self, term: &str) -> Result<Vec<Posting>, RepoError> {
+        Ok(self.postings.get(term).map(|m| m.values().cloned().collect()).unwrap_or_default())
+    }
+}
@@ -0,0 +1,26 @@
+/*
+Module: rssify_core::test::archive
+Purpose: Validate archive MIME allowlist matching and blob path layout
+*/
+
+use rssify_core::{blob_path, ArchiveSettings};
+
+#[test]
+fn allowlist_matches_wildcards_ignores_parameters_and_defaults_to_media() {
+    let defaults = ArchiveSettings::default();
+    assert!(defaults.allows("image/png"));
+    assert!(defaults.allows("Audio/MPEG; charset=binary"));
+    assert!(defaults.allows("application/pdf"));
+    assert!(!defaults.allows("text/html"));
+    assert!(!defaults.allows("image"));
+
+    let images = ArchiveSettings { mime_allow: vec!["image/*".into(), "text/plain".into()], ..Default::default() };
+    assert!(images.allows("image/webp"));
+    assert!(images.allows("text/plain"));
+    assert!(!images.allows("video/mp4"));
+}
+
+#[test]
+fn blob_paths_fan_out_by_hash_prefix() {
+    assert_eq!(blob_path("ab12cd"), "blobs/sha256/ab/ab12cd");
+}
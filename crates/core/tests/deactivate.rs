@@ -0,0 +1,113 @@
+/*
+Module: rssify_core::tests::deactivate
+Purpose: Lock the automatic deactivation policy and failure streak folding
+Public API surface: tests only
+Invariants: Pure; time is passed in as unix seconds
+Logging keys used: component, op, feed_id, elapsed_ms, items
+Notes: Test files may exceed header rules; scripts skip /tests/.
+*/
+
+use rssify_core::{
+    ContentBlob, ContentKind, DeactivationPolicy, DeactivationReason, FailureStreak, Feed, FeedId,
+    FetchOutcome,
+};
+
+const DAY: i64 = 86_400;
+
+fn permanent() -> FetchOutcome {
+    FetchOutcome::PermanentFailure { hint: Some("404".into()) }
+}
+
+fn transient() -> FetchOutcome {
+    FetchOutcome::TransientFailure { hint: None }
+}
+
+#[test]
+fn gone_deactivates_at_once() {
+    let policy = DeactivationPolicy::default();
+    let streak = FailureStreak::default().observe(&FetchOutcome::Gone, 100);
+    assert_eq!(
+        policy.evaluate(&FetchOutcome::Gone, &streak, 100),
+        Some(DeactivationReason::Gone)
+    );
+}
+
+#[test]
+fn consecutive_permanent_failures_deactivate_at_threshold() {
+    let policy = DeactivationPolicy { max_permanent_failures: 3, max_days_without_success: 30 };
+    let mut streak = FailureStreak::default();
+    for i in 0..2 {
+        streak = streak.observe(&permanent(), 10 + i);
+        assert_eq!(policy.evaluate(&permanent(), &streak, 10 + i), None);
+    }
+    // A transient failure in between neither resets nor advances the count.
+    streak = streak.observe(&transient(), 20);
+    assert_eq!(streak.permanent, 2);
+    streak = streak.observe(&permanent(), 30);
+    assert_eq!(
+        policy.evaluate(&permanent(), &streak, 30),
+        Some(DeactivationReason::PermanentFailures { count: 3 })
+    );
+}
+
+#[test]
+fn success_resets_the_streak() {
+    let streak = FailureStreak::default()
+        .observe(&permanent(), 1)
+        .observe(&permanent(), 2)
+        .observe(&FetchOutcome::NotModified, 3);
+    assert_eq!(streak, FailureStreak::default());
+
+    let blob = ContentBlob { kind: ContentKind::Xml, bytes: b"<rss/>".to_vec() };
+    let streak = FailureStreak::default()
+        .observe(&transient(), 1)
+        .observe(&FetchOutcome::NewContent { blob, elapsed_ms: 5 }, 2);
+    assert_eq!(streak, FailureStreak::default());
+}
+
+#[test]
+fn days_without_success_count_from_first_failure() {
+    let policy = DeactivationPolicy { max_permanent_failures: 100, max_days_without_success: 7 };
+    let streak = FailureStreak::default().observe(&transient(), 0);
+    assert_eq!(streak.since_ts, Some(0));
+
+    let later = streak.observe(&transient(), 6 * DAY);
+    assert_eq!(later.since_ts, Some(0), "first failure time is kept");
+    assert_eq!(policy.evaluate(&transient(), &later, 6 * DAY), None);
+
+    let later = later.observe(&transient(), 7 * DAY);
+    assert_eq!(
+        policy.evaluate(&transient(), &later, 7 * DAY),
+        Some(DeactivationReason::NoSuccess { days: 7 })
+    );
+}
+
+#[test]
+fn successes_never_deactivate() {
+    let policy = DeactivationPolicy { max_permanent_failures: 0, max_days_without_success: 0 };
+    let streak = FailureStreak { permanent: 9, since_ts: Some(0) };
+    assert_eq!(policy.evaluate(&FetchOutcome::NotModified, &streak, 100 * DAY), None);
+}
+
+#[test]
+fn feed_deactivate_and_reactivate_keep_flags_in_sync() {
+    let mut feed = Feed {
+        id: FeedId::from_url("https://ex.com/feed"),
+        url: "https://ex.com/feed".into(),
+        title: None,
+        site_url: None,
+        etag: None,
+        last_modified: None,
+        active: true,
+        deactivated: None,
+        settings: Default::default(),
+    };
+    feed.deactivate(DeactivationReason::Gone, 42);
+    assert!(!feed.active);
+    assert_eq!(feed.deactivated.as_ref().map(|d| d.ts), Some(42));
+    assert_eq!(feed.deactivated.as_ref().unwrap().reason.to_string(), "gone");
+
+    feed.reactivate();
+    assert!(feed.active);
+    assert!(feed.deactivated.is_none());
+}
@@ -0,0 +1,32 @@
+/*
+Module: rssify_core::test::diff
+Purpose: Validate new/updated/unchanged classification of parsed entries
+*/
+
+mod support;
+
+use rssify_core::{diff_entries, ContentBlob, ContentKind, Entry, EntryDiff};
+
+fn entry(id: &str, title: &str) -> Entry {
+    Entry { title: Some(title.into()), published_ts: Some(1), ..support::entry("f", id) }
+}
+
+#[test]
+fn classifies_by_id_and_content() {
+    let stored = vec![entry("a", "A"), entry("b", "B"), entry("gone", "G")];
+    let parsed = vec![entry("a", "A"), entry("b", "B2"), entry("c", "C")];
+    assert_eq!(diff_entries(&stored, &parsed), EntryDiff { new: 1, updated: 1, unchanged: 1 });
+    assert_eq!(diff_entries(&stored, &[]), EntryDiff::default());
+}
+
+#[test]
+fn fetched_article_content_does_not_count_as_an_update() {
+    let mut enriched = entry("a", "A");
+    enriched.content = Some(ContentBlob { kind: ContentKind::Html, bytes: b"<article></article>".to_vec() });
+    enriched.content_text = Some("Full text".into());
+    assert_eq!(diff_entries(&[enriched.clone()], &[entry("a", "A")]).unchanged, 1);
+    assert_eq!(diff_entries(&[enriched], &[entry("a", "A2")]).updated, 1);
+    let mut traced = entry("b", "B");
+    traced.warc_records = vec!["<urn:uuid:00000000-0000-4000-8000-000000000000>".into()];
+    assert_eq!(diff_entries(&[traced], &[entry("b", "B")]).unchanged, 1);
+}
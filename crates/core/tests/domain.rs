@@ -17,6 +17,8 @@ fn feed_is_constructible_with_expected_fields() {
         etag: None,
         last_modified: None,
         active: true,
+        deactivated: None,
+        settings: Default::default(),
     };
 
     assert_eq!(f.id.as_str(), id.as_str());
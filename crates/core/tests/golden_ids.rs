@@ -7,6 +7,7 @@ Logging keys used: component, op, feed_id, elapsed_ms, items
 Notes: Marked #[ignore] until fixtures are added.
 */
 
+#[allow(unused_imports)] // used once the TODO below lands
 use rssify_core::{EntryId, FeedId};
 
 fn td(p: &str) -> std::path::PathBuf {
@@ -21,5 +22,4 @@ fn golden_entry_id_examples() {
     // and assert the computed EntryId strings match a saved snapshot.
     let _fixture = td("entries_min.csv");
     // TODO: parse fixture, compute ids with EntryId::from_parts, compare to snapshot.
-    assert!(true);
 }
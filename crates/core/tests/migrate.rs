@@ -0,0 +1,58 @@
+/*
+Module: rssify_core::test::migrate
+Purpose: Validate migration steps run in order, record each version, resume and refuse newer stores
+*/
+
+use rssify_core::{run_migrations, Migration, RepoError};
+use std::cell::RefCell;
+
+/// Store double: the log of steps applied to it.
+type Log = RefCell<Vec<&'static str>>;
+
+fn steps() -> Vec<Migration<Log>> {
+    vec![
+        Migration { to: 2, name: "two", apply: |l: &Log| { l.borrow_mut().push("two"); Ok("did two".into()) } },
+        Migration { to: 3, name: "three", apply: |l: &Log| { l.borrow_mut().push("three"); Ok(String::new()) } },
+    ]
+}
+
+#[test]
+fn applies_pending_steps_in_order_and_records_each_version() {
+    let log = Log::default();
+    let mut recorded = Vec::new();
+    let report = run_migrations(&log, 1, 3, &steps(), |_, v| {
+        recorded.push(v);
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!((report.from, report.to), (1, 3));
+    assert_eq!(report.applied.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(), ["two", "three"]);
+    assert_eq!(report.applied[0].note, "did two");
+    assert_eq!(recorded, [2, 3]);
+    assert_eq!(*log.borrow(), ["two", "three"]);
+}
+
+#[test]
+fn current_stores_are_untouched_and_interrupted_runs_resume() {
+    let log = Log::default();
+    let report = run_migrations(&log, 3, 3, &steps(), |_, _| Ok(())).unwrap();
+    assert!(report.applied.is_empty());
+    assert_eq!((report.from, report.to), (3, 3));
+
+    let failing = |_: &Log, v: u32| if v == 3 { Err(RepoError::Backend("disk full".into())) } else { Ok(()) };
+    assert!(run_migrations(&log, 1, 3, &steps(), failing).is_err());
+    log.borrow_mut().clear();
+    let report = run_migrations(&log, 2, 3, &steps(), |_, _| Ok(())).unwrap();
+    assert_eq!(*log.borrow(), ["three"], "resumes after the last recorded version");
+    assert_eq!(report.from, 2);
+}
+
+#[test]
+fn newer_stores_and_gaps_are_refused() {
+    let log = Log::default();
+    let err = run_migrations(&log, 4, 3, &steps(), |_, _| Ok(())).unwrap_err();
+    assert!(err.to_string().contains("newer"), "{err}");
+    assert!(run_migrations(&log, 0, 3, &steps(), |_, _| Ok(())).is_err(), "no step to version 1");
+    assert!(run_migrations(&log, 1, 4, &steps(), |_, _| Ok(())).is_err(), "no step to version 4");
+    assert!(log.borrow().is_empty(), "nothing applied when the chain has a gap");
+}
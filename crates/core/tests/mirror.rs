@@ -0,0 +1,33 @@
+/*
+Module: rssify_core::test::mirror
+Purpose: Validate mirror health scoring, ordering and folding of all-mirror failures
+*/
+
+use rssify_core::{combine_failures, FetchOutcome, MirrorHealth};
+
+fn urls() -> Vec<String> {
+    vec!["https://a.test/rss".to_string(), "https://b.test/rss".to_string()]
+}
+
+#[test]
+fn order_prefers_healthy_fast_urls_and_keeps_configured_order_on_ties() {
+    let mut h = MirrorHealth::default();
+    assert_eq!(h.order(&urls()), urls(), "unknown urls keep configured order");
+
+    h.record("https://a.test/rss", false, 0, 10);
+    assert_eq!(h.order(&urls())[0], "https://b.test/rss");
+
+    h.record("https://a.test/rss", true, 50, 20);
+    h.record("https://b.test/rss", true, 3_000, 20);
+    assert_eq!(h.urls["https://a.test/rss"].consecutive_failures, 0);
+    assert_eq!(h.order(&urls())[0], "https://a.test/rss", "slow mirror ranks below recovered primary");
+}
+
+#[test]
+fn combined_failure_is_gone_only_when_every_url_is_gone() {
+    let transient = FetchOutcome::TransientFailure { hint: Some("http 503".into()) };
+    assert_eq!(combine_failures(&[FetchOutcome::Gone, FetchOutcome::Gone]), FetchOutcome::Gone);
+    assert_eq!(combine_failures(&[FetchOutcome::Gone, transient.clone()]), transient);
+    let permanent = FetchOutcome::PermanentFailure { hint: Some("http 404".into()) };
+    assert_eq!(combine_failures(&[FetchOutcome::Gone, permanent.clone()]), permanent);
+}
@@ -0,0 +1,75 @@
+/*
+Module: rssify_core::test::query
+Purpose: Validate entry query filters, ordering and cursor pagination as scanning backends use them
+*/
+
+mod support;
+
+use rssify_core::{Cursor, Entry, EntryOrder, EntryPage, EntryQuery, FeedId, RepoError};
+
+fn entry(feed: &str, id: &str, published_ts: Option<i64>, title: Option<&str>) -> Entry {
+    Entry { published_ts, title: title.map(str::to_string), ..support::entry(feed, id) }
+}
+
+/// Two feeds; a1/b1 share a timestamp so ties fall back to the id.
+fn corpus() -> Vec<Entry> {
+    vec![
+        entry("a", "a1", Some(30), Some("Rust 2024 released")),
+        entry("a", "a2", Some(10), Some("Weekly notes")),
+        entry("a", "a3", None, Some("Undated rust post")),
+        entry("b", "b1", Some(30), None),
+        entry("b", "b2", Some(20), Some("RUST tips")),
+    ]
+}
+
+fn ids(page: &EntryPage) -> Vec<&str> {
+    page.entries.iter().map(|e| e.id.as_str()).collect()
+}
+
+#[test]
+fn pages_walk_every_entry_once_in_order_with_undated_last() {
+    let mut q = EntryQuery { limit: 2, ..EntryQuery::default() };
+    let mut seen = Vec::new();
+    loop {
+        let page = q.page(corpus()).unwrap();
+        seen.extend(ids(&page).into_iter().map(str::to_string));
+        match page.next {
+            Some(c) => q.cursor = Some(Cursor::decode(&c.encode()).unwrap()),
+            None => break,
+        }
+    }
+    assert_eq!(seen, ["b1", "a1", "b2", "a2", "a3"]);
+
+    let oldest = EntryQuery { order: EntryOrder::OldestFirst, ..EntryQuery::default() };
+    assert_eq!(ids(&oldest.page(corpus()).unwrap()), ["a3", "a2", "b2", "a1", "b1"]);
+}
+
+#[test]
+fn filters_combine_feed_set_range_and_case_insensitive_title() {
+    let q = EntryQuery {
+        feeds: vec![FeedId::new("a"), FeedId::new("b")],
+        published_from: Some(20),
+        published_until: Some(31),
+        title_contains: Some("rust".into()),
+        ..EntryQuery::default()
+    };
+    let page = q.page(corpus()).unwrap();
+    assert_eq!(ids(&page), ["a1", "b2"], "a3 is undated, b1 has no title");
+    assert!(page.next.is_none());
+
+    let only_b = EntryQuery { feeds: vec![FeedId::new("b")], published_until: Some(30), ..EntryQuery::default() };
+    assert_eq!(ids(&only_b.page(corpus()).unwrap()), ["b2"], "the upper bound is exclusive");
+}
+
+#[test]
+fn bad_limits_and_cursors_are_invalid_queries() {
+    let zero = EntryQuery { limit: 0, ..EntryQuery::default() };
+    assert!(matches!(zero.page(corpus()), Err(RepoError::InvalidQuery(_))));
+    assert!(matches!(Cursor::decode("zz"), Err(RepoError::InvalidQuery(_))));
+    assert!(matches!(Cursor::decode("6e31"), Err(RepoError::InvalidQuery(_))), "no id separator");
+
+    let first = EntryQuery { limit: 1, ..EntryQuery::default() }.page(corpus()).unwrap();
+    let other_order =
+        EntryQuery { order: EntryOrder::OldestFirst, cursor: first.next, ..EntryQuery::default() };
+    assert!(matches!(other_order.page(corpus()), Err(RepoError::InvalidQuery(_))));
+}
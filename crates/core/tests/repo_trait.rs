@@ -4,7 +4,9 @@ Purpose: Compile-time and runtime sanity checks for core repo traits.
 Notes: In-memory dummy backend; no I/O; validates trait signatures.
 */
 
-use rssify_core::{Entry, EntryId, EntryRepo, Feed, FeedId, FeedRepo, RepoError, Tx};
+use rssify_core::{
+    Entry, EntryId, EntryPage, EntryQuery, EntryRepo, Feed, FeedId, FeedRepo, LastBlob, RepoError, RetentionPolicy, Tx,
+};
 
 use std::collections::BTreeMap;
 
@@ -49,6 +51,31 @@ impl FeedRepo for MemFeeds {
     ) -> Result<Vec<Feed>, RepoError> {
         Ok(self.feeds.values().cloned().collect())
     }
+
+    fn last_blob<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        _id: &FeedId,
+    ) -> Result<Option<LastBlob>, RepoError> {
+        Ok(None)
+    }
+
+    fn put_last_blob<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        _id: &FeedId,
+        _blob: &LastBlob,
+    ) -> Result<(), RepoError> {
+        Ok(())
+    }
+
+    fn delete<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        _id: &FeedId,
+    ) -> Result<(), RepoError> {
+        Err(RepoError::NotFound)
+    }
 }
 
 #[derive(Default)]
@@ -79,6 +106,32 @@ impl EntryRepo for MemEntries {
     ) -> Result<Vec<Entry>, RepoError> {
         Ok(Vec::new())
     }
+
+    fn count_by_feed<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, _feed: &FeedId) -> Result<usize, RepoError> {
+        Ok(0)
+    }
+
+    fn query<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, query: &EntryQuery) -> Result<EntryPage, RepoError> {
+        query.page(Vec::new())
+    }
+
+    fn delete<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        _id: &EntryId,
+    ) -> Result<(), RepoError> {
+        Err(RepoError::NotFound)
+    }
+
+    fn prune<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        _feed: &FeedId,
+        _policy: &RetentionPolicy,
+        _now: i64,
+    ) -> Result<Vec<EntryId>, RepoError> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -92,7 +145,7 @@ fn repo_traits_are_object_safe() {
     fn takes_feed_repo<R: FeedRepo>(_r: &R) {}
     fn takes_entry_repo<R: EntryRepo>(_r: &R) {}
     let feeds = MemFeeds::default();
-    let entries = MemEntries::default();
+    let entries = MemEntries;
     takes_feed_repo(&feeds);
     takes_entry_repo(&entries);
 }
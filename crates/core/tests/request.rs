@@ -0,0 +1,77 @@
+/*
+Module: rssify_core::test::request
+Purpose: Validate per-feed request settings: serde shape, secret references and header resolution
+*/
+
+use rssify_core::{AuthSetting, FeedSettings, HeaderValue, RequestSettings, SecretRef};
+
+fn settings() -> RequestSettings {
+    RequestSettings {
+        headers: vec![
+            ("Accept".into(), HeaderValue::Plain("application/rss+xml".into())),
+            ("X-Api-Key".into(), HeaderValue::Ref(SecretRef::Env("FEED_KEY".into()))),
+        ],
+        auth: Some(AuthSetting::Basic {
+            username: "user".into(),
+            password: SecretRef::Secret("feed_pw".into()),
+        }),
+        user_agent: Some("rssify/0.1".into()),
+        cookie: Some(SecretRef::Secret("session".into())),
+    }
+}
+
+fn lookup(r: &SecretRef) -> Option<String> {
+    match r {
+        SecretRef::Env(n) if n == "FEED_KEY" => Some("k-123".into()),
+        SecretRef::Secret(k) if k == "feed_pw" => Some("pass".into()),
+        SecretRef::Secret(k) if k == "session" => Some("sid=abc".into()),
+        _ => None,
+    }
+}
+
+#[test]
+fn resolves_headers_auth_user_agent_and_cookie() {
+    let headers = settings().resolve(lookup).expect("all secrets present");
+    let pairs: Vec<(&str, &str)> = headers.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    assert_eq!(
+        pairs,
+        vec![
+            ("Accept", "application/rss+xml"),
+            ("X-Api-Key", "k-123"),
+            ("User-Agent", "rssify/0.1"),
+            ("Authorization", "Basic dXNlcjpwYXNz"),
+            ("Cookie", "sid=abc"),
+        ]
+    );
+
+    let bearer = RequestSettings {
+        auth: Some(AuthSetting::Bearer { token: SecretRef::Env("FEED_KEY".into()) }),
+        ..RequestSettings::default()
+    };
+    assert_eq!(
+        bearer.resolve(lookup).unwrap(),
+        vec![("Authorization".to_string(), "Bearer k-123".to_string())]
+    );
+}
+
+#[test]
+fn missing_secret_reports_the_reference_only() {
+    let s = RequestSettings {
+        cookie: Some(SecretRef::Env("NOPE".into())),
+        ..RequestSettings::default()
+    };
+    let err = s.resolve(lookup).unwrap_err();
+    assert_eq!(err.to_string(), "env:NOPE");
+}
+
+#[test]
+fn serialized_settings_hold_references_not_values() {
+    let fs = FeedSettings { request: settings(), ..FeedSettings::default() };
+    let json = serde_json::to_string(&fs).unwrap();
+    assert!(json.contains(r#"{"env":"FEED_KEY"}"#), "{json}");
+    assert!(json.contains(r#""auth":{"type":"basic","username":"user","password":{"secret":"feed_pw"}}"#), "{json}");
+    assert!(!json.contains("pass\""));
+    let back: FeedSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, fs);
+    assert_eq!(serde_json::to_string(&FeedSettings::default()).unwrap(), "{}");
+}
@@ -0,0 +1,54 @@
+/*
+Module: rssify_core::test::retention
+Purpose: Validate which entries a retention policy drops: keep-last, max-age, their union, starred and undated entries
+*/
+
+mod support;
+
+use rssify_core::{Entry, EntryId, RetentionPolicy};
+
+const DAY: i64 = 86_400;
+const NOW: i64 = 100 * DAY;
+
+fn entry(id: &str, published_ts: Option<i64>) -> Entry {
+    Entry { published_ts, ..support::entry("f", id) }
+}
+
+/// e1 is the oldest, e5 the newest (one a day up to yesterday), plus one undated entry.
+fn feed_entries() -> Vec<Entry> {
+    let mut v: Vec<Entry> = (1..=5).map(|i| entry(&format!("e{i}"), Some(NOW - (6 - i) * DAY))).collect();
+    v.push(entry("undated", None));
+    v
+}
+
+fn ids(v: Vec<EntryId>) -> Vec<String> {
+    v.into_iter().map(|id| id.as_str().to_string()).collect()
+}
+
+#[test]
+fn an_empty_policy_keeps_everything() {
+    let policy = RetentionPolicy::default();
+    assert!(policy.is_unbounded());
+    assert!(policy.expired(&feed_entries(), NOW).is_empty());
+}
+
+#[test]
+fn keep_last_and_max_age_each_keep_entries_and_combine_as_a_union() {
+    let last_two = RetentionPolicy { keep_last: Some(2), max_age_days: None };
+    assert_eq!(ids(last_two.expired(&feed_entries(), NOW)), ["e3", "e2", "e1", "undated"]);
+
+    let three_days = RetentionPolicy { keep_last: None, max_age_days: Some(3) };
+    assert_eq!(ids(three_days.expired(&feed_entries(), NOW)), ["e2", "e1", "undated"]);
+
+    let either = RetentionPolicy { keep_last: Some(4), max_age_days: Some(1) };
+    assert_eq!(ids(either.expired(&feed_entries(), NOW)), ["e1", "undated"]);
+}
+
+#[test]
+fn starred_entries_are_never_expired_and_updated_ts_dates_undated_posts() {
+    let mut entries = feed_entries();
+    entries[0].starred = true;
+    entries[5].updated_ts = Some(NOW);
+    let policy = RetentionPolicy { keep_last: Some(1), max_age_days: None };
+    assert_eq!(ids(policy.expired(&entries, NOW)), ["e5", "e4", "e3", "e2"]);
+}
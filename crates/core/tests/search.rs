@@ -0,0 +1,72 @@
+/*
+Module: rssify_core::test::search
+Purpose: Validate the search query language, what gets indexed and BM25 ranking over a MemIndex
+*/
+
+mod support;
+
+use rssify_core::{rank, tokenize, Entry, FeedId, IndexedDoc, MemIndex, RepoError, SearchQuery};
+
+fn entry(feed: &str, id: &str, published_ts: i64, title: &str, summary: &str) -> Entry {
+    Entry { published_ts: Some(published_ts), title: Some(title.into()), summary: Some(summary.into()), ..support::entry(feed, id) }
+}
+
+fn corpus() -> Vec<Entry> {
+    vec![
+        entry("a", "a1", 100, "Rust async runtimes", "<p>Tokio &amp; smol compared</p>"),
+        entry("a", "a2", 200, "Weekly notes", "rust rust rust, and a runtime for async code"),
+        entry("b", "b1", 300, "Async Rust in practice", "A long summary about many other things entirely"),
+        Entry {
+            authors: vec!["Jane Q. Doe".into()],
+            content_text: Some("Readable text mentions runtimes".into()),
+            ..entry("b", "b2", 400, "Gardening", "Tomatoes")
+        },
+    ]
+}
+
+fn ids(input: &str) -> Vec<String> {
+    let index = MemIndex::from_entries(&corpus());
+    let hits = rank(&index, &SearchQuery::parse(input).unwrap(), 10).unwrap();
+    hits.into_iter().map(|h| h.entry.as_str().to_string()).collect()
+}
+
+#[test]
+fn parses_words_phrases_and_field_filters() {
+    let q = SearchQuery::parse(r#"Rust "async runtimes" rust-lang feed:a author:"Jane Doe" after:2024-01-02"#).unwrap();
+    assert_eq!(q.words, ["rust"]);
+    assert_eq!(q.phrases, [vec!["async", "runtimes"], vec!["rust", "lang"]]);
+    assert_eq!(q.feeds, [FeedId::new("a")]);
+    assert_eq!(q.authors, [vec!["jane", "doe"]]);
+    assert_eq!(q.after, Some(1_704_153_600));
+
+    let q = SearchQuery::parse("x feed:https://ex.com/rss after:1700000000").unwrap();
+    assert_eq!(q.feeds, [FeedId::new("https://ex.com/rss"), FeedId::from_url("https://ex.com/rss")]);
+    assert_eq!(q.after, Some(1_700_000_000));
+
+    assert!(matches!(SearchQuery::parse("feed:a author:x"), Err(RepoError::InvalidQuery(_))));
+    assert!(matches!(SearchQuery::parse("x after:soon"), Err(RepoError::InvalidQuery(_))));
+}
+
+#[test]
+fn indexes_title_summary_text_and_authors_without_markup() {
+    assert_eq!(tokenize("Héllo, WORLD-wide_web 2024!"), ["héllo", "world", "wide", "web", "2024"]);
+    let doc = IndexedDoc::from_entry(&corpus()[3]);
+    assert!(doc.terms.contains_key("runtimes") && doc.terms.contains_key("author:doe"));
+    assert_eq!(doc.terms["doe"], doc.terms["author:doe"]);
+    assert_eq!(doc.len, 9, "author: terms do not add to the length");
+    let first = IndexedDoc::from_entry(&corpus()[0]);
+    assert!(!first.terms.contains_key("p") && !first.terms.contains_key("amp"));
+}
+
+#[test]
+fn every_word_must_match_and_bm25_orders_the_matches() {
+    assert_eq!(ids("rust async"), ["a2", "a1", "b1"], "tf favors a2, then the shorter of a1 and b1");
+    assert_eq!(ids("runtimes"), ["a1", "b2"]);
+    assert_eq!(ids(r#""async rust""#), ["b1"], "a phrase needs consecutive positions");
+    assert_eq!(ids(r#""notes rust""#), Vec::<String>::new(), "phrases never span fields");
+    assert_eq!(ids("runtimes author:jane"), ["b2"]);
+    assert_eq!(ids(r#"runtimes author:"doe jane""#), Vec::<String>::new());
+    assert_eq!(ids("rust feed:b"), ["b1"]);
+    assert_eq!(ids("rust after:150"), ["a2", "b1"]);
+    assert_eq!(ids("absent"), Vec::<String>::new());
+}
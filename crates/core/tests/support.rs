@@ -1,14 +1,36 @@
 /*
 Module: rssify_core::tests::support
-Purpose: Tiny helpers for reading fixtures (no extra deps)
+Purpose: Tiny helpers for reading fixtures and building domain values (no extra deps)
 Public API surface: tests only
 Invariants: Only used within tests
 Logging keys used: component, op, feed_id, elapsed_ms, items
 Notes: Keep tiny and dependency-free.
 */
 
+#![allow(dead_code)]
+
+use rssify_core::{Entry, EntryId, FeedId};
 use std::fs;
 
 pub fn read(path: &std::path::Path) -> String {
     fs::read_to_string(path).expect("read fixture")
 }
+
+/// A bare entry of `feed`; tests override what they exercise with struct update syntax.
+pub fn entry(feed: &str, id: &str) -> Entry {
+    Entry {
+        id: EntryId::new(id),
+        feed: FeedId::new(feed),
+        url: None,
+        title: None,
+        published_ts: None,
+        updated_ts: None,
+        summary: None,
+        content: None,
+        content_text: None,
+        enclosures: Vec::new(),
+        warc_records: Vec::new(),
+        starred: false,
+        authors: Vec::new(),
+    }
+}
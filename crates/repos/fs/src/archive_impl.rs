@@ -0,0 +1,58 @@
+/*
+File: crates/repos/fs/src/archive_impl.rs
+Purpose: ArchiveRepo impl for FsRepo.
+Inputs: rssify_core::{ArchiveRepo, ArchivedEntry, EntryId, FeedId, RepoError}; util read/write helpers.
+Outputs: JSON files under archive/by_id and archive/by_feed/<feed>.
+Side effects: Filesystem I/O.
+*/
+
+use crate::repo::FsRepo;
+use crate::repo::entry_file_name;
+use crate::util::read_json;
+use rssify_core::{ArchiveRepo, ArchivedEntry, EntryId, FeedId, RepoError};
+
+impl ArchiveRepo for FsRepo {
+    type Tx<'a> = crate::tx::FsTx where Self: 'a;
+
+    fn put_archive<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, archived: &ArchivedEntry) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        let by_feed = self
+            .archive_by_feed_dir(&archived.feed)
+            .join(entry_file_name(&archived.entry));
+        self.put_json(tx, &self.archive_by_id_path(&archived.entry), archived)?;
+        self.put_json(tx, &by_feed, archived)
+    }
+
+    fn archive<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, entry: &EntryId) -> Result<Option<ArchivedEntry>, RepoError> {
+        self.check_version(false)?;
+        let p = self.archive_by_id_path(entry);
+        if !p.is_file() {
+            return Ok(None);
+        }
+        read_json(&p).map(Some)
+    }
+
+    fn archives_by_feed<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<Vec<ArchivedEntry>, RepoError> {
+        self.check_version(false)?;
+        let mut out = Vec::new();
+        let rd = match std::fs::read_dir(self.archive_by_feed_dir(feed)) {
+            Ok(rd) => rd,
+            Err(_) => return Ok(out),
+        };
+        for entry in rd {
+            let entry = entry.map_err(|e| RepoError::Backend(e.to_string()))?;
+            let p = entry.path();
+            if p.extension().and_then(|s| s.to_str()) == Some("json")
+                && let Ok(a) = read_json::<ArchivedEntry>(&p)
+            {
+                out.push(a);
+            }
+        }
+        out.sort_by(|a, b| a.entry.as_str().cmp(b.entry.as_str()));
+        Ok(out)
+    }
+}
@@ -0,0 +1,84 @@
+/*
+File: crates/repos/fs/src/blob_impl.rs
+Purpose: BlobRepo impl for FsRepo (sha256 content-addressed files).
+Inputs: rssify_core::{BlobRepo, RepoError}; sha2 for hashing.
+Outputs: blobs/sha256/<aa>/<hex> raw files.
+Side effects: Filesystem I/O.
+Invariants: A blob file's name is the sha256 of its bytes; existing blobs are never rewritten.
+            gc_blobs keeps every blob an entry file or archive record references and refuses to
+            run if any of them is unreadable; it only removes files named like a sha256.
+*/
+
+use crate::content::{entry_files, read_stored, StoredContent};
+use crate::repo::FsRepo;
+use crate::util::{children, json_files, read_json};
+use rssify_core::{ArchivedEntry, BlobRepo, RepoError};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Lowercase hex sha256 of `bytes`.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().fold(String::with_capacity(64), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+impl BlobRepo for FsRepo {
+    type Tx<'a> = crate::tx::FsTx where Self: 'a;
+
+    fn put_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, bytes: &[u8]) -> Result<String, RepoError> {
+        self.check_version(true)?;
+        let hash = sha256_hex(bytes);
+        let path = self.blob_file(&hash);
+        if !path.is_file() {
+            self.put_bytes(tx, &path, bytes)?;
+        }
+        Ok(hash)
+    }
+
+    fn get_blob<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, sha256: &str) -> Result<Option<Vec<u8>>, RepoError> {
+        self.check_version(false)?;
+        if sha256.len() != 64 || !sha256.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Ok(None);
+        }
+        match std::fs::read(self.blob_file(sha256)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RepoError::Backend(e.to_string())),
+        }
+    }
+
+    fn gc_blobs<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError> {
+        self.check_version(false)?;
+        let unreadable = |p: &Path, e: RepoError| RepoError::Backend(format!("cannot collect blobs, {} is unreadable: {e}", p.display()));
+        let mut live = BTreeSet::new();
+        for path in entry_files(&self.root)? {
+            if let (_, Some(StoredContent::Blob { sha256, .. })) = read_stored(&path).map_err(|e| unreadable(&path, e))? {
+                live.insert(sha256);
+            }
+        }
+        let archive = self.root.join("archive");
+        let mut records = json_files(&archive.join("by_id"))?;
+        for dir in children(&archive.join("by_feed"))? {
+            records.extend(json_files(&dir)?);
+        }
+        for path in records {
+            let archived: ArchivedEntry = read_json(&path).map_err(|e| unreadable(&path, e))?;
+            live.extend(archived.attachments.into_iter().map(|a| a.sha256));
+        }
+        let mut dead = Vec::new();
+        for shard in children(&self.root.join("blobs").join("sha256"))? {
+            for blob in children(&shard)? {
+                let name = blob.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+                if name.len() == 64 && name.bytes().all(|b| b.is_ascii_hexdigit()) && !live.contains(name) {
+                    dead.push(blob);
+                }
+            }
+        }
+        self.in_tx(tx, |tx| dead.iter().try_for_each(|p| tx.stage_remove(p)))?;
+        Ok(dead.len())
+    }
+}
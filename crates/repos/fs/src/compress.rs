@@ -0,0 +1,140 @@
+/*
+File: crates/repos/fs/src/compress.rs
+Purpose: Optional zstd compression of JSON records, and on-disk versus logical size of a repo.
+Inputs: Record bytes as serialized by the repo; file contents under the repo root.
+Outputs: Record bytes as stored (compressed or not), plain bytes back; DiskUsage totals.
+Side effects: disk_usage reads the tree (frame headers only for compressed files).
+Invariants:
+ - A compressed record is one zstd frame and keeps its plain file name; reads tell the two apart
+   by the zstd magic number, which no JSON text starts with. Plain and compressed records mix
+   freely, so switching compression on or off needs no migration.
+ - Only JSON records are compressed: blobs, last_blob.bin, index posting files and run logs
+   stay plain (blobs are served by path, the others are appended to).
+*/
+
+use crate::util::write_atomic_bytes;
+use rssify_core::RepoError;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+/// First bytes of every zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+/// Longest zstd frame header; enough to learn a frame's content size.
+const FRAME_HEADER_MAX: usize = 18;
+
+/// How new records are written; reads accept either form whatever the setting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+}
+
+impl FromStr for Compression {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "zstd" => Ok(Self::Zstd),
+            other => Err(format!("unknown compression {other:?} (expected zstd or none)")),
+        }
+    }
+}
+
+impl Display for Compression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "none",
+            Self::Zstd => "zstd",
+        })
+    }
+}
+
+fn io(e: std::io::Error) -> RepoError {
+    RepoError::Backend(e.to_string())
+}
+
+/// `bytes` as a record is stored under `compression`.
+pub(crate) fn encode(compression: Compression, bytes: &[u8]) -> Result<Vec<u8>, RepoError> {
+    match compression {
+        Compression::None => Ok(bytes.to_vec()),
+        Compression::Zstd => zstd::bulk::compress(bytes, zstd::DEFAULT_COMPRESSION_LEVEL).map_err(io),
+    }
+}
+
+/// Write `value` as a JSON record at `path` (temp-then-rename), stored per `compression`.
+pub(crate) fn write_record<T: ?Sized + Serialize>(path: &Path, compression: Compression, value: &T) -> Result<(), RepoError> {
+    let json = serde_json::to_vec_pretty(value).map_err(|e| RepoError::Backend(e.to_string()))?;
+    write_atomic_bytes(path, &encode(compression, &json)?)
+}
+
+/// The plain bytes of a stored record; plain records come back unchanged.
+pub(crate) fn decode(bytes: Vec<u8>) -> Result<Vec<u8>, RepoError> {
+    if bytes.starts_with(&ZSTD_MAGIC) {
+        zstd::decode_all(bytes.as_slice()).map_err(|e| RepoError::Backend(format!("corrupt zstd record: {e}")))
+    } else {
+        Ok(bytes)
+    }
+}
+
+/// The plain bytes of the record at `path`.
+pub fn read_record(path: &Path) -> Result<Vec<u8>, RepoError> {
+    decode(fs::read(path).map_err(io)?)
+}
+
+/// Sizes of every file under a repo root.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiskUsage {
+    pub files: u64,
+    /// Bytes the files take as stored.
+    pub on_disk_bytes: u64,
+    /// Bytes they would take uncompressed.
+    pub logical_bytes: u64,
+}
+
+/// Walk `root` and total its file sizes; a missing root is empty.
+pub fn disk_usage(root: &Path) -> Result<DiskUsage, RepoError> {
+    let mut usage = DiskUsage::default();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(rd) => rd,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(io(e)),
+        };
+        for entry in entries {
+            let entry = entry.map_err(io)?;
+            let kind = entry.file_type().map_err(io)?;
+            if kind.is_dir() {
+                dirs.push(entry.path());
+            } else if kind.is_file() {
+                let on_disk = entry.metadata().map_err(io)?.len();
+                usage.files += 1;
+                usage.on_disk_bytes += on_disk;
+                usage.logical_bytes += logical_len(&entry.path(), on_disk)?;
+            }
+        }
+    }
+    Ok(usage)
+}
+
+/// Uncompressed length of the file at `path`, `on_disk` bytes long. Only `.json` records can be
+/// compressed, so other files count as stored even if their bytes happen to look like zstd.
+fn logical_len(path: &Path, on_disk: u64) -> Result<u64, RepoError> {
+    if path.extension().is_none_or(|x| x != "json") {
+        return Ok(on_disk);
+    }
+    let mut head = Vec::with_capacity(FRAME_HEADER_MAX);
+    fs::File::open(path).map_err(io)?.take(FRAME_HEADER_MAX as u64).read_to_end(&mut head).map_err(io)?;
+    if !head.starts_with(&ZSTD_MAGIC) {
+        return Ok(on_disk);
+    }
+    match zstd::zstd_safe::get_frame_content_size(&head) {
+        Ok(Some(len)) => Ok(len),
+        _ => Ok(read_record(path)?.len() as u64),
+    }
+}
@@ -0,0 +1,95 @@
+/*
+File: crates/repos/fs/src/content.rs
+Purpose: Entry files with content held by reference: the bytes go to the blob store once and the
+         entry JSON names their sha256.
+Inputs: rssify_core::{Entry, ContentBlob}; blob files (blob_impl.rs).
+Outputs: Entry JSON whose "content" is {"kind": .., "sha256": ..}; blobs/sha256/<aa>/<hex>.
+Side effects: Filesystem I/O.
+Invariants:
+ - Reads also accept inline content ({"kind", "bytes"}), as written before repo version 4.
+ - A content blob is staged in the same tx as the entry referencing it, so no committed entry
+   points at a missing blob; one that does anyway fails to read rather than losing its content.
+*/
+
+use crate::blob_impl::sha256_hex;
+use crate::repo::FsRepo;
+use crate::tx::FsTx;
+use crate::util::{children, json_files, read_json};
+use rssify_core::{ContentBlob, ContentKind, Entry, RepoError};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How an entry file holds its content.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum StoredContent {
+    Blob { kind: ContentKind, sha256: String },
+    Inline(ContentBlob),
+}
+
+fn ser(e: serde_json::Error) -> RepoError {
+    RepoError::Ser(e.to_string())
+}
+
+/// The entry file at `path`: the record with `content` unset, and how its content is held.
+pub(crate) fn read_stored(path: &Path) -> Result<(Entry, Option<StoredContent>), RepoError> {
+    let mut value: serde_json::Value = read_json(path)?;
+    let content = value.as_object_mut().and_then(|o| o.remove("content")).filter(|c| !c.is_null());
+    let entry: Entry = serde_json::from_value(value).map_err(ser)?;
+    let content = content.map(serde_json::from_value).transpose().map_err(ser)?;
+    Ok((entry, content))
+}
+
+/// A content blob to store: its sha256 and bytes.
+type PendingBlob<'e> = (String, &'e [u8]);
+
+/// `entry` as its file stores it, plus the content blob it references.
+pub(crate) fn to_stored(entry: &Entry) -> Result<(serde_json::Value, Option<PendingBlob<'_>>), RepoError> {
+    let mut value = serde_json::to_value(Entry { content: None, ..entry.clone() }).map_err(ser)?;
+    let Some(content) = &entry.content else { return Ok((value, None)) };
+    let sha256 = sha256_hex(&content.bytes);
+    value["content"] = serde_json::to_value(StoredContent::Blob { kind: content.kind, sha256: sha256.clone() }).map_err(ser)?;
+    Ok((value, Some((sha256, &content.bytes))))
+}
+
+/// Every entry file under `root`, feeds and files in name order.
+pub(crate) fn entry_files(root: &Path) -> Result<Vec<PathBuf>, RepoError> {
+    let mut files = Vec::new();
+    for feed in children(&root.join("feeds"))? {
+        files.extend(json_files(&feed.join("entries"))?);
+    }
+    Ok(files)
+}
+
+impl FsRepo {
+    /// Stage `entry`'s file, and its content blob unless the store already holds it.
+    pub(crate) fn write_entry(&self, tx: &FsTx, entry: &Entry) -> Result<(), RepoError> {
+        let (value, blob) = to_stored(entry)?;
+        if let Some((sha256, bytes)) = blob
+            && !self.blob_file(&sha256).is_file()
+        {
+            tx.stage_write(&self.blob_file(&sha256), bytes)?;
+        }
+        self.put_json(Some(tx), &self.entry_path(&entry.feed, &entry.id), &value)
+    }
+
+    /// `entry` (as `read_stored` returned it) with its content filled back in.
+    pub(crate) fn with_content(&self, mut entry: Entry, content: Option<StoredContent>) -> Result<Entry, RepoError> {
+        entry.content = match content {
+            None => None,
+            Some(StoredContent::Inline(blob)) => Some(blob),
+            Some(StoredContent::Blob { kind, sha256 }) => {
+                let bytes = std::fs::read(self.blob_file(&sha256)).map_err(|e| {
+                    RepoError::Backend(format!("content blob {sha256} of entry {}: {e}", entry.id.as_str()))
+                })?;
+                Some(ContentBlob { kind, bytes })
+            }
+        };
+        Ok(entry)
+    }
+
+    pub(crate) fn read_entry(&self, path: &Path) -> Result<Entry, RepoError> {
+        let (entry, content) = read_stored(path)?;
+        self.with_content(entry, content)
+    }
+}
@@ -2,50 +2,118 @@
 File: crates/repos/fs/src/entry_impl.rs
 Purpose: EntryRepo impl for FsRepo.
 Inputs: rssify_core::{Entry, EntryId, EntryRepo, FeedId, RepoError}; util read/write helpers.
-Outputs: JSON files under by_id and by_feed.
+Outputs: One JSON file per entry under its feed: feeds/<feed>/entries/<entry>.json, content
+         by reference to the blob store (content.rs).
 Side effects: Filesystem I/O.
+Invariants: An entry id belongs to one feed, as in the other backends: get and delete find it
+            through the index doc record (index.rs), falling back to a scan of
+            every feed's entries/ directory when the record is missing (index removed or damaged), and
+            upserting an id under another feed moves the entry there.
+            upsert writes the entry and its search postings in one tx; delete and prune remove
+            postings and archive records with their entries, in one tx.
+            query scans the entry files of the requested feeds (all feeds when none are named).
 */
 
-use crate::repo::FsRepo;
-use crate::util::{read_json, write_atomic_json};
-use rssify_core::{Entry, EntryId, EntryRepo, FeedId, RepoError};
+use crate::repo::{entry_file_name, FsRepo};
+use crate::tx::FsTx;
+use crate::content::read_stored;
+use crate::layout::decode_component;
+use rssify_core::{Entry, EntryId, EntryPage, EntryQuery, EntryRepo, FeedId, RepoError, RetentionPolicy};
+use std::path::{Path, PathBuf};
 
-impl EntryRepo for FsRepo {
-    type Tx<'a> = crate::tx::FsTx where Self: 'a;
+impl FsRepo {
+    /// Feed holding the stored entry `id`.
+    fn entry_feed(&self, tx: Option<&FsTx>, id: &EntryId) -> Result<FeedId, RepoError> {
+        self.stored_feed(tx, id)?.ok_or(RepoError::NotFound)
+    }
 
-    fn get<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<Entry, RepoError> {
-        let p = self.entry_by_id_path(id);
-        read_json::<Entry>(&p)
+    /// Feed holding entry `id`: its doc record, else whichever feed directory holds the file
+    /// (through `tx` when given); the index is a cache here, not the source of truth.
+    fn stored_feed(&self, tx: Option<&FsTx>, id: &EntryId) -> Result<Option<FeedId>, RepoError> {
+        if let Some(feed) = self.indexed_feed(tx, id)? {
+            return Ok(Some(feed));
+        }
+        let Ok(rd) = std::fs::read_dir(self.feeds_dir()) else {
+            return Ok(None);
+        };
+        let name = entry_file_name(id);
+        for dir in rd {
+            let dir = dir.map_err(|e| RepoError::Backend(e.to_string()))?;
+            let Some(feed) = dir.file_name().to_str().and_then(decode_component) else {
+                continue;
+            };
+            let path = dir.path().join("entries").join(&name);
+            let staged = match tx {
+                Some(tx) => tx.staged(&path)?,
+                None => None,
+            };
+            if matches!(staged, Some(Some(_))) || (staged.is_none() && path.is_file()) {
+                return Ok(Some(FeedId::new(feed)));
+            }
+        }
+        Ok(None)
     }
 
-    fn upsert<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, entry: &Entry) -> Result<(), RepoError> {
-        let by_id = self.entry_by_id_path(&entry.id);
-        let by_feed = self.entry_by_feed_path(&entry.feed, &entry.id);
-        write_atomic_json(&by_id, entry)?;
-        write_atomic_json(&by_feed, entry)?;
-        Ok(())
+    /// Stage removal of an entry file, its search postings and both archive records of the entry.
+    fn stage_entry_removal(&self, tx: &FsTx, feed: &FeedId, id: &EntryId) -> Result<(), RepoError> {
+        tx.stage_remove(&self.entry_path(feed, id))?;
+        self.unindex_entry(tx, id)?;
+        tx.stage_remove(&self.archive_by_id_path(id))?;
+        tx.stage_remove(&self.archive_by_feed_dir(feed).join(entry_file_name(id)))
     }
 
-    fn list_by_feed<'a>(
-        &'a self,
-        _tx: Option<&'a Self::Tx<'a>>,
-        feed: &FeedId,
-    ) -> Result<Vec<Entry>, RepoError> {
-        let dir = self.entries_by_feed_dir(feed);
+    /// Readable entry files in `dir` that pass `keep` (judged before content is loaded); a
+    /// missing directory holds none.
+    fn read_entries(&self, dir: &Path, keep: impl Fn(&Entry) -> bool) -> Result<Vec<Entry>, RepoError> {
         let mut out = Vec::new();
-        let rd = match std::fs::read_dir(&dir) {
+        let rd = match std::fs::read_dir(dir) {
             Ok(rd) => rd,
             Err(_) => return Ok(out),
         };
         for entry in rd {
             let entry = entry.map_err(|e| RepoError::Backend(e.to_string()))?;
             let p = entry.path();
-            if p.extension().and_then(|s| s.to_str()) == Some("json") && p.is_file() {
-                if let Ok(e) = read_json::<Entry>(&p) {
-                    out.push(e);
-                }
+            if p.extension().and_then(|s| s.to_str()) == Some("json")
+                && p.is_file()
+                && let Ok((e, content)) = read_stored(&p)
+                && keep(&e)
+            {
+                out.push(self.with_content(e, content)?);
             }
         }
+        Ok(out)
+    }
+}
+
+impl EntryRepo for FsRepo {
+    type Tx<'a> = crate::tx::FsTx where Self: 'a;
+
+    fn get<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<Entry, RepoError> {
+        self.check_version(false)?;
+        let feed = self.entry_feed(None, id)?;
+        self.read_entry(&self.entry_path(&feed, id))
+    }
+
+    fn upsert<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, entry: &Entry) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        self.in_tx(tx, |tx| {
+            if let Some(old) = self.stored_feed(Some(tx), &entry.id)?
+                && old != entry.feed
+            {
+                tx.stage_remove(&self.entry_path(&old, &entry.id))?;
+            }
+            self.write_entry(tx, entry)?;
+            self.index_entry(tx, entry)
+        })
+    }
+
+    fn list_by_feed<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<Vec<Entry>, RepoError> {
+        self.check_version(false)?;
+        let mut out = self.read_entries(&self.entries_dir(feed), |_| true)?;
         // Deterministic order: published_ts, updated_ts, then id
         out.sort_by(|a, b| {
             a.published_ts
@@ -55,5 +123,65 @@ impl EntryRepo for FsRepo {
         });
         Ok(out)
     }
-}
 
+    fn count_by_feed<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<usize, RepoError> {
+        self.check_version(false)?;
+        // One file per entry; the records themselves are not read.
+        let rd = match std::fs::read_dir(self.entries_dir(feed)) {
+            Ok(rd) => rd,
+            Err(_) => return Ok(0),
+        };
+        Ok(rd
+            .filter_map(Result::ok)
+            .map(|d| d.path())
+            .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("json") && p.is_file())
+            .count())
+    }
+
+    fn query<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, query: &EntryQuery) -> Result<EntryPage, RepoError> {
+        self.check_version(false)?;
+        query.validate()?;
+        let dirs: Vec<PathBuf> = if query.feeds.is_empty() {
+            match std::fs::read_dir(self.feeds_dir()) {
+                Ok(rd) => rd.filter_map(Result::ok).map(|d| d.path().join("entries")).collect(),
+                Err(_) => Vec::new(),
+            }
+        } else {
+            query.feeds.iter().map(|f| self.entries_dir(f)).collect()
+        };
+        // Filter while reading so only matching entries are held, then order and cut.
+        let mut matching = Vec::new();
+        for dir in dirs {
+            matching.extend(self.read_entries(&dir, |e| query.matches(e) && query.is_past_cursor(e))?);
+        }
+        query.page(matching)
+    }
+
+    fn delete<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<(), RepoError> {
+        self.check_version(false)?;
+        self.in_tx(tx, |tx| {
+            let feed = self.entry_feed(Some(tx), id)?;
+            self.stage_entry_removal(tx, &feed, id)
+        })
+    }
+
+    fn prune<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<Vec<EntryId>, RepoError> {
+        let expired = policy.expired(&EntryRepo::list_by_feed(self, tx, feed)?, now);
+        if expired.is_empty() {
+            return Ok(expired);
+        }
+        self.in_tx(tx, |tx| {
+            for id in &expired {
+                self.stage_entry_removal(tx, feed, id)?;
+            }
+            Ok(())
+        })?;
+        Ok(expired)
+    }
+}
@@ -2,28 +2,45 @@
 File: crates/repos/fs/src/feed_impl.rs
 Purpose: FeedRepo impl for FsRepo.
 Inputs: rssify_core::{Feed, FeedId, FeedRepo, RepoError}; util read/write helpers.
-Outputs: JSON files per feed.
+Outputs: JSON files per feed; last_blob.bin (raw) + last_blob.json (meta).
 Side effects: Filesystem I/O.
+Invariants: delete removes the feed directory (entries included), its schedule directory, its
+            archive records and the search postings of its entries in one tx.
 */
 
+use crate::layout::decode_component;
 use crate::repo::FsRepo;
-use crate::util::{read_json, write_atomic_json};
-use rssify_core::{Feed, FeedId, FeedRepo, RepoError};
+use crate::util::read_json;
+use rssify_core::{ContentBlob, ContentKind, EntryId, Feed, FeedId, FeedRepo, LastBlob, RepoError};
+use serde::{Deserialize, Serialize};
+
+/// Sidecar for last_blob.bin; the bytes themselves stay raw on disk.
+#[derive(Serialize, Deserialize)]
+struct LastBlobMeta {
+    kind: ContentKind,
+    headers: Vec<(String, String)>,
+    fetched_ts: i64,
+    len: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    warc_record: Option<String>,
+}
 
 impl FeedRepo for FsRepo {
     type Tx<'a> = crate::tx::FsTx where Self: 'a;
 
     fn get<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Feed, RepoError> {
+        self.check_version(false)?;
         let path = self.feed_path(id);
         read_json::<Feed>(&path)
     }
 
-    fn put<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &Feed) -> Result<(), RepoError> {
-        let path = self.feed_path(&feed.id);
-        write_atomic_json(&path, feed)
+    fn put<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &Feed) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        self.put_json(tx, &self.feed_path(&feed.id), feed)
     }
 
     fn list<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>) -> Result<Vec<Feed>, RepoError> {
+        self.check_version(false)?;
         let mut out = Vec::new();
         let feeds_dir = self.feeds_dir();
         let rd = match std::fs::read_dir(&feeds_dir) {
@@ -33,13 +50,82 @@ impl FeedRepo for FsRepo {
         for entry in rd {
             let entry = entry.map_err(|e| RepoError::Backend(e.to_string()))?;
             let p = entry.path().join("feed.json");
-            if p.is_file() {
-                if let Ok(feed) = read_json::<Feed>(&p) {
-                    out.push(feed);
-                }
+            if p.is_file()
+                && let Ok(feed) = read_json::<Feed>(&p)
+            {
+                out.push(feed);
             }
         }
         Ok(out)
     }
-}
 
+    fn last_blob<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Option<LastBlob>, RepoError> {
+        self.check_version(false)?;
+        let meta_path = self.last_blob_meta_path(id);
+        if !meta_path.is_file() {
+            return Ok(None);
+        }
+        let meta = read_json::<LastBlobMeta>(&meta_path)?;
+        let bytes = std::fs::read(self.last_blob_path(id)).map_err(|e| RepoError::Backend(e.to_string()))?;
+        if bytes.len() as u64 != meta.len {
+            return Err(RepoError::Backend(format!(
+                "last_blob.bin length {} does not match metadata {}",
+                bytes.len(),
+                meta.len
+            )));
+        }
+        Ok(Some(LastBlob {
+            blob: ContentBlob { kind: meta.kind, bytes },
+            headers: meta.headers,
+            fetched_ts: meta.fetched_ts,
+            warc_record: meta.warc_record,
+        }))
+    }
+
+    fn put_last_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId, blob: &LastBlob) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        // Bytes first: without a tx, a crash between the two writes leaves old metadata whose
+        // length check fails.
+        self.put_bytes(tx, &self.last_blob_path(id), &blob.blob.bytes)?;
+        let meta = LastBlobMeta {
+            kind: blob.blob.kind,
+            headers: blob.headers.clone(),
+            fetched_ts: blob.fetched_ts,
+            len: blob.blob.bytes.len() as u64,
+            warc_record: blob.warc_record.clone(),
+        };
+        self.put_json(tx, &self.last_blob_meta_path(id), &meta)
+    }
+
+    fn delete<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<(), RepoError> {
+        self.check_version(false)?;
+        let archived = self.archive_by_feed_dir(id);
+        let dirs = [self.feed_dir(id), self.schedule_dir(id), archived.clone()];
+        if !dirs.iter().any(|d| d.exists()) {
+            return Err(RepoError::NotFound);
+        }
+        let by_id: Vec<_> = match std::fs::read_dir(&archived) {
+            Ok(rd) => rd.filter_map(Result::ok).map(|d| d.file_name()).collect(),
+            Err(_) => Vec::new(),
+        };
+        // Entry ids come from the file names alone: the records need not be read, let alone
+        // parse, for their postings to go with the feed.
+        let entries: Vec<EntryId> = match std::fs::read_dir(self.entries_dir(id)) {
+            Ok(rd) => rd
+                .filter_map(Result::ok)
+                .filter_map(|d| d.file_name().to_str()?.strip_suffix(".json").and_then(decode_component))
+                .map(EntryId::new)
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        self.in_tx(tx, |tx| {
+            for name in &by_id {
+                tx.stage_remove(&self.archive_by_id_dir().join(name))?;
+            }
+            for entry in &entries {
+                self.unindex_entry(tx, entry)?;
+            }
+            dirs.iter().try_for_each(|d| tx.stage_remove(d))
+        })
+    }
+}
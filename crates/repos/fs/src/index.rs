@@ -0,0 +1,268 @@
+/*
+File: crates/repos/fs/src/index.rs
+Purpose: On-disk full-text index: incremental upkeep with entry writes, postings reads, rebuild.
+Inputs: rssify_core::{IndexedDoc, Posting, CorpusStats}; entries under feeds/<feed>/entries/.
+Outputs: <root>/index/stats.json (CorpusStats), index/docs/<entry>.json (feed, terms and length
+         of each indexed entry; also the id-to-feed index entry lookups go through) and index/terms/<xx>/<term>.jsonl (one posting per line; xx is the
+         first byte of the term's sha256, in hex).
+Side effects: Filesystem I/O; index_entry/unindex_entry only stage through the caller's tx.
+Invariants:
+ - Posting files are appended to: a later line for an entry replaces earlier ones and a
+   `{"entry": .., "removed": true}` line drops it, so an upsert appends to the terms it touches
+   only. The first append of a tx that takes a file across a power-of-two size (from 4 KiB)
+   counts its lines, and when dead ones outnumber live ones the tx rewrites it with the live
+   lines only; the check costs amortized O(1) per appended byte. `rssify reindex` rewrites
+   every file.
+ - Doc records and stats are read through the tx (`FsTx::staged`), so several writes of one
+   tx keep the counts right.
+ - A torn last line (crash mid-append outside a tx) is skipped on read.
+ - Every stored entry has a doc record, written in its upsert's tx, so `indexed_feed` names the
+   feed holding an id without scanning feed directories.
+*/
+
+use crate::compress::{decode, write_record, Compression};
+use crate::content::{entry_files, read_stored};
+use crate::layout::encode_component;
+use crate::repo::{entry_file_name, FsRepo};
+use crate::tx::{remove_target, FsTx};
+use crate::util::{read_json, write_atomic_bytes};
+use rssify_core::{CorpusStats, Entry, EntryId, FeedId, IndexedDoc, Posting, PostingSource, RepoError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Index directory under the repo root.
+pub(crate) const INDEX_DIR: &str = "index";
+/// Entries indexed per batch by `rebuild`, bounding its memory.
+const REBUILD_BATCH: usize = 5_000;
+/// Posting files smaller than this are never compacted.
+const COMPACT_MIN_BYTES: u64 = 4 * 1024;
+
+/// What the index remembers about an entry, to undo its postings later.
+#[derive(Serialize, Deserialize)]
+struct DocRecord {
+    feed: FeedId,
+    len: u32,
+    terms: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Line {
+    Removed {
+        entry: EntryId,
+        #[allow(dead_code)] // only required to tell the two shapes apart
+        removed: bool,
+    },
+    Posting(Posting),
+}
+
+fn stats_path(index: &Path) -> PathBuf {
+    index.join("stats.json")
+}
+
+fn doc_path(index: &Path, id: &EntryId) -> PathBuf {
+    index.join("docs").join(entry_file_name(id))
+}
+
+fn term_path(index: &Path, term: &str) -> PathBuf {
+    let shard = format!("{:02x}", Sha256::digest(term.as_bytes())[0]);
+    index.join("terms").join(shard).join(format!("{}.jsonl", encode_component(term)))
+}
+
+fn json_line<T: Serialize>(value: &T) -> Result<Vec<u8>, RepoError> {
+    let mut line = serde_json::to_vec(value).map_err(|e| RepoError::Ser(e.to_string()))?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+fn io(e: std::io::Error) -> RepoError {
+    RepoError::Backend(e.to_string())
+}
+
+/// Live postings of a posting file's bytes, by entry, and how many lines it holds.
+fn live_postings(bytes: &[u8]) -> (BTreeMap<EntryId, Posting>, usize) {
+    let mut live: BTreeMap<EntryId, Option<Posting>> = BTreeMap::new();
+    let mut lines = 0;
+    for line in bytes.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+        lines += 1;
+        match serde_json::from_slice::<Line>(line) {
+            Ok(Line::Removed { entry, .. }) => live.insert(entry, None),
+            Ok(Line::Posting(p)) => live.insert(p.entry.clone(), Some(p)),
+            Err(_) => continue,
+        };
+    }
+    (live.into_iter().filter_map(|(id, p)| Some((id, p?))).collect(), lines)
+}
+
+impl FsRepo {
+    pub(crate) fn index_dir(&self) -> PathBuf {
+        self.root.join(INDEX_DIR)
+    }
+
+    /// `path` as `tx` will leave it, else as committed; `None` when absent.
+    fn read_through<T: DeserializeOwned>(&self, tx: &FsTx, path: &Path) -> Result<Option<T>, RepoError> {
+        match tx.staged(path)? {
+            Some(Some(bytes)) => serde_json::from_slice(&decode(bytes)?).map(Some).map_err(|e| RepoError::Ser(e.to_string())),
+            Some(None) => Ok(None),
+            None if path.is_file() => read_json(path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Feed whose directory holds entry `id`, per its doc record (through `tx` when given);
+    /// `None` for ids the repo does not hold.
+    pub(crate) fn indexed_feed(&self, tx: Option<&FsTx>, id: &EntryId) -> Result<Option<FeedId>, RepoError> {
+        let path = doc_path(&self.index_dir(), id);
+        let doc = match tx {
+            Some(tx) => self.read_through::<DocRecord>(tx, &path)?,
+            None if path.is_file() => Some(read_json::<DocRecord>(&path)?),
+            None => None,
+        };
+        Ok(doc.map(|d| d.feed))
+    }
+
+    /// Stage `entry`'s postings, replacing whatever the index held for its id.
+    pub(crate) fn index_entry(&self, tx: &FsTx, entry: &Entry) -> Result<(), RepoError> {
+        let doc = IndexedDoc::from_entry(entry);
+        let (mut stats, _) = self.drop_doc(tx, &doc.entry, |t| doc.terms.contains_key(t))?;
+        for (term, posting) in doc.postings() {
+            self.stage_term_line(tx, &term_path(&self.index_dir(), term), &json_line(&posting)?)?;
+        }
+        stats.docs += 1;
+        stats.total_len += u64::from(doc.len);
+        let record = DocRecord { feed: doc.feed, len: doc.len, terms: doc.terms.into_keys().collect() };
+        self.put_json(Some(tx), &doc_path(&self.index_dir(), &doc.entry), &record)?;
+        self.put_json(Some(tx), &stats_path(&self.index_dir()), &stats)
+    }
+
+    /// Stage the removal of `id` from the index; a no-op for entries it does not hold.
+    pub(crate) fn unindex_entry(&self, tx: &FsTx, id: &EntryId) -> Result<(), RepoError> {
+        let (stats, held) = self.drop_doc(tx, id, |_| false)?;
+        if !held {
+            return Ok(());
+        }
+        tx.stage_remove(&doc_path(&self.index_dir(), id))?;
+        self.put_json(Some(tx), &stats_path(&self.index_dir()), &stats)
+    }
+
+    /// Tombstone the indexed terms of `id` that `kept` does not rewrite anyway; returns the
+    /// corpus stats without it and whether the index held it.
+    fn drop_doc(&self, tx: &FsTx, id: &EntryId, kept: impl Fn(&str) -> bool) -> Result<(CorpusStats, bool), RepoError> {
+        let index = self.index_dir();
+        let mut stats: CorpusStats = self.read_through(tx, &stats_path(&index))?.unwrap_or_default();
+        let Some(old) = self.read_through::<DocRecord>(tx, &doc_path(&index, id))? else {
+            return Ok((stats, false));
+        };
+        stats.docs = stats.docs.saturating_sub(1);
+        stats.total_len = stats.total_len.saturating_sub(u64::from(old.len));
+        let tombstone = json_line(&serde_json::json!({ "entry": id, "removed": true }))?;
+        for term in old.terms.iter().filter(|t| !kept(t)) {
+            self.stage_term_line(tx, &term_path(&index, term), &tombstone)?;
+        }
+        Ok((stats, true))
+    }
+
+    /// Stage `line` onto the posting file at `path`, rewriting the file without its dead lines
+    /// instead when this append is due a compaction check and most of its lines are dead.
+    fn stage_term_line(&self, tx: &FsTx, path: &Path, line: &[u8]) -> Result<(), RepoError> {
+        let len = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let grown = len + line.len() as u64;
+        if grown < COMPACT_MIN_BYTES || len.checked_ilog2() == grown.checked_ilog2() || tx.touches(path)? {
+            return tx.stage_append(path, line);
+        }
+        let bytes = match fs::read(path) {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return tx.stage_append(path, line),
+            Err(e) => return Err(io(e)),
+        };
+        let (live, lines) = live_postings(&bytes);
+        if (lines - live.len()) <= live.len() {
+            return tx.stage_append(path, line);
+        }
+        let mut compacted = Vec::with_capacity(bytes.len() / 2);
+        for posting in live.values() {
+            compacted.extend(json_line(posting)?);
+        }
+        compacted.extend_from_slice(line);
+        tx.stage_write(path, &compacted)
+    }
+}
+
+/// Committed index of one repo, as ranking reads it.
+pub(crate) struct FsPostings<'r>(pub(crate) &'r FsRepo);
+
+impl PostingSource for FsPostings<'_> {
+    fn corpus(&self) -> Result<CorpusStats, RepoError> {
+        let path = stats_path(&self.0.index_dir());
+        if path.is_file() { read_json(&path) } else { Ok(CorpusStats::default()) }
+    }
+
+    fn postings(&self, term: &str) -> Result<Vec<Posting>, RepoError> {
+        let bytes = match fs::read(term_path(&self.0.index_dir(), term)) {
+            Ok(b) => b,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io(e)),
+        };
+        Ok(live_postings(&bytes).0.into_values().collect())
+    }
+}
+
+/// Rebuild the index of the repo at `root` from its entry files, then swap it into place, with
+/// doc records and stats stored per `compression`. Writers must be stopped; returns how many
+/// entries were indexed.
+pub(crate) fn rebuild(root: &Path, compression: Compression) -> Result<usize, RepoError> {
+    let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+    let fresh = root.join(format!(".{INDEX_DIR}-rebuild-{}-{nanos}", std::process::id()));
+    let mut stats = CorpusStats::default();
+    let mut batch: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut in_batch = 0;
+    let mut ids = BTreeSet::new();
+    for file in entry_files(root)? {
+        let Ok((entry, _)) = read_stored(&file) else { continue };
+        if !ids.insert(entry.id.clone()) {
+            continue; // the same id under two feeds (left by older writers): the first feed, in name order, wins
+        }
+        let doc = IndexedDoc::from_entry(&entry);
+        for (term, posting) in doc.postings() {
+            batch.entry(term.to_string()).or_default().extend(json_line(&posting)?);
+        }
+        stats.docs += 1;
+        stats.total_len += u64::from(doc.len);
+        let record = DocRecord { feed: doc.feed, len: doc.len, terms: doc.terms.into_keys().collect() };
+        write_record(&doc_path(&fresh, &doc.entry), compression, &record)?;
+        in_batch += 1;
+        if in_batch == REBUILD_BATCH {
+            flush(&fresh, &mut batch)?;
+            in_batch = 0;
+        }
+    }
+    flush(&fresh, &mut batch)?;
+    write_record(&stats_path(&fresh), compression, &stats)?;
+    let live = root.join(INDEX_DIR);
+    let retired = root.join(format!(".{INDEX_DIR}-retired-{nanos}"));
+    if live.exists() {
+        fs::rename(&live, &retired).map_err(io)?;
+    }
+    fs::rename(&fresh, &live).map_err(io)?;
+    remove_target(&retired)?;
+    Ok(ids.len())
+}
+
+/// Append each batched term's lines to its posting file.
+fn flush(index: &Path, batch: &mut BTreeMap<String, Vec<u8>>) -> Result<(), RepoError> {
+    for (term, lines) in std::mem::take(batch) {
+        let path = term_path(index, &term);
+        if !path.exists() {
+            write_atomic_bytes(&path, &lines)?;
+            continue;
+        }
+        let mut f = fs::OpenOptions::new().append(true).open(&path).map_err(io)?;
+        f.write_all(&lines).map_err(io)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+/*
+File: crates/repos/fs/src/layout.rs
+Purpose: Canonical on-disk layout: id-to-path-component encoding and the repo version header.
+Inputs: Repo root; logical id strings.
+Outputs: encode_component, REPO_VERSION, repo_version; crate-internal version writer.
+Side effects: repo_version reads <root>/meta.json; write_version writes it.
+Invariants:
+ - Every id-derived path component goes through encode_component, in FsRepo, the CLI path
+   helpers and stats alike.
+ - A root holding repo data but no meta.json is version 1 (before the header existed).
+*/
+
+use crate::util::{read_json, write_atomic_json};
+use rssify_core::RepoError;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Version written by this binary; older roots need `migrate` (see migrate.rs for the steps).
+pub const REPO_VERSION: u32 = 4;
+/// Version header at the repo root.
+pub const MARKER_FILE: &str = "meta.json";
+/// Top-level directories that only exist once a repo holds data.
+pub(crate) const DATA_DIRS: &[&str] = &["feeds", "entries", "schedule", "runs", "archive"];
+
+#[derive(Serialize, Deserialize)]
+struct Meta {
+    /// Version-2 headers written before the migration framework called this "layout".
+    #[serde(alias = "layout")]
+    repo_version: u32,
+}
+
+/// Percent-encode an id into one path component: ASCII alphanumerics, '-', '_' and '.' pass
+/// through, everything else becomes `%XX` (uppercase hex). A leading '.' is encoded too, so a
+/// component is never hidden, "." or "..".
+pub fn encode_component(id: &str) -> String {
+    let mut out = String::with_capacity(id.len());
+    for (i, b) in id.bytes().enumerate() {
+        let keep = b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || (b == b'.' && i > 0);
+        if keep {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "%{b:02X}");
+        }
+    }
+    out
+}
+
+/// Inverse of `encode_component`; `None` for malformed escapes or non-UTF-8 results.
+pub fn decode_component(name: &str) -> Option<String> {
+    let bytes = name.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Version of the repo at `root`: from meta.json, 1 for data without a header, `None` when the
+/// root is missing or holds no repo data yet.
+pub fn repo_version(root: &Path) -> Result<Option<u32>, RepoError> {
+    let meta = root.join(MARKER_FILE);
+    if meta.is_file() {
+        return read_json::<Meta>(&meta).map(|m| Some(m.repo_version));
+    }
+    Ok(DATA_DIRS.iter().any(|d| root.join(d).exists()).then_some(1))
+}
+
+pub(crate) fn write_version(root: &Path, version: u32) -> Result<(), RepoError> {
+    write_atomic_json(&root.join(MARKER_FILE), &Meta { repo_version: version })
+}
@@ -2,20 +2,33 @@
 File: crates/repos/fs/src/lib.rs
 Purpose: Module glue and public re-exports for the filesystem repository adapter.
 Inputs: rssify_core traits and types.
-Outputs: Public FsRepo and FsTx types; trait impls are in submodules.
+Outputs: Public FsRepo and FsTx types, layout helpers, the version header and migrations,
+         record compression and disk usage; trait impls (search included) are in submodules.
 Side effects: None here.
 Invariants:
  - Keep this file minimal and under 200 LOC.
  - All heavy logic lives in dedicated modules.
 */
 
+mod compress;
 mod tx;
 mod util;
+mod layout;
+mod migrate;
 mod repo;
 mod feed_impl;
 mod entry_impl;
 mod schedule_impl;
+mod run_impl;
+mod blob_impl;
+mod archive_impl;
+mod content;
+mod index;
+mod search_impl;
 
+pub use compress::{disk_usage, read_record, Compression, DiskUsage};
+pub use layout::{decode_component, encode_component, repo_version, MARKER_FILE, REPO_VERSION};
+pub use migrate::{backup, migrate};
 pub use repo::FsRepo;
 pub use tx::FsTx;
 
@@ -0,0 +1,227 @@
+/*
+File: crates/repos/fs/src/migrate.rs
+Purpose: Ordered upgrade steps for fs repos, run through rssify_core::run_migrations, plus the
+         pre-migration backup.
+Inputs: Repo root.
+Outputs: MigrationReport; meta.json rewritten after each step.
+Side effects: Step 2 (layout) renames feed, schedule and run directories, moves entries from
+              entries/by_id + by_feed to feeds/<feed>/entries/ and rewrites archive files;
+              step 3 (search index) builds index/ from the entry files; step 4 moves inline entry
+              content to blobs/; backup copies the whole root.
+Invariants:
+ - Re-running a step after a crash finishes the job: new files are written before old ones are
+   removed, and names already in the new encoding are recognized.
+ - Ids come from file contents where they exist; bare directory names are decoded only as a fallback.
+*/
+
+use crate::compress::Compression;
+use crate::content::{entry_files, read_stored, to_stored, StoredContent};
+use crate::layout::{decode_component, encode_component, repo_version, write_version, REPO_VERSION};
+use crate::repo::entry_file_name;
+use crate::util::{children, json_files, read_json, write_atomic_bytes, write_atomic_json};
+use rssify_core::{blob_path, run_migrations, ArchivedEntry, Entry, EntryId, Feed, FeedId, Migration, MigrationReport, RepoError, RunManifest};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Upgrade steps, oldest first; append one (and bump REPO_VERSION) per format change.
+const STEPS: &[Migration<Path>] = &[
+    Migration { to: 2, name: "per-feed entries, percent-encoded names", apply: layout_v2 },
+    Migration { to: 3, name: "search index", apply: search_index_v3 },
+    Migration { to: 4, name: "entry content in the blob store", apply: content_blobs_v4 },
+];
+
+/// Bring the repo at `root` to `REPO_VERSION`; a no-op for current or empty roots.
+pub fn migrate(root: &Path) -> Result<MigrationReport, RepoError> {
+    let Some(current) = repo_version(root)? else {
+        return Ok(MigrationReport { from: REPO_VERSION, to: REPO_VERSION, ..MigrationReport::default() });
+    };
+    run_migrations(root, current, REPO_VERSION, STEPS, write_version)
+}
+
+fn search_index_v3(root: &Path) -> Result<String, RepoError> {
+    crate::index::rebuild(root, Compression::None).map(|n| format!("entries_indexed={n}"))
+}
+
+/// Move inline entry content to the blob store; entries already holding a reference are skipped.
+fn content_blobs_v4(root: &Path) -> Result<String, RepoError> {
+    let (mut entries_rewritten, mut blobs_written) = (0, 0);
+    for path in entry_files(root)? {
+        let (mut entry, Some(StoredContent::Inline(blob))) = read_stored(&path)? else { continue };
+        entry.content = Some(blob);
+        let (value, blob) = to_stored(&entry)?;
+        if let Some((sha256, bytes)) = blob {
+            let target = root.join(blob_path(&sha256));
+            if !target.is_file() {
+                write_atomic_bytes(&target, bytes)?;
+                blobs_written += 1;
+            }
+        }
+        write_atomic_json(&path, &value)?;
+        entries_rewritten += 1;
+    }
+    Ok(format!("entries_rewritten={entries_rewritten} blobs_written={blobs_written}"))
+}
+
+/// Copy the whole repo at `root` to `dest` (which must not exist yet).
+pub fn backup(root: &Path, dest: &Path) -> Result<(), RepoError> {
+    if dest.exists() {
+        return Err(RepoError::Conflict);
+    }
+    copy_tree(root, dest)
+}
+
+fn copy_tree(from: &Path, to: &Path) -> Result<(), RepoError> {
+    fs::create_dir_all(to).map_err(backend)?;
+    for path in children(from)? {
+        let target = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            copy_tree(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).map_err(backend)?;
+        }
+    }
+    Ok(())
+}
+
+/// Layout-1 escaping: ASCII alnum, '-' and '_' pass through, everything else becomes `_xx`.
+fn legacy_escape(id: &str) -> String {
+    id.bytes()
+        .map(|b| if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' { (b as char).to_string() } else { format!("_{b:02x}") })
+        .collect()
+}
+
+/// Best-effort id for a directory name with no record inside. Layout 1 never emits '%', so a
+/// name containing one was already renamed by an interrupted run.
+fn guess_id(name: &str) -> String {
+    if name.contains('%') {
+        return decode_component(name).unwrap_or_else(|| name.to_string());
+    }
+    let (bytes, mut out, mut i) = (name.as_bytes(), Vec::new(), 0);
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match hex.filter(|h| bytes[i] == b'_' && h.bytes().all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'))) {
+            Some(h) => {
+                out.push(u8::from_str_radix(h, 16).unwrap_or(b'_'));
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(out).unwrap_or_else(|_| name.to_string())
+}
+
+fn backend(e: std::io::Error) -> RepoError {
+    RepoError::Backend(e.to_string())
+}
+
+fn file_name(p: &Path) -> String {
+    p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+/// Rename every child directory of `dir` to `encode_component(id_of(name, path))`.
+fn rename_dirs(dir: &Path, id_of: impl Fn(&str, &Path) -> String) -> Result<usize, RepoError> {
+    let mut renamed = 0;
+    for path in children(dir)?.into_iter().filter(|p| p.is_dir()) {
+        let name = file_name(&path);
+        let target = dir.join(encode_component(&id_of(&name, &path)));
+        if target == path {
+            continue;
+        }
+        if target.exists() {
+            return Err(RepoError::Conflict);
+        }
+        fs::rename(&path, &target).map_err(backend)?;
+        renamed += 1;
+    }
+    Ok(renamed)
+}
+
+/// Version 1 -> 2: `_xx`-escaped names and entries/by_id + by_feed become percent-encoded names
+/// and feeds/<feed>/entries/<entry>.json.
+fn layout_v2(root: &Path) -> Result<String, RepoError> {
+    let (mut dirs_renamed, mut entries_moved, mut archives_rewritten) = (0, 0, 0);
+
+    // Entries: by_feed first so by_id (the copy `get` read) wins.
+    let legacy = root.join("entries");
+    let mut entries: BTreeMap<EntryId, Entry> = BTreeMap::new();
+    let mut sources = Vec::new();
+    for dir in children(&legacy.join("by_feed"))? {
+        sources.extend(json_files(&dir)?);
+    }
+    sources.extend(json_files(&legacy.join("by_id"))?);
+    let mut unreadable = 0;
+    for p in &sources {
+        match read_json::<Entry>(p) {
+            Ok(e) => {
+                entries.insert(e.id.clone(), e);
+            }
+            Err(_) => unreadable += 1,
+        }
+    }
+    if unreadable > 0 {
+        return Err(RepoError::Backend(format!(
+            "{unreadable} unreadable files under {}; fix or remove them and re-run",
+            legacy.display()
+        )));
+    }
+
+    // Every feed id the repo mentions, keyed by both encodings of its directory name.
+    let mut ids: BTreeSet<FeedId> = entries.values().map(|e| e.feed.clone()).collect();
+    for dir in children(&root.join("feeds"))? {
+        if let Ok(f) = read_json::<Feed>(&dir.join("feed.json")) {
+            ids.insert(f.id);
+        }
+    }
+    let archives: Vec<ArchivedEntry> =
+        json_files(&root.join("archive").join("by_id"))?.iter().filter_map(|p| read_json(p).ok()).collect();
+    ids.extend(archives.iter().map(|a| a.feed.clone()));
+    let mut known: BTreeMap<String, String> = BTreeMap::new();
+    for id in &ids {
+        known.insert(legacy_escape(id.as_str()), id.as_str().to_string());
+        known.insert(encode_component(id.as_str()), id.as_str().to_string());
+    }
+    let feed_id = |name: &str, _: &Path| known.get(name).cloned().unwrap_or_else(|| guess_id(name));
+
+    dirs_renamed += rename_dirs(&root.join("feeds"), feed_id)?;
+    dirs_renamed += rename_dirs(&root.join("schedule"), feed_id)?;
+    dirs_renamed += rename_dirs(&root.join("runs"), |name, path| {
+        read_json::<RunManifest>(&path.join("manifest.json")).map_or_else(|_| guess_id(name), |r| r.run_id)
+    })?;
+
+    for e in entries.values() {
+        let dir = root.join("feeds").join(encode_component(e.feed.as_str())).join("entries");
+        write_atomic_json(&dir.join(entry_file_name(&e.id)), e)?;
+        entries_moved += 1;
+    }
+    if legacy.exists() {
+        fs::remove_dir_all(&legacy).map_err(backend)?;
+    }
+
+    // Archives: write the canonical files, then drop every file that is not one of them.
+    let (by_id, by_feed) = (root.join("archive").join("by_id"), root.join("archive").join("by_feed"));
+    let mut keep = BTreeSet::new();
+    for a in &archives {
+        let files = [by_id.join(entry_file_name(&a.entry)), by_feed.join(encode_component(a.feed.as_str())).join(entry_file_name(&a.entry))];
+        for f in files {
+            write_atomic_json(&f, a)?;
+            keep.insert(f);
+        }
+        archives_rewritten += 1;
+    }
+    let mut stale = json_files(&by_id)?;
+    for dir in children(&by_feed)? {
+        stale.extend(json_files(&dir)?);
+    }
+    for f in stale.into_iter().filter(|f| !keep.contains(f)) {
+        fs::remove_file(&f).map_err(backend)?;
+        if let Some(dir) = f.parent().filter(|d| d.starts_with(&by_feed) && *d != by_feed) {
+            let _ = fs::remove_dir(dir); // only succeeds once empty
+        }
+    }
+
+    Ok(format!("dirs_renamed={dirs_renamed} entries_moved={entries_moved} archives_rewritten={archives_rewritten}"))
+}
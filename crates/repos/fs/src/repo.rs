@@ -1,25 +1,50 @@
 /*
 File: crates/repos/fs/src/repo.rs
-Purpose: FsRepo struct and path helpers.
-Inputs: root path injected at construction.
-Outputs: FsRepo methods for path building and tx creation.
-Side effects: None, except path string work.
+Purpose: FsRepo struct, version check, write helpers and path helpers.
+Inputs: root path injected at construction; optional record compression.
+Outputs: FsRepo methods for path building, tx-aware writes and tx creation.
+Side effects: open replays or discards journals left by crashed transactions; the version check
+              reads <root>/meta.json and stamps it on a fresh root's first write.
+Invariants: Every trait method checks the version first; older and newer roots are refused.
+            Trait impls write only through put_json / put_bytes / append_bytes (and delete only
+            through a tx, see in_tx), so a tx sees every change. put_json compresses per the
+            repo's setting; reads take either form.
 */
 
-use crate::tx::FsTx;
-use crate::util::escape_id;
-use rssify_core::{EntryId, FeedId};
+use crate::compress::{encode, Compression};
+use crate::layout::{encode_component, repo_version, write_version, REPO_VERSION};
+use crate::tx::{recover, FsTx};
+use crate::util::write_atomic_bytes;
+use rssify_core::{blob_path, EntryId, FeedId, RepoError};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Clone, Debug)]
 pub struct FsRepo {
     pub(crate) root: PathBuf,
+    /// Set once a current header has been seen (or written); later calls skip the check.
+    version_ok: OnceLock<()>,
+    /// Journal recovery failure from `open`, reported by every trait call.
+    recovery_err: Option<String>,
+    /// How put_json stores new records.
+    pub(crate) compression: Compression,
 }
 
 impl FsRepo {
-    /// Create a repo rooted at `root` (directories created lazily by writers).
+    /// Create a repo rooted at `root` (directories created lazily by writers), first finishing
+    /// or discarding transactions a crashed writer left in its journal.
     pub fn open<P: AsRef<Path>>(root: P) -> Self {
-        Self { root: root.as_ref().to_path_buf() }
+        let root = root.as_ref().to_path_buf();
+        let recovery_err = recover(&root).err().map(|e| format!("fs journal recovery at {}: {e}", root.display()));
+        Self { root, version_ok: OnceLock::new(), recovery_err, compression: Compression::None }
+    }
+
+    /// Write new JSON records with `compression`; existing records read either way.
+    #[must_use]
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
     }
 
     /// Alias used by some tests.
@@ -27,38 +52,161 @@ impl FsRepo {
         Self::open(root)
     }
 
-    /// Begin a no-op transactional scope (for API symmetry).
+    /// Begin a journaled transaction; writes passed it land together on `FsTx::commit`.
     pub fn begin_tx(&self) -> FsTx {
-        FsTx { active: true }
+        FsTx::new(&self.root)
+    }
+
+    /// Refuse roots at another version; `write` stamps the header on a root without data.
+    pub(crate) fn check_version(&self, write: bool) -> Result<(), RepoError> {
+        if let Some(e) = &self.recovery_err {
+            return Err(RepoError::Backend(e.clone()));
+        }
+        if self.version_ok.get().is_some() {
+            return Ok(());
+        }
+        match repo_version(&self.root)? {
+            Some(REPO_VERSION) => {}
+            None if write => write_version(&self.root, REPO_VERSION)?,
+            None => return Ok(()),
+            Some(v) if v < REPO_VERSION => {
+                return Err(RepoError::Backend(format!(
+                    "fs repo version {v} at {}; run `rssify migrate --store fs:{}` first",
+                    self.root.display(),
+                    self.root.display()
+                )));
+            }
+            Some(v) => {
+                return Err(RepoError::Backend(format!(
+                    "fs repo version {v} at {} is newer than this binary supports ({REPO_VERSION})",
+                    self.root.display()
+                )));
+            }
+        }
+        let _ = self.version_ok.set(());
+        Ok(())
+    }
+
+// --------- writes (staged when a tx is given) ---------
+    pub(crate) fn put_json<T: ?Sized + serde::Serialize>(&self, tx: Option<&FsTx>, path: &Path, value: &T) -> Result<(), RepoError> {
+        let s = serde_json::to_string_pretty(value).map_err(|e| RepoError::Backend(e.to_string()))?;
+        self.put_bytes(tx, path, &encode(self.compression, s.as_bytes())?)
+    }
+
+    pub(crate) fn put_bytes(&self, tx: Option<&FsTx>, path: &Path, bytes: &[u8]) -> Result<(), RepoError> {
+        match tx {
+            Some(tx) => tx.stage_write(path, bytes),
+            None => write_atomic_bytes(path, bytes),
+        }
+    }
+
+    pub(crate) fn append_bytes(&self, tx: Option<&FsTx>, path: &Path, bytes: &[u8]) -> Result<(), RepoError> {
+        if let Some(tx) = tx {
+            return tx.stage_append(path, bytes);
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| RepoError::Backend(e.to_string()))?;
+        }
+        let mut f = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| RepoError::Backend(e.to_string()))?;
+        f.write_all(bytes).map_err(|e| RepoError::Backend(e.to_string()))?;
+        f.sync_all().map_err(|e| RepoError::Backend(e.to_string()))
     }
 
-    // --------- path builders ---------
+    /// Run `f` in `tx`, or in a tx of its own committed on success, so multi-file changes
+    /// (deletes, prunes) land together.
+    pub(crate) fn in_tx<T>(&self, tx: Option<&FsTx>, f: impl FnOnce(&FsTx) -> Result<T, RepoError>) -> Result<T, RepoError> {
+        match tx {
+            Some(tx) => f(tx),
+            None => {
+                let own = self.begin_tx();
+                let out = f(&own)?;
+                own.commit()?;
+                Ok(out)
+            }
+        }
+    }
+
+// --------- path builders ---------
     pub(crate) fn feeds_dir(&self) -> PathBuf {
         self.root.join("feeds")
     }
 
-    pub(crate) fn entries_by_id_dir(&self) -> PathBuf {
-        self.root.join("entries").join("by_id")
+    pub(crate) fn feed_dir(&self, id: &FeedId) -> PathBuf {
+        self.feeds_dir().join(encode_component(id.as_str()))
     }
 
-    pub(crate) fn entries_by_feed_dir(&self, feed: &FeedId) -> PathBuf {
-        self.root.join("entries").join("by_feed").join(escape_id(feed.as_str()))
+    pub(crate) fn feed_path(&self, id: &FeedId) -> PathBuf {
+        self.feed_dir(id).join("feed.json")
     }
 
-    pub(crate) fn feed_path(&self, id: &FeedId) -> PathBuf {
-        self.feeds_dir().join(escape_id(id.as_str())).join("feed.json")
+    pub(crate) fn entries_dir(&self, feed: &FeedId) -> PathBuf {
+        self.feed_dir(feed).join("entries")
     }
 
-    pub(crate) fn entry_by_id_path(&self, id: &EntryId) -> PathBuf {
-        self.entries_by_id_dir().join(format!("{}.json", escape_id(id.as_str())))
+    pub(crate) fn entry_path(&self, feed: &FeedId, id: &EntryId) -> PathBuf {
+        self.entries_dir(feed).join(entry_file_name(id))
     }
 
-    pub(crate) fn entry_by_feed_path(&self, feed: &FeedId, id: &EntryId) -> PathBuf {
-        self.entries_by_feed_dir(feed).join(format!("{}.json", escape_id(id.as_str())))
+    /// Raw bytes of the last fetch; `last_blob_meta_path` holds kind, headers and time.
+    pub(crate) fn last_blob_path(&self, id: &FeedId) -> PathBuf {
+        self.feed_dir(id).join("last_blob.bin")
+    }
+
+    pub(crate) fn last_blob_meta_path(&self, id: &FeedId) -> PathBuf {
+        self.feed_dir(id).join("last_blob.json")
+    }
+
+    pub(crate) fn schedule_dir(&self, feed: &FeedId) -> PathBuf {
+        self.root.join("schedule").join(encode_component(feed.as_str()))
     }
 
     pub(crate) fn schedule_last_ok_path(&self, feed: &FeedId) -> PathBuf {
-        self.root.join("schedule").join(escape_id(feed.as_str())).join("last_ok.txt")
+        self.schedule_dir(feed).join("last_ok.txt")
+    }
+
+    pub(crate) fn schedule_failures_path(&self, feed: &FeedId) -> PathBuf {
+        self.schedule_dir(feed).join("failures.json")
+    }
+
+    pub(crate) fn runs_dir(&self) -> PathBuf {
+        self.root.join("runs")
+    }
+
+    pub(crate) fn run_manifest_path(&self, run_id: &str) -> PathBuf {
+        self.runs_dir().join(encode_component(run_id)).join("manifest.json")
+    }
+
+    pub(crate) fn run_log_path(&self, run_id: &str) -> PathBuf {
+        self.runs_dir().join(encode_component(run_id)).join("checkpoints.log")
+    }
+
+    /// Content-addressed blob file; see `rssify_core::blob_path`.
+    pub(crate) fn blob_file(&self, sha256: &str) -> PathBuf {
+        self.root.join(blob_path(sha256))
+    }
+
+    pub(crate) fn archive_by_id_dir(&self) -> PathBuf {
+        self.root.join("archive").join("by_id")
+    }
+
+    pub(crate) fn archive_by_id_path(&self, id: &EntryId) -> PathBuf {
+        self.archive_by_id_dir().join(entry_file_name(id))
+    }
+
+    pub(crate) fn archive_by_feed_dir(&self, feed: &FeedId) -> PathBuf {
+        self.root.join("archive").join("by_feed").join(encode_component(feed.as_str()))
+    }
+
+    pub(crate) fn schedule_mirrors_path(&self, feed: &FeedId) -> PathBuf {
+        self.schedule_dir(feed).join("mirrors.json")
     }
 }
 
+/// `<encoded entry id>.json`, the file name of an entry (and of its archived copy).
+pub(crate) fn entry_file_name(id: &EntryId) -> String {
+    format!("{}.json", encode_component(id.as_str()))
+}
@@ -0,0 +1,91 @@
+/*
+File: crates/repos/fs/src/run_impl.rs
+Purpose: RunRepo impl for FsRepo (fetch run manifests plus append-only per-feed checkpoints).
+Inputs: rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo}; std::fs for I/O.
+Outputs: runs/<run_id>/manifest.json and runs/<run_id>/checkpoints.log (one JSON
+         {"feed", "status"} object per line).
+Side effects: Filesystem I/O; each checkpoint line is fsynced.
+Invariants: get_run applies log lines over the manifest in order; a torn last line is ignored.
+*/
+
+use crate::repo::FsRepo;
+use crate::util::read_json;
+use rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo};
+use std::fs;
+use serde::{Deserialize, Serialize};
+
+/// One line of checkpoints.log.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    feed: String,
+    status: FeedRunStatus,
+}
+
+impl RunRepo for FsRepo {
+    type Tx<'a> = crate::tx::FsTx where Self: 'a;
+
+    fn put_run<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, run: &RunManifest) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        self.put_json(tx, &self.run_manifest_path(&run.run_id), run)
+    }
+
+    fn record_run_feed<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        run_id: &str,
+        feed: &str,
+        status: FeedRunStatus,
+    ) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        let mut line = serde_json::to_string(&Checkpoint { feed: feed.to_string(), status })
+            .map_err(|e| RepoError::Ser(e.to_string()))?;
+        line.push('\n');
+        self.append_bytes(tx, &self.run_log_path(run_id), line.as_bytes())
+    }
+
+    fn get_run<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, run_id: &str) -> Result<RunManifest, RepoError> {
+        self.check_version(false)?;
+        let p = self.run_manifest_path(run_id);
+        if !p.is_file() {
+            return Err(RepoError::NotFound);
+        }
+        let mut run = read_json::<RunManifest>(&p)?;
+        let log = match fs::read_to_string(self.run_log_path(run_id)) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(RepoError::Backend(e.to_string())),
+        };
+        // Only newline-terminated lines are complete checkpoints.
+        for line in log.split_inclusive('\n').filter_map(|l| l.strip_suffix('\n')) {
+            if let Ok(c) = serde_json::from_str::<Checkpoint>(line) {
+                run.mark(&c.feed, c.status);
+            }
+        }
+        Ok(run)
+    }
+
+    fn latest_incomplete_run<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+    ) -> Result<Option<RunManifest>, RepoError> {
+        self.check_version(false)?;
+        let rd = match fs::read_dir(self.runs_dir()) {
+            Ok(rd) => rd,
+            Err(_) => return Ok(None),
+        };
+        let mut best: Option<RunManifest> = None;
+        for entry in rd {
+            let entry = entry.map_err(|e| RepoError::Backend(e.to_string()))?;
+            let Ok(run) = read_json::<RunManifest>(&entry.path().join("manifest.json")) else {
+                continue;
+            };
+            if run.is_complete() {
+                continue;
+            }
+            if best.as_ref().is_none_or(|b| (run.started_at, &run.run_id) > (b.started_at, &b.run_id)) {
+                best = Some(run);
+            }
+        }
+        best.map(|b| self.get_run(tx, &b.run_id)).transpose()
+    }
+}
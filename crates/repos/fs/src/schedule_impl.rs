@@ -1,15 +1,15 @@
 /*
 File: crates/repos/fs/src/schedule_impl.rs
-Purpose: ScheduleRepo impl for FsRepo (tiny "last ok" timestamp file).
-Inputs: rssify_core::{FeedId, RepoError, ScheduleRepo}; std::fs for I/O.
-Outputs: last_ok.txt per feed; i64 unix seconds.
+Purpose: ScheduleRepo impl for FsRepo (tiny "last ok" timestamp file, failure streak, mirror health).
+Inputs: rssify_core::{FailureStreak, FeedId, MirrorHealth, RepoError, ScheduleRepo}; std::fs for I/O.
+Outputs: last_ok.txt (i64 unix seconds), failures.json and mirrors.json per feed.
 Side effects: Filesystem I/O.
 */
 
 use crate::repo::FsRepo;
-use rssify_core::{FeedId, RepoError, ScheduleRepo};
+use crate::util::read_json;
+use rssify_core::{FailureStreak, FeedId, MirrorHealth, RepoError, ScheduleRepo};
 use std::fs;
-use std::io::Write;
 
 impl ScheduleRepo for FsRepo {
     type Tx<'a> = crate::tx::FsTx where Self: 'a;
@@ -19,6 +19,7 @@ impl ScheduleRepo for FsRepo {
         _tx: Option<&'a Self::Tx<'a>>,
         feed: &FeedId,
     ) -> Result<Option<i64>, RepoError> {
+        self.check_version(false)?;
         let p = self.schedule_last_ok_path(feed);
         if !p.exists() {
             return Ok(None);
@@ -36,18 +37,57 @@ impl ScheduleRepo for FsRepo {
 
     fn record_fetch_ts<'a>(
         &'a self,
-        _tx: Option<&'a Self::Tx<'a>>,
+        tx: Option<&'a Self::Tx<'a>>,
         feed: &FeedId,
         ts: i64,
     ) -> Result<(), RepoError> {
-        let p = self.schedule_last_ok_path(feed);
-        if let Some(dir) = p.parent() {
-            fs::create_dir_all(dir).map_err(|e| RepoError::Backend(e.to_string()))?;
+        self.check_version(true)?;
+        self.put_bytes(tx, &self.schedule_last_ok_path(feed), format!("{ts}\n").as_bytes())
+    }
+
+    fn failure_streak<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<FailureStreak, RepoError> {
+        self.check_version(false)?;
+        let p = self.schedule_failures_path(feed);
+        if !p.exists() {
+            return Ok(FailureStreak::default());
         }
-        let mut f = fs::File::create(&p).map_err(|e| RepoError::Backend(e.to_string()))?;
-        write!(f, "{ts}\n").map_err(|e| RepoError::Backend(e.to_string()))?;
-        f.sync_all().map_err(|e| RepoError::Backend(e.to_string()))?;
-        Ok(())
+        read_json::<FailureStreak>(&p)
+    }
+
+    fn record_failure_streak<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        streak: &FailureStreak,
+    ) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        self.put_json(tx, &self.schedule_failures_path(feed), streak)
     }
-}
 
+    fn mirror_health<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<MirrorHealth, RepoError> {
+        self.check_version(false)?;
+        let p = self.schedule_mirrors_path(feed);
+        if !p.exists() {
+            return Ok(MirrorHealth::default());
+        }
+        read_json::<MirrorHealth>(&p)
+    }
+
+    fn record_mirror_health<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        health: &MirrorHealth,
+    ) -> Result<(), RepoError> {
+        self.check_version(true)?;
+        self.put_json(tx, &self.schedule_mirrors_path(feed), health)
+    }
+}
@@ -0,0 +1,27 @@
+/*
+File: crates/repos/fs/src/search_impl.rs
+Purpose: SearchRepo impl for FsRepo.
+Inputs: rssify_core::{SearchQuery, SearchRepo, rank}; the index under <root>/index (index.rs).
+Outputs: BM25-ranked hits; reindex rewrites the index from the entry files.
+Side effects: Filesystem I/O.
+Invariants: search reads committed postings only. reindex builds beside the live index and
+            swaps it in; it ignores the tx (it is not a staged write) and needs writers stopped.
+*/
+
+use crate::index::{rebuild, FsPostings};
+use crate::repo::FsRepo;
+use rssify_core::{rank, RepoError, SearchHit, SearchQuery, SearchRepo};
+
+impl SearchRepo for FsRepo {
+    type Tx<'a> = crate::tx::FsTx where Self: 'a;
+
+    fn search<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, query: &SearchQuery, limit: usize) -> Result<Vec<SearchHit>, RepoError> {
+        self.check_version(false)?;
+        rank(&FsPostings(self), query, limit)
+    }
+
+    fn reindex<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError> {
+        self.check_version(true)?;
+        rebuild(&self.root, self.compression)
+    }
+}
@@ -1,21 +1,361 @@
 /*
 File: crates/repos/fs/src/tx.rs
-Purpose: Trivial transaction/context handle for the FS adapter.
-Inputs: rssify_core::Tx trait.
-Outputs: FsTx implementing Tx.
-Side effects: None.
+Purpose: Journaled transaction handle for the FS adapter, plus crash recovery of journals.
+Inputs: rssify_core::Tx trait; paths under the repo root.
+Outputs: FsTx implementing Tx, TxRepo for FsRepo; `recover` for FsRepo::open.
+Side effects: Staged files under <root>/.journal/<tx id>/; commit moves them into place and
+              performs staged removals.
+Invariants:
+ - Writes passed a tx only touch the journal until commit; reads show committed state, except
+   where a caller asks `staged` for the tx's own pending write.
+ - commit.json is the commit point: written (atomically) after every staged file is synced.
+   A journal without it is discarded by recovery, one with it is replayed.
+ - Every op has a staged file (an empty marker for removals) that is gone once the op is done,
+   so replay is idempotent and resumes at the first pending op.
+   Only an append cut off between writing and removing its staged file repeats; checkpoint
+   logs tolerate a repeated line.
+ - A live tx holds an exclusive lock on its journal's `lock` file, so recovery in another
+   process (or another FsRepo) never touches it. The journal is set up under a hidden
+   `.<tx id>.staging` name, locked there and only then renamed into place, so recovery never
+   sees a live journal before its lock is held; it skips staging directories until they are
+   an hour old (left by a crash during setup). Finding the journal or its staging directory
+   already there is contention (`RepoError::Conflict`), never a shared journal.
 */
 
-use rssify_core::Tx as TxTrait;
+use crate::util::{read_json, write_atomic_bytes, write_atomic_json};
+use crate::repo::FsRepo;
+use rssify_core::{RepoError, Tx as TxTrait, TxRepo};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug, Clone)]
+/// Journal directory under the repo root.
+pub(crate) const JOURNAL_DIR: &str = ".journal";
+const COMMIT_FILE: &str = "commit.json";
+const LOCK_FILE: &str = "lock";
+const STAGING_SUFFIX: &str = ".staging";
+/// Age after which recovery treats a staging directory as a crashed setup.
+const STALE_STAGING: std::time::Duration = std::time::Duration::from_secs(3600);
+
+static NEXT_TX: AtomicU64 = AtomicU64::new(0);
+
+/// One staged change; `target` is relative to the repo root, `file` names the staged bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Op {
+    /// Replace `target` with the staged file.
+    Write { target: String, file: String },
+    /// Append the staged bytes to `target` (run checkpoint logs).
+    Append { target: String, file: String },
+    /// Delete `target`, a file or a whole directory; `file` is an empty marker.
+    Remove { target: String, file: String },
+}
+
+enum Staging<'b> {
+    Write(&'b [u8]),
+    Append(&'b [u8]),
+    Remove,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CommitRecord {
+    ops: Vec<Op>,
+}
+
+#[derive(Debug, Default)]
+struct Journal {
+    /// Held from the first staged write until the journal is removed.
+    lock: Option<File>,
+    ops: Vec<Op>,
+    prepared: bool,
+    finished: bool,
+}
+
+/// Write transaction over an fs repo; pass it as `Some(&tx)` to repo calls, then `commit`.
+/// Dropping it uncommitted discards the staged writes.
+#[derive(Debug)]
 pub struct FsTx {
-    pub(crate) active: bool,
+    root: PathBuf,
+    dir: PathBuf,
+    journal: Mutex<Journal>,
+}
+
+impl FsTx {
+    pub(crate) fn new(root: &Path) -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+        let id = format!("{nanos:020}-{}-{}", std::process::id(), NEXT_TX.fetch_add(1, Ordering::Relaxed));
+        Self { root: root.to_path_buf(), dir: root.join(JOURNAL_DIR).join(id), journal: Mutex::new(Journal::default()) }
+    }
+
+    /// Stage `bytes` to replace `target` at commit.
+    pub(crate) fn stage_write(&self, target: &Path, bytes: &[u8]) -> Result<(), RepoError> {
+        self.stage(target, Staging::Write(bytes))
+    }
+
+    /// Stage `bytes` to be appended to `target` at commit.
+    pub(crate) fn stage_append(&self, target: &Path, bytes: &[u8]) -> Result<(), RepoError> {
+        self.stage(target, Staging::Append(bytes))
+    }
+
+    /// Stage the removal of `target` (file or directory tree) at commit.
+    pub(crate) fn stage_remove(&self, target: &Path) -> Result<(), RepoError> {
+        self.stage(target, Staging::Remove)
+    }
+
+    fn stage(&self, target: &Path, staging: Staging<'_>) -> Result<(), RepoError> {
+        let mut j = self.lock_journal()?;
+        if j.prepared {
+            return Err(RepoError::Backend("fs tx is already committed; begin a new one".into()));
+        }
+        if j.lock.is_none() {
+            j.lock = Some(self.publish_journal()?);
+        }
+        let rel = target
+            .strip_prefix(&self.root)
+            .map_err(|_| RepoError::Backend(format!("{} is outside the repo", target.display())))?;
+        let target = rel.to_string_lossy().into_owned();
+        let file = format!("{}.data", j.ops.len());
+        let op = match staging {
+            Staging::Write(bytes) => {
+                write_atomic_bytes(&self.dir.join(&file), bytes)?;
+                Op::Write { target, file }
+            }
+            Staging::Append(bytes) => {
+                write_atomic_bytes(&self.dir.join(&file), bytes)?;
+                Op::Append { target, file }
+            }
+            Staging::Remove => {
+                write_atomic_bytes(&self.dir.join(&file), &[])?;
+                Op::Remove { target, file }
+            }
+        };
+        j.ops.push(op);
+        Ok(())
+    }
+
+    /// Create the journal directory with its lock already held: set it up under a staging name
+    /// recovery skips, lock it, then rename it into place.
+    fn publish_journal(&self) -> Result<File, RepoError> {
+        let (Some(journals), Some(id)) = (self.dir.parent(), self.dir.file_name()) else {
+            return Err(RepoError::Backend(format!("bad journal path {}", self.dir.display())));
+        };
+        fs::create_dir_all(journals).map_err(io)?;
+        // Journal ids are unique, so an existing directory means another handle owns it.
+        let staging = journals.join(format!(".{}{STAGING_SUFFIX}", id.to_string_lossy()));
+        match fs::create_dir(&staging) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Err(RepoError::Conflict),
+            Err(e) => return Err(io(e)),
+        }
+        let published = File::options()
+            .write(true)
+            .create_new(true)
+            .open(staging.join(LOCK_FILE))
+            .and_then(|lock| lock.lock().map(|()| lock))
+            .map_err(io)
+            .and_then(|lock| {
+                if self.dir.exists() {
+                    return Err(RepoError::Conflict);
+                }
+                fs::rename(&staging, &self.dir).map_err(io)?;
+                Ok(lock)
+            });
+        if published.is_err() {
+            let _ = remove_target(&staging);
+        }
+        published
+    }
+
+    /// What this tx leaves at `target` once committed: `Some(Some(bytes))` for a staged write,
+    /// `Some(None)` when it removes the target (or a directory holding it), `None` when the tx
+    /// has not touched it. Lets callers read their own writes; appends are not reflected.
+    pub(crate) fn staged(&self, target: &Path) -> Result<Option<Option<Vec<u8>>>, RepoError> {
+        let Ok(rel) = target.strip_prefix(&self.root) else { return Ok(None) };
+        let j = self.lock_journal()?;
+        for op in j.ops.iter().rev() {
+            match op {
+                Op::Write { target, file } if Path::new(target) == rel => {
+                    return fs::read(self.dir.join(file)).map(|b| Some(Some(b))).map_err(io);
+                }
+                Op::Remove { target, .. } if rel.starts_with(target) => return Ok(Some(None)),
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether this tx has staged any op on exactly `target`.
+    pub(crate) fn touches(&self, target: &Path) -> Result<bool, RepoError> {
+        let Ok(rel) = target.strip_prefix(&self.root) else { return Ok(false) };
+        let j = self.lock_journal()?;
+        Ok(j.ops.iter().any(|op| match op {
+            Op::Write { target, .. } | Op::Append { target, .. } | Op::Remove { target, .. } => Path::new(target) == rel,
+        }))
+    }
+
+    /// Make the staged writes durable as one unit: from here on they are applied, if not by
+    /// `commit` then by recovery on the next `FsRepo::open`. Further writes are refused.
+    pub fn prepare(&self) -> Result<(), RepoError> {
+        let mut j = self.lock_journal()?;
+        if j.prepared || j.ops.is_empty() {
+            j.prepared = true;
+            return Ok(());
+        }
+        write_atomic_json(&self.dir.join(COMMIT_FILE), &CommitRecord { ops: j.ops.clone() })?;
+        j.prepared = true;
+        Ok(())
+    }
+
+    /// Apply every write staged through this transaction.
+    pub fn commit(self) -> Result<(), RepoError> {
+        self.prepare()?;
+        let mut j = self.lock_journal()?;
+        replay(&self.root, &self.dir, &j.ops)?;
+        j.finished = true;
+        j.lock = None;
+        Ok(())
+    }
+
+    /// Discard every write staged through this transaction (same as dropping it).
+    pub fn rollback(self) -> Result<(), RepoError> {
+        let mut j = self.lock_journal()?;
+        if j.prepared && !j.ops.is_empty() {
+            return Err(RepoError::Backend("fs tx is already committed; it can no longer roll back".into()));
+        }
+        j.finished = true;
+        j.lock = None;
+        remove_target(&self.dir)
+    }
+
+    fn lock_journal(&self) -> Result<std::sync::MutexGuard<'_, Journal>, RepoError> {
+        self.journal.lock().map_err(|_| RepoError::Backend("fs tx journal lock poisoned".into()))
+    }
+}
+
+impl Drop for FsTx {
+    fn drop(&mut self) {
+        let Ok(j) = self.journal.get_mut() else { return };
+        if j.finished || j.lock.is_none() {
+            return;
+        }
+        // Prepared work must still land; failures leave the journal for recovery.
+        let _ = if j.prepared { replay(&self.root, &self.dir, &j.ops) } else { remove_target(&self.dir) };
+        j.lock = None;
+    }
 }
 
 impl TxTrait for FsTx {
     fn is_active(&self) -> bool {
-        self.active
+        self.lock_journal().is_ok_and(|j| !j.finished)
+    }
+}
+
+impl TxRepo for FsRepo {
+    type Tx<'a> = FsTx where Self: 'a;
+
+    fn begin_tx(&self) -> Result<FsTx, RepoError> {
+        Ok(FsRepo::begin_tx(self))
+    }
+
+    fn commit_tx(tx: FsTx) -> Result<(), RepoError> {
+        tx.commit()
+    }
+
+    fn reborrow<'s, 'r: 's>(tx: &'s FsTx) -> &'s FsTx {
+        tx
+    }
+}
+
+/// Finish or discard every journal left by a crashed writer; returns how many were replayed.
+/// Journals still locked by a live transaction are skipped.
+pub(crate) fn recover(root: &Path) -> Result<usize, RepoError> {
+    let mut dirs: Vec<PathBuf> = match fs::read_dir(root.join(JOURNAL_DIR)) {
+        Ok(rd) => rd.filter_map(Result::ok).map(|d| d.path()).filter(|p| p.is_dir()).collect(),
+        Err(_) => return Ok(0),
+    };
+    dirs.sort();
+    let mut replayed = 0;
+    for dir in dirs {
+        if dir.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')) {
+            reclaim_staging(&dir)?;
+            continue;
+        }
+        // Journals are published with their lock file, so a missing one is not ours to judge.
+        let _lock = match File::options().write(true).open(dir.join(LOCK_FILE)) {
+            Ok(f) if f.try_lock().is_ok() => f,
+            Ok(_) => continue,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(io(e)),
+        };
+        let commit = dir.join(COMMIT_FILE);
+        if commit.is_file() {
+            replay(root, &dir, &read_json::<CommitRecord>(&commit)?.ops)?;
+            replayed += 1;
+        } else {
+            remove_target(&dir)?;
+        }
+    }
+    Ok(replayed)
+}
+
+/// Remove a staging directory left by a crash during journal setup: one that is old and
+/// unlocked. Anything younger may be a live tx between creating and publishing its journal.
+fn reclaim_staging(dir: &Path) -> Result<(), RepoError> {
+    let age = fs::metadata(dir).and_then(|m| m.modified()).map(|t| t.elapsed().unwrap_or_default());
+    if !age.is_ok_and(|age| age > STALE_STAGING) {
+        return Ok(());
+    }
+    match File::options().write(true).open(dir.join(LOCK_FILE)) {
+        Ok(f) if f.try_lock().is_err() => Ok(()),
+        Ok(_) => remove_target(dir),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => remove_target(dir),
+        Err(e) => Err(io(e)),
     }
 }
 
+/// Apply `ops` in order, then remove the journal.
+fn replay(root: &Path, dir: &Path, ops: &[Op]) -> Result<(), RepoError> {
+    for op in ops {
+        let (Op::Write { target, file } | Op::Append { target, file } | Op::Remove { target, file }) = op;
+        let staged = dir.join(file);
+        if !staged.exists() {
+            continue;
+        }
+        let target = root.join(target);
+        if let (Op::Write { .. } | Op::Append { .. }, Some(parent)) = (op, target.parent()) {
+            fs::create_dir_all(parent).map_err(io)?;
+        }
+        match op {
+            Op::Write { .. } => fs::rename(&staged, &target).map_err(io)?,
+            Op::Remove { .. } => {
+                remove_target(&target)?;
+                fs::remove_file(&staged).map_err(io)?;
+            }
+            Op::Append { .. } => {
+                let bytes = fs::read(&staged).map_err(io)?;
+                let mut f = File::options().create(true).append(true).open(&target).map_err(io)?;
+                f.write_all(&bytes).map_err(io)?;
+                f.sync_all().map_err(io)?;
+                fs::remove_file(&staged).map_err(io)?;
+            }
+        }
+    }
+    remove_target(dir)
+}
+
+/// Delete a file or directory tree; a missing target is not an error.
+pub(crate) fn remove_target(path: &Path) -> Result<(), RepoError> {
+    let removed = if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    match removed {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(io(e)),
+        _ => Ok(()),
+    }
+}
+
+fn io(e: std::io::Error) -> RepoError {
+    RepoError::Backend(e.to_string())
+}
@@ -1,26 +1,31 @@
 /*
 File: crates/repos/fs/src/util.rs
-Purpose: Small utilities: JSON read/write and ID escaping.
+Purpose: Small utilities: JSON/bytes read/write (JSON reads accept compressed records).
 Inputs: serde/serde_json.
 Outputs: helpers used by repo/trait impls.
 Side effects: Filesystem I/O in read/write helpers.
 */
 
+use crate::compress::read_record;
 use serde::de::DeserializeOwned;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
 pub fn write_atomic_json<T: ?Sized + serde::Serialize>(path: &Path, value: &T) -> Result<(), rssify_core::RepoError> {
+    let s = serde_json::to_string_pretty(value)
+        .map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
+    write_atomic_bytes(path, s.as_bytes())
+}
+
+pub fn write_atomic_bytes(path: &Path, bytes: &[u8]) -> Result<(), rssify_core::RepoError> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
     }
     let tmp = tmp_path(path, "tmp");
     {
         let mut f = fs::File::create(&tmp).map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
-        let s = serde_json::to_string_pretty(value)
-            .map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
-        f.write_all(s.as_bytes())
+        f.write_all(bytes)
             .map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
         f.sync_all()
             .map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
@@ -28,9 +33,31 @@ pub fn write_atomic_json<T: ?Sized + serde::Serialize>(path: &Path, value: &T) -
     fs::rename(&tmp, path).map_err(|e| rssify_core::RepoError::Backend(e.to_string()))
 }
 
+/// Read a JSON record, compressed or not (see compress.rs).
 pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, rssify_core::RepoError> {
-    let s = fs::read_to_string(path).map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
-    serde_json::from_str(&s).map_err(|e| rssify_core::RepoError::Backend(e.to_string()))
+    let bytes = read_record(path)?;
+    serde_json::from_slice(&bytes).map_err(|e| rssify_core::RepoError::Backend(e.to_string()))
+}
+
+/// Paths directly under `dir`, sorted; a missing directory has none.
+pub(crate) fn children(dir: &Path) -> Result<Vec<PathBuf>, rssify_core::RepoError> {
+    match fs::read_dir(dir) {
+        Ok(rd) => {
+            let mut out = rd
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| rssify_core::RepoError::Backend(e.to_string()))?;
+            out.sort();
+            Ok(out)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(rssify_core::RepoError::Backend(e.to_string())),
+    }
+}
+
+/// The `.json` files directly under `dir`, sorted.
+pub(crate) fn json_files(dir: &Path) -> Result<Vec<PathBuf>, rssify_core::RepoError> {
+    Ok(children(dir)?.into_iter().filter(|p| p.is_file() && p.extension().is_some_and(|x| x == "json")).collect())
 }
 
 fn tmp_path(p: &Path, ext: &str) -> PathBuf {
@@ -38,29 +65,3 @@ fn tmp_path(p: &Path, ext: &str) -> PathBuf {
     tmp.push(format!(".{}", ext));
     PathBuf::from(tmp)
 }
-
-/// Escape an ID into a filesystem-safe component:
-/// - ASCII alnum, '-' and '_' pass through
-/// - everything else becomes _XX (lower-hex)
-pub fn escape_id(id: &str) -> String {
-    fn hex(n: u8) -> char {
-        match n {
-            0..=9 => (b'0' + n) as char,
-            10..=15 => (b'a' + (n - 10)) as char,
-            _ => '?',
-        }
-    }
-    let mut out = String::with_capacity(id.len());
-    for b in id.bytes() {
-        let c = b as char;
-        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
-            out.push(c);
-        } else {
-            out.push('_');
-            out.push(hex(b >> 4));
-            out.push(hex(b & 0x0F));
-        }
-    }
-    out
-}
-
@@ -0,0 +1,87 @@
+/*
+File: crates/repos/fs/tests/compress.rs
+Purpose: zstd record compression: compressed writes, transparent reads of compressed and plain
+         records by either kind of repo, and on-disk versus logical size.
+Inputs: FsRepo::with_compression, disk_usage, read_record.
+Outputs: Asserts on file bytes and on what each repo reads back.
+Side effects: Filesystem I/O in a temp dir (not deleted).
+Invariants:
+ - No network.
+ - Each test uses a fresh directory name with pid+nanos to avoid collisions.
+*/
+
+mod support;
+
+use rssify_core::{ContentBlob, ContentKind, Entry, EntryRepo, Feed, FeedId, FeedRepo, SearchQuery, SearchRepo};
+use rssify_repo_fs::{disk_usage, read_record, Compression, FsRepo};
+use std::fs;
+use support::temp_root;
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+fn feed(url: &str) -> Feed {
+    Feed { title: Some("A feed with a title that repeats, repeats, repeats".into()), ..support::feed(url) }
+}
+
+fn entry(feed: &FeedId, id: &str) -> Entry {
+    Entry {
+        url: Some(format!("https://a.test/{id}")),
+        title: Some(format!("Compressed entry {id}")),
+        published_ts: Some(1_700_000_000),
+        summary: Some("lorem ipsum ".repeat(40)),
+        content: Some(ContentBlob { kind: ContentKind::Html, bytes: b"<p>body</p>".to_vec() }),
+        ..support::entry(feed, id)
+    }
+}
+
+#[test]
+fn compressed_records_read_back_through_either_repo() {
+    let root = temp_root();
+    let zstd = FsRepo::open(&root).with_compression(Compression::Zstd);
+    let f = feed("https://a.test/rss");
+    FeedRepo::put(&zstd, None, &f).unwrap();
+    let tx = zstd.begin_tx();
+    let (e1, e2) = (entry(&f.id, "e1"), entry(&f.id, "e2"));
+    EntryRepo::upsert(&zstd, Some(&tx), &e1).unwrap();
+    EntryRepo::upsert(&zstd, Some(&tx), &e2).unwrap();
+    tx.commit().unwrap();
+
+    let feed_file = root.join("feeds").join(rssify_repo_fs::encode_component(f.id.as_str())).join("feed.json");
+    assert!(fs::read(&feed_file).unwrap().starts_with(&ZSTD_MAGIC));
+    let plain: Feed = serde_json::from_slice(&read_record(&feed_file).unwrap()).unwrap();
+    assert_eq!(plain, f);
+    assert!(fs::read(root.join("meta.json")).unwrap().starts_with(b"{"), "the version header stays plain");
+
+    let reader = FsRepo::open(&root);
+    assert_eq!(FeedRepo::get(&reader, None, &f.id).unwrap(), f);
+    assert_eq!(EntryRepo::get(&reader, None, &e1.id).unwrap(), e1);
+    let hits = SearchRepo::search(&reader, None, &SearchQuery::parse("compressed").unwrap(), 10).unwrap();
+    assert_eq!(hits.len(), 2, "index records of one tx stay consistent when compressed");
+
+    let f2 = feed("https://b.test/rss");
+    FeedRepo::put(&reader, None, &f2).unwrap();
+    assert_eq!(FeedRepo::list(&zstd, None).unwrap().len(), 2, "plain records written later mix in");
+    assert_eq!(SearchRepo::reindex(&zstd, None).unwrap(), 2);
+    assert_eq!(EntryRepo::list_by_feed(&zstd, None, &f.id).unwrap(), vec![e1, e2]);
+}
+
+#[test]
+fn disk_usage_reports_stored_and_logical_bytes() {
+    let (plain_root, zstd_root) = (temp_root(), temp_root().join("z"));
+    let f = feed("https://a.test/rss");
+    for repo in [FsRepo::open(&plain_root), FsRepo::open(&zstd_root).with_compression(Compression::Zstd)] {
+        FeedRepo::put(&repo, None, &f).unwrap();
+        for i in 0..5 {
+            EntryRepo::upsert(&repo, None, &entry(&f.id, &format!("e{i}"))).unwrap();
+        }
+    }
+    let plain = disk_usage(&plain_root).unwrap();
+    assert_eq!(plain.on_disk_bytes, plain.logical_bytes);
+    let zstd = disk_usage(&zstd_root).unwrap();
+    assert_eq!((zstd.files, zstd.logical_bytes), (plain.files, plain.logical_bytes));
+    assert!(zstd.on_disk_bytes < zstd.logical_bytes, "{zstd:?}");
+    assert_eq!(disk_usage(&plain_root.join("missing")).unwrap().files, 0);
+
+    assert_eq!("ZSTD".parse::<Compression>(), Ok(Compression::Zstd));
+    assert!("gzip".parse::<Compression>().is_err());
+}
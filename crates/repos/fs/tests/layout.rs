@@ -0,0 +1,146 @@
+/*
+File: crates/repos/fs/tests/layout.rs
+Purpose: Canonical layout (percent-encoded names, per-feed entries), the meta.json version header,
+         in-place migration of version-1 repos and the pre-migration backup.
+Inputs: FsRepo, migrate, backup; hand-built version-1 trees.
+Outputs: Asserts on paths, RepoError results and migration reports.
+Side effects: Filesystem I/O in a temp dir (not deleted).
+Invariants:
+ - No network.
+ - Each test uses a fresh directory name with pid+nanos to avoid collisions.
+*/
+
+mod support;
+
+use rssify_core::{ArchiveRepo, ArchivedEntry, ContentBlob, ContentKind, EntryId, EntryRepo, FailureStreak, FeedId, FeedRepo, ScheduleRepo};
+use rssify_repo_fs::{backup, decode_component, encode_component, migrate, repo_version, FsRepo, REPO_VERSION};
+use std::fs;
+use std::path::Path;
+use support::{entry, feed, temp_root};
+
+fn write_json<T: serde::Serialize>(path: &Path, value: &T) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, serde_json::to_vec(value).unwrap()).unwrap();
+}
+
+#[test]
+fn components_are_percent_encoded_and_reversible() {
+    assert_eq!(encode_component("url:https://example.com/feed"), "url%3Ahttps%3A%2F%2Fexample.com%2Ffeed");
+    assert_eq!(encode_component("guid:ABC 123"), "guid%3AABC%20123");
+    assert_eq!(encode_component(".."), "%2E.");
+    assert_eq!(encode_component("a_b-c.d"), "a_b-c.d");
+    for id in ["url:https://example.com/feed", "..", "ünïcode/€", ""] {
+        assert_eq!(decode_component(&encode_component(id)).as_deref(), Some(id));
+    }
+    assert_eq!(decode_component("%zz"), None);
+}
+
+#[test]
+fn entries_live_under_their_feed_and_writes_stamp_the_version() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    assert_eq!(repo_version(&root).unwrap(), None);
+    assert!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().is_empty());
+    assert!(!root.join("meta.json").exists(), "reads leave a fresh root untouched");
+
+    EntryRepo::upsert(&repo, None, &entry(&f.id, "guid:A 1")).unwrap();
+    assert_eq!(repo_version(&root).unwrap(), Some(REPO_VERSION));
+    let path = root.join("feeds/url%3Ahttps%3A%2F%2Fex.com%2Frss/entries/guid%3AA%201.json");
+    assert!(path.is_file(), "{}", path.display());
+    assert!(!root.join("entries").exists());
+    assert_eq!(EntryRepo::get(&repo, None, &EntryId::new("guid:A 1")).unwrap(), entry(&f.id, "guid:A 1"));
+    assert!(matches!(EntryRepo::get(&repo, None, &EntryId::new("nope")), Err(rssify_core::RepoError::NotFound)));
+}
+
+#[test]
+fn unversioned_and_newer_repos_are_refused() {
+    let root = temp_root();
+    fs::create_dir_all(root.join("entries/by_id")).unwrap();
+    let repo = FsRepo::open(&root);
+    let err = FeedRepo::list(&repo, None).expect_err("version 1");
+    assert!(err.to_string().contains("rssify migrate"), "{err}");
+    assert!(FeedRepo::put(&repo, None, &feed("https://ex.com/rss")).is_err());
+
+    let newer = temp_root();
+    fs::write(newer.join("meta.json"), r#"{"repo_version": 99}"#).unwrap();
+    let err = FeedRepo::list(&FsRepo::open(&newer), None).expect_err("newer");
+    assert!(err.to_string().contains("newer"), "{err}");
+    assert!(migrate(&newer).unwrap_err().to_string().contains("newer"));
+
+    let legacy_key = temp_root();
+    fs::write(legacy_key.join("meta.json"), r#"{"layout": 2}"#).unwrap();
+    assert_eq!(repo_version(&legacy_key).unwrap(), Some(2));
+}
+
+#[test]
+fn version_one_repos_migrate_in_place_after_a_backup() {
+    let root = temp_root();
+    let f = feed("https://ex.com/rss");
+    let bare = FeedId::from_url("https://only-entries.test/a_b");
+    let (mut e1, e2, e3) = (entry(&f.id, "guid:A 1"), entry(&f.id, "link:https://ex.com/2"), entry(&bare, "x"));
+    e1.content = Some(ContentBlob { kind: ContentKind::Html, bytes: b"<p>kept</p>".to_vec() });
+    let old_feed = "url_3ahttps_3a_2f_2fex_2ecom_2frss";
+    write_json(&root.join("feeds").join(old_feed).join("feed.json"), &f);
+    fs::write(root.join("feeds").join(old_feed).join("last_blob.bin"), b"<rss/>").unwrap();
+    for e in [&e1, &e2, &e3] {
+        let name = |id: &str| {
+            id.bytes().map(|b| if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' { (b as char).to_string() } else { format!("_{b:02x}") }).collect::<String>()
+        };
+        write_json(&root.join("entries/by_id").join(format!("{}.json", name(e.id.as_str()))), e);
+        write_json(&root.join("entries/by_feed").join(name(e.feed.as_str())).join(format!("{}.json", name(e.id.as_str()))), e);
+    }
+    write_json(&root.join("schedule").join(old_feed).join("failures.json"), &FailureStreak { permanent: 2, since_ts: Some(5) });
+    let archived = ArchivedEntry {
+        entry: e1.id.clone(),
+        feed: f.id.clone(),
+        archived_ts: 7,
+        content: None,
+        attachments: Vec::new(),
+        skipped: Vec::new(),
+    };
+    write_json(&root.join("archive/by_id/guid_3aA_201.json"), &archived);
+    write_json(&root.join("archive/by_feed").join(old_feed).join("guid_3aA_201.json"), &archived);
+    assert_eq!(repo_version(&root).unwrap(), Some(1));
+
+    let copy = root.with_extension("backup");
+    backup(&root, &copy).expect("backup");
+    assert!(backup(&root, &copy).is_err(), "never overwrites a backup");
+
+    let report = migrate(&root).expect("migrate");
+    assert_eq!((report.from, report.to, report.applied.len()), (1, REPO_VERSION, 3));
+    assert_eq!(report.applied[0].note, "dirs_renamed=2 entries_moved=3 archives_rewritten=1");
+    assert_eq!(report.applied[1].note, "entries_indexed=3");
+    assert_eq!(report.applied[2].note, "entries_rewritten=1 blobs_written=1");
+    assert!(root.join("index/stats.json").is_file());
+    assert_eq!(repo_version(&root).unwrap(), Some(REPO_VERSION));
+    assert!(!root.join("entries").exists());
+    assert!(!root.join("archive/by_feed").join(old_feed).exists());
+
+    let repo = FsRepo::open(&root);
+    assert_eq!(FeedRepo::list(&repo, None).unwrap(), vec![f.clone()]);
+    assert_eq!(FeedRepo::last_blob(&repo, None, &f.id).unwrap(), None, "no sidecar, no blob");
+    assert!(root.join("feeds").join(encode_component(f.id.as_str())).join("last_blob.bin").is_file());
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().len(), 2);
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &bare).unwrap(), vec![e3]);
+    assert_eq!(ScheduleRepo::failure_streak(&repo, None, &f.id).unwrap().permanent, 2);
+    assert_eq!(ArchiveRepo::archives_by_feed(&repo, None, &f.id).unwrap(), vec![archived.clone()]);
+    assert_eq!(ArchiveRepo::archive(&repo, None, &e1.id).unwrap(), Some(archived));
+    assert_eq!(EntryRepo::get(&repo, None, &e1.id).unwrap(), e1, "content reads back from its blob");
+
+    let again = migrate(&root).expect("idempotent");
+    assert_eq!((again.from, again.applied.len()), (REPO_VERSION, 0));
+    assert_eq!(repo_version(&copy).unwrap(), Some(1), "the backup keeps the old version");
+    assert!(copy.join("entries/by_id/guid_3aA_201.json").is_file());
+}
+
+#[test]
+fn unreadable_legacy_entries_fail_the_step_and_keep_the_version() {
+    let root = temp_root();
+    write_json(&root.join("entries/by_id/ok.json"), &entry(&FeedId::from_url("https://ex.com/rss"), "ok"));
+    fs::write(root.join("entries/by_id/broken.json"), b"{not json").unwrap();
+    let err = migrate(&root).expect_err("unreadable");
+    assert!(err.to_string().contains("1 unreadable"), "{err}");
+    assert_eq!(repo_version(&root).unwrap(), Some(1));
+    assert!(root.join("entries/by_id/ok.json").is_file(), "nothing removed");
+}
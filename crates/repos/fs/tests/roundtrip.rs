@@ -1,6 +1,7 @@
 /*
 File: crates/repos/fs/tests/roundtrip.rs
-Purpose: Round-trip tests for FsRepo implementing core repo traits.
+Purpose: Round-trip tests for FsRepo: the shared conformance suite plus on-disk layout details
+         (raw files, torn checkpoint lines, index-free lookups, posting compaction, content dedupe).
 Inputs: FsRepo; rssify_core domain types and traits.
 Outputs: Asserts on RepoError results; creates unique temp dirs.
 Side effects: Filesystem I/O in a temp dir (not deleted).
@@ -9,130 +10,173 @@ Invariants:
  - Each test uses a fresh directory name with pid+nanos to avoid collisions.
 */
 
+#[macro_use]
+mod support;
+
 use rssify_core::{
-    ContentBlob, ContentKind, Entry, EntryId, EntryRepo, Feed, FeedId, FeedRepo, ScheduleRepo,
+    ArchiveRepo, ArchivedEntry, Attachment, AttachmentKind, BlobRepo, ContentBlob, ContentKind, Entry, EntryId, EntryRepo, FeedId, FeedRepo,
+    FeedRunStatus, LastBlob, RepoError, RunManifest, RunRepo, SearchQuery, SearchRepo,
 };
 use rssify_repo_fs::FsRepo;
 use std::fs;
-use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
-
-fn temp_root() -> PathBuf {
-    let mut p = std::env::temp_dir();
-    let pid = std::process::id();
-    let nanos = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .subsec_nanos();
-    p.push(format!("rssify-fsrepo-{}-{}", pid, nanos));
-    fs::create_dir_all(&p).expect("create temp dir");
-    p
+use support::{archived, entry, feed, temp_root};
+
+conformance_suite!(FsRepo::open(temp_root()));
+
+#[test]
+fn run_checkpoints_ignore_torn_lines() {
+    let root = temp_root();
+    let repo = FsRepo::new(&root);
+    let ids = ["url:a".to_string(), "url:b".to_string(), "url:c".to_string()];
+    RunRepo::put_run(&repo, None, &RunManifest::new("200-1", 200, &ids)).expect("put run");
+    RunRepo::record_run_feed(&repo, None, "200-1", "url:a", FeedRunStatus::Done).expect("checkpoint a");
+    let log = root.join("runs").join("200-1").join("checkpoints.log");
+    let mut f = fs::OpenOptions::new().append(true).open(&log).unwrap();
+    std::io::Write::write_all(&mut f, br#"{"feed": "url:c", "sta"#).unwrap();
+
+    let got = RunRepo::latest_incomplete_run(&repo, None).expect("read").expect("incomplete run");
+    assert_eq!(got.pending(), ["url:b", "url:c"]);
 }
 
 #[test]
-fn feed_roundtrip_and_list() {
+fn raw_bytes_live_in_plain_files() {
     let root = temp_root();
     let repo = FsRepo::new(&root);
-    let f1 = Feed {
-        id: FeedId::from_url("https://example.com/feed"),
-        url: "https://example.com/feed".into(),
-        title: Some("Example".into()),
-        site_url: Some("https://example.com".into()),
-        etag: None,
-        last_modified: None,
-        active: true,
-    };
-    let f2 = Feed {
-        id: FeedId::from_url("https://blog.test/rss"),
-        url: "https://blog.test/rss".into(),
-        title: None,
-        site_url: None,
-        etag: Some("W/123".into()),
-        last_modified: None,
-        active: true,
-    };
-    let tx = repo.begin_tx();
-    FeedRepo::put(&repo, Some(&tx), &f1).expect("put f1");
-    FeedRepo::put(&repo, Some(&tx), &f2).expect("put f2");
+    let feed = FeedId::from_url("https://ex.com/rss");
+    let blob = LastBlob { blob: ContentBlob { kind: ContentKind::Xml, bytes: b"<rss>\xff</rss>".to_vec() }, headers: Vec::new(), fetched_ts: 1, warc_record: None };
+    FeedRepo::put_last_blob(&repo, None, &feed, &blob).expect("put blob");
+    let raw = root.join("feeds").join("url%3Ahttps%3A%2F%2Fex.com%2Frss").join("last_blob.bin");
+    assert_eq!(fs::read(raw).expect("raw file"), blob.blob.bytes);
 
-    let got = FeedRepo::get(&repo, None, &f1.id).expect("get f1");
-    assert_eq!(got.url, f1.url);
+    let sha = BlobRepo::put_blob(&repo, None, b"abc").unwrap();
+    assert!(root.join("blobs/sha256/ba").join(&sha).is_file());
+}
 
-    let list = FeedRepo::list(&repo, None).expect("list feeds");
-    assert_eq!(list.len(), 2);
+#[test]
+fn an_id_shared_by_two_feeds_belongs_to_the_last_upsert() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let (f, g) = (feed("https://a.test/rss"), feed("https://b.test/rss"));
+    let (first, second) = (entry(&f.id, "guid:shared"), entry(&g.id, "guid:shared"));
+    EntryRepo::upsert(&repo, None, &first).unwrap();
+    EntryRepo::upsert(&repo, None, &second).unwrap();
+
+    assert_eq!(EntryRepo::get(&repo, None, &second.id).unwrap(), second, "the later feed, not the first in name order");
+    assert!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().is_empty(), "moved, not copied");
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &g.id).unwrap(), vec![second.clone()]);
+
+    EntryRepo::delete(&repo, None, &second.id).expect("delete shared id");
+    assert!(matches!(EntryRepo::get(&repo, None, &second.id), Err(RepoError::NotFound)));
+    assert!(EntryRepo::list_by_feed(&repo, None, &g.id).unwrap().is_empty());
 }
 
 #[test]
-fn entry_roundtrip_and_scan() {
+fn entries_are_found_without_the_search_index() {
     let root = temp_root();
-    let repo = FsRepo::new(&root);
-    let feed = Feed {
-        id: FeedId::from_url("https://ex.com/rss"),
-        url: "https://ex.com/rss".into(),
-        title: None,
-        site_url: None,
-        etag: None,
-        last_modified: None,
-        active: true,
-    };
-    FeedRepo::put(&repo, None, &feed).expect("put feed");
-
-    let e1 = Entry {
-        id: EntryId::from_parts(&feed.id, Some("guid-1"), None, Some("A"), Some(10)),
-        feed: feed.id.clone(),
-        url: Some("https://ex.com/a".into()),
-        title: Some("A".into()),
-        published_ts: Some(10),
-        updated_ts: None,
-        summary: None,
-        content: Some(ContentBlob {
-            kind: ContentKind::Xml,
-            bytes: b"<xml/>".to_vec(),
-        }),
-    };
-    let e2 = Entry {
-        id: EntryId::from_parts(
-            &feed.id,
-            None,
-            Some("https://ex.com/b"),
-            Some("B"),
-            Some(20),
-        ),
-        feed: feed.id.clone(),
-        url: Some("https://ex.com/b".into()),
-        title: Some("B".into()),
-        published_ts: Some(20),
-        updated_ts: None,
-        summary: None,
-        content: None,
-    };
+    let repo = FsRepo::open(&root);
+    let (f, g) = (feed("https://a.test/rss"), feed("https://b.test/rss"));
+    let (kept, gone, moved) = (entry(&f.id, "guid:kept"), entry(&f.id, "guid:gone"), entry(&f.id, "guid:moved"));
+    for e in [&kept, &gone, &moved] {
+        EntryRepo::upsert(&repo, None, e).unwrap();
+    }
+    fs::remove_dir_all(root.join("index")).unwrap();
 
-    EntryRepo::upsert(&repo, None, &e1).expect("upsert e1");
-    EntryRepo::upsert(&repo, None, &e1).expect("upsert e1 again (idempotent)");
-    EntryRepo::upsert(&repo, None, &e2).expect("upsert e2");
+    assert_eq!(EntryRepo::get(&repo, None, &kept.id).unwrap(), kept);
+    EntryRepo::delete(&repo, None, &gone.id).expect("delete without a doc record");
+    assert!(matches!(EntryRepo::get(&repo, None, &gone.id), Err(RepoError::NotFound)));
+    let moved = Entry { feed: g.id.clone(), ..moved };
+    EntryRepo::upsert(&repo, None, &moved).unwrap();
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap(), vec![kept], "moved, not copied");
+    assert_eq!(EntryRepo::get(&repo, None, &moved.id).unwrap(), moved);
+}
 
-    let got = EntryRepo::get(&repo, None, &e1.id).expect("get e1");
-    assert_eq!(got.title.as_deref(), Some("A"));
+#[test]
+fn deleting_a_feed_drops_postings_of_unreadable_entries() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://a.test/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    EntryRepo::upsert(&repo, None, &Entry { title: Some("Rust weekly".into()), ..entry(&f.id, "guid:1") }).unwrap();
+    let file = root.join("feeds").join(rssify_repo_fs::encode_component(f.id.as_str())).join("entries/guid%3A1.json");
+    fs::write(&file, b"{ torn").unwrap();
 
-    let list = EntryRepo::list_by_feed(&repo, None, &feed.id).expect("list entries by feed");
-    assert_eq!(list.len(), 2);
-    assert_eq!(list[0].id, e1.id);
-    assert_eq!(list[1].id, e2.id);
+    FeedRepo::delete(&repo, None, &f.id).expect("delete without reading entries");
+    let hits = SearchRepo::search(&repo, None, &SearchQuery::parse("rust").unwrap(), 10).unwrap();
+    assert!(hits.is_empty(), "postings go with the feed");
+}
+
+#[test]
+fn search_reads_committed_postings() {
+    let repo = FsRepo::open(temp_root());
+    let b = feed("https://b.test/rss");
+    FeedRepo::put(&repo, None, &b).unwrap();
+    let ids = |q: &str| -> Vec<String> {
+        let hits = SearchRepo::search(&repo, None, &SearchQuery::parse(q).unwrap(), 10).unwrap();
+        hits.into_iter().map(|h| h.entry.as_str().to_string()).collect()
+    };
+    let tx = repo.begin_tx();
+    for title in ["Draft one", "Final cut"] {
+        EntryRepo::upsert(&repo, Some(&tx), &Entry { title: Some(title.into()), ..entry(&b.id, "b2") }).unwrap();
+    }
+    assert_eq!(ids("final"), Vec::<String>::new(), "search reads committed postings");
+    tx.commit().unwrap();
+    assert_eq!((ids("final"), ids("draft")), (vec!["b2".to_string()], Vec::new()));
 }
 
 #[test]
-fn schedule_record_and_read() {
+fn posting_files_are_compacted_once_mostly_dead() {
     let root = temp_root();
-    let repo = FsRepo::new(&root);
-    let feed = FeedId::from_url("https://ex.com/rss");
-    assert_eq!(
-        ScheduleRepo::last_ok_fetch_ts(&repo, None, &feed).expect("none yet"),
-        None
-    );
-    ScheduleRepo::record_fetch_ts(&repo, None, &feed, 12345).expect("record ts");
-    assert_eq!(
-        ScheduleRepo::last_ok_fetch_ts(&repo, None, &feed).expect("read ts"),
-        Some(12345)
-    );
+    let repo = FsRepo::open(&root);
+    let f = feed("https://a.test/rss");
+    let other = Entry { title: Some("Rust in gardens".into()), ..entry(&f.id, "other") };
+    EntryRepo::upsert(&repo, None, &other).unwrap();
+    let churn = |n: i64| Entry { title: Some("Rust weekly".into()), published_ts: Some(n), ..entry(&f.id, "churn") };
+    for n in 0..300 {
+        EntryRepo::upsert(&repo, None, &churn(n)).unwrap();
+    }
+
+    let shards = fs::read_dir(root.join("index/terms")).unwrap();
+    let file = shards.map(|d| d.unwrap().path().join("rust.jsonl")).find(|p| p.is_file()).expect("posting file");
+    let len = fs::metadata(&file).unwrap().len();
+    assert!(len < 8 * 1024, "300 rewrites of one entry left {len} bytes");
+    let hits = SearchRepo::search(&repo, None, &SearchQuery::parse("rust").unwrap(), 10).unwrap();
+    let mut ids: Vec<_> = hits.iter().map(|h| h.entry.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, ["churn", "other"]);
+    EntryRepo::delete(&repo, None, &EntryId::new("churn")).unwrap();
+    let hits = SearchRepo::search(&repo, None, &SearchQuery::parse("rust").unwrap(), 10).unwrap();
+    assert_eq!(hits.iter().map(|h| h.entry.as_str()).collect::<Vec<_>>(), ["other"]);
 }
 
+#[test]
+fn entry_content_is_stored_once_by_hash_and_collected_when_unreferenced() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://a.test/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    let body = ContentBlob { kind: ContentKind::Html, bytes: b"<p>same body</p>".to_vec() };
+    let (e1, e2) = (entry(&f.id, "c1"), entry(&f.id, "c2"));
+    for e in [&e1, &e2] {
+        EntryRepo::upsert(&repo, None, &Entry { content: Some(body.clone()), ..e.clone() }).unwrap();
+    }
+    let sha = BlobRepo::put_blob(&repo, None, &body.bytes).unwrap();
+    let shard: Vec<_> = fs::read_dir(root.join("blobs/sha256").join(&sha[..2])).unwrap().collect();
+    assert_eq!(shard.len(), 1, "both entries share one blob file");
+    let file = root.join("feeds").join(rssify_repo_fs::encode_component(f.id.as_str())).join("entries/c1.json");
+    let stored: serde_json::Value = serde_json::from_slice(&fs::read(file).unwrap()).unwrap();
+    assert_eq!(stored["content"], serde_json::json!({ "kind": "Html", "sha256": sha }));
+    assert_eq!(EntryRepo::get(&repo, None, &e1.id).unwrap().content, Some(body.clone()));
+
+    let image = BlobRepo::put_blob(&repo, None, b"png").unwrap();
+    let attachment = Attachment { url: "https://a.test/x.png".into(), kind: AttachmentKind::Image, mime: "image/png".into(), size: 3, sha256: image.clone(), warc_record: None };
+    ArchiveRepo::put_archive(&repo, None, &ArchivedEntry { attachments: vec![attachment], ..archived(&entry(&f.id, "other")) }).unwrap();
+    BlobRepo::put_blob(&repo, None, b"orphan").unwrap();
+    assert_eq!(BlobRepo::gc_blobs(&repo, None).unwrap(), 1, "only the orphan goes");
+
+    EntryRepo::delete(&repo, None, &e1.id).unwrap();
+    assert_eq!(BlobRepo::gc_blobs(&repo, None).unwrap(), 0, "c2 still references the body");
+    EntryRepo::delete(&repo, None, &e2.id).unwrap();
+    assert_eq!(BlobRepo::gc_blobs(&repo, None).unwrap(), 1);
+    assert_eq!(BlobRepo::get_blob(&repo, None, &sha).unwrap(), None);
+    assert_eq!(BlobRepo::get_blob(&repo, None, &image).unwrap().as_deref(), Some(&b"png"[..]));
+}
@@ -0,0 +1,153 @@
+/*
+File: crates/repos/fs/tests/tx.rs
+Purpose: Journaled FsTx: atomic commit, rollback on drop, and recovery on open after simulated
+         crashes between staged writes and after the commit point.
+Inputs: FsRepo, FsTx, backup (a copy of the root stands in for the disk at the moment of a crash).
+Outputs: Asserts on what each repo sees and on leftover journal directories.
+Side effects: Filesystem I/O in a temp dir (not deleted).
+Invariants:
+ - No network.
+ - Each test uses a fresh directory name with pid+nanos to avoid collisions.
+*/
+
+mod support;
+
+use rssify_core::{ContentBlob, ContentKind, EntryRepo, FeedRepo, FeedRunStatus, LastBlob, RepoError, RunManifest, RunRepo, Tx};
+use rssify_repo_fs::{backup, FsRepo};
+use std::fs;
+use std::path::{Path, PathBuf};
+use support::{entry, feed, temp_root};
+
+fn journals(root: &Path) -> usize {
+    fs::read_dir(root.join(".journal")).map_or(0, |rd| rd.count())
+}
+
+/// Copy of `root` as it is on disk right now, as a crash at this instant would leave it.
+fn crash_image(root: &Path, name: &str) -> PathBuf {
+    let copy = root.with_extension(name);
+    backup(root, &copy).expect("copy root");
+    copy
+}
+
+#[test]
+fn staged_writes_land_together_on_commit() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    let run = RunManifest::new("run-1", 10, &[f.id.as_str().to_string()]);
+    RunRepo::put_run(&repo, None, &run).unwrap();
+
+    let tx = repo.begin_tx();
+    assert!(tx.is_active());
+    FeedRepo::put(&repo, Some(&tx), &f).unwrap();
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:1")).unwrap();
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:2")).unwrap();
+    let blob = LastBlob { blob: ContentBlob { kind: ContentKind::Xml, bytes: b"<rss/>".to_vec() }, headers: Vec::new(), fetched_ts: 11, warc_record: None };
+    FeedRepo::put_last_blob(&repo, Some(&tx), &f.id, &blob).unwrap();
+    RunRepo::record_run_feed(&repo, Some(&tx), "run-1", f.id.as_str(), FeedRunStatus::Done).unwrap();
+
+    assert!(matches!(FeedRepo::get(&repo, None, &f.id), Err(RepoError::NotFound) | Err(RepoError::Backend(_))));
+    assert!(EntryRepo::list_by_feed(&repo, Some(&tx), &f.id).unwrap().is_empty(), "reads show committed state");
+    assert_eq!(journals(&root), 1);
+
+    tx.commit().expect("commit");
+    assert_eq!(FeedRepo::get(&repo, None, &f.id).unwrap(), f);
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().len(), 2);
+    assert_eq!(FeedRepo::last_blob(&repo, None, &f.id).unwrap(), Some(blob));
+    assert_eq!(RunRepo::get_run(&repo, None, "run-1").unwrap().feeds[0].status, FeedRunStatus::Done);
+    assert_eq!(journals(&root), 0);
+}
+
+#[test]
+fn dropping_or_rolling_back_discards_staged_writes() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    {
+        let tx = repo.begin_tx();
+        FeedRepo::put(&repo, Some(&tx), &f).unwrap();
+    }
+    let tx = repo.begin_tx();
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:1")).unwrap();
+    tx.rollback().unwrap();
+
+    assert!(FeedRepo::list(&repo, None).unwrap().is_empty());
+    assert!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().is_empty());
+    assert_eq!(journals(&root), 0);
+}
+
+#[test]
+fn a_crash_between_staged_writes_is_rolled_back_on_open() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    FeedRepo::put(&repo, None, &f).unwrap();
+
+    let tx = repo.begin_tx();
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:1")).unwrap();
+    let after_one = crash_image(&root, "crash1");
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:2")).unwrap();
+    let after_two = crash_image(&root, "crash2");
+
+    // Another handle on the live root leaves the in-flight journal alone.
+    assert!(EntryRepo::list_by_feed(&FsRepo::open(&root), None, &f.id).unwrap().is_empty());
+    assert_eq!(journals(&root), 1);
+
+    for image in [after_one, after_two] {
+        assert_eq!(journals(&image), 1);
+        let recovered = FsRepo::open(&image);
+        assert_eq!(journals(&image), 0, "uncommitted journal discarded");
+        assert_eq!(FeedRepo::list(&recovered, None).unwrap(), vec![f.clone()]);
+        assert!(EntryRepo::list_by_feed(&recovered, None, &f.id).unwrap().is_empty(), "no half-written feed");
+    }
+
+    tx.commit().expect("the live tx still commits");
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().len(), 2);
+}
+
+#[test]
+fn a_crash_after_the_commit_point_is_replayed_on_open() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    RunRepo::put_run(&repo, None, &RunManifest::new("run-1", 10, &[f.id.as_str().to_string()])).unwrap();
+
+    let tx = repo.begin_tx();
+    FeedRepo::put(&repo, Some(&tx), &f).unwrap();
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:1")).unwrap();
+    RunRepo::record_run_feed(&repo, Some(&tx), "run-1", f.id.as_str(), FeedRunStatus::Done).unwrap();
+    tx.prepare().expect("prepare");
+    assert!(EntryRepo::upsert(&repo, Some(&tx), &entry(&f.id, "guid:2")).is_err(), "no writes after the commit point");
+    let image = crash_image(&root, "crash");
+
+    let recovered = FsRepo::open(&image);
+    assert_eq!(journals(&image), 0);
+    assert_eq!(FeedRepo::get(&recovered, None, &f.id).unwrap(), f);
+    assert_eq!(EntryRepo::list_by_feed(&recovered, None, &f.id).unwrap(), vec![entry(&f.id, "guid:1")]);
+    assert_eq!(RunRepo::get_run(&recovered, None, "run-1").unwrap().feeds[0].status, FeedRunStatus::Done);
+    let log = fs::read_to_string(image.join("runs/run-1/checkpoints.log")).unwrap();
+    assert_eq!(log.lines().count(), 1, "replay appends once");
+
+    drop(tx);
+    assert_eq!(journals(&root), 0, "dropping a prepared tx applies it");
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).unwrap().len(), 1);
+}
+
+#[test]
+fn recovery_leaves_journals_that_are_still_being_set_up() {
+    let root = temp_root();
+    let repo = FsRepo::open(&root);
+    let f = feed("https://ex.com/rss");
+    let live = repo.begin_tx();
+    FeedRepo::put(&repo, Some(&live), &f).unwrap();
+    // Another writer paused between creating its lock file and locking it.
+    let setting_up = root.join(".journal/.00000000000000000001-1-0.staging");
+    fs::create_dir_all(&setting_up).unwrap();
+    fs::write(setting_up.join("lock"), b"").unwrap();
+
+    let other = FsRepo::open(&root);
+    assert!(setting_up.join("lock").is_file(), "a journal under setup is not recovery's to discard");
+    assert!(FeedRepo::list(&other, None).unwrap().is_empty());
+    live.commit().expect("the live tx still commits");
+    assert_eq!(FeedRepo::list(&other, None).unwrap(), vec![f]);
+}
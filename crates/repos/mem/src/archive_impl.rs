@@ -0,0 +1,33 @@
+/*
+File: crates/repos/mem/src/archive_impl.rs
+Purpose: ArchiveRepo impl for MemRepo.
+Inputs: rssify_core::{ArchiveRepo, ArchivedEntry, EntryId, FeedId, RepoError}.
+Outputs: Archived entries keyed by entry id in the shared state.
+Side effects: None beyond memory.
+Invariants: archives_by_feed returns entries ordered by entry id.
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{ArchiveRepo, ArchivedEntry, EntryId, FeedId, RepoError};
+
+impl ArchiveRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn put_archive<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, archived: &ArchivedEntry) -> Result<(), RepoError> {
+        self.lock().archives.insert(archived.entry.clone(), archived.clone());
+        Ok(())
+    }
+
+    fn archive<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, entry: &EntryId) -> Result<Option<ArchivedEntry>, RepoError> {
+        Ok(self.lock().archives.get(entry).cloned())
+    }
+
+    fn archives_by_feed<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<Vec<ArchivedEntry>, RepoError> {
+        Ok(self.lock().archives.values().filter(|a| &a.feed == feed).cloned().collect())
+    }
+}
@@ -0,0 +1,46 @@
+/*
+File: crates/repos/mem/src/blob_impl.rs
+Purpose: BlobRepo impl for MemRepo (sha256 content-addressed map).
+Inputs: rssify_core::{BlobRepo, RepoError}; sha2 for hashing.
+Outputs: Blobs keyed by lowercase sha256 hex.
+Side effects: None beyond memory.
+Invariants: A key is the sha256 of its bytes; existing blobs are never rewritten.
+            Entry content is held inline, so only archive attachments keep a blob alive.
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{BlobRepo, RepoError};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().fold(String::with_capacity(64), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+impl BlobRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn put_blob<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, bytes: &[u8]) -> Result<String, RepoError> {
+        let hash = sha256_hex(bytes);
+        self.lock().blobs.entry(hash.clone()).or_insert_with(|| bytes.to_vec());
+        Ok(hash)
+    }
+
+    fn get_blob<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, sha256: &str) -> Result<Option<Vec<u8>>, RepoError> {
+        Ok(self.lock().blobs.get(sha256).cloned())
+    }
+
+    fn gc_blobs<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError> {
+        let mut state = self.lock();
+        let live: BTreeSet<String> =
+            state.archives.values().flat_map(|a| a.attachments.iter().map(|at| at.sha256.clone())).collect();
+        let before = state.blobs.len();
+        state.blobs.retain(|hash, _| live.contains(hash));
+        Ok(before - state.blobs.len())
+    }
+}
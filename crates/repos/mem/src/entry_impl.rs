@@ -0,0 +1,67 @@
+/*
+File: crates/repos/mem/src/entry_impl.rs
+Purpose: EntryRepo impl for MemRepo.
+Inputs: rssify_core::{Entry, EntryId, EntryRepo, FeedId, RepoError}.
+Outputs: Entries in the shared state.
+Side effects: None beyond memory.
+Invariants: list_by_feed orders like the FS adapter: published_ts, updated_ts, then id.
+            delete and prune drop archive records with their entries.
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{Entry, EntryId, EntryPage, EntryQuery, EntryRepo, FeedId, RepoError, RetentionPolicy};
+
+impl EntryRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn get<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<Entry, RepoError> {
+        self.lock().entries.get(id).cloned().ok_or(RepoError::NotFound)
+    }
+
+    fn upsert<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, entry: &Entry) -> Result<(), RepoError> {
+        self.lock().entries.insert(entry.id.clone(), entry.clone());
+        Ok(())
+    }
+
+    fn list_by_feed<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<Vec<Entry>, RepoError> {
+        let mut out: Vec<Entry> = self.lock().entries.values().filter(|e| &e.feed == feed).cloned().collect();
+        // Map order already breaks ties by id.
+        out.sort_by_key(|e| (e.published_ts, e.updated_ts));
+        Ok(out)
+    }
+
+    fn count_by_feed<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<usize, RepoError> {
+        Ok(self.lock().entries.values().filter(|e| &e.feed == feed).count())
+    }
+
+    fn query<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, query: &EntryQuery) -> Result<EntryPage, RepoError> {
+        query.validate()?;
+        let state = self.lock();
+        query.page(state.entries.values().filter(|e| query.matches(e) && query.is_past_cursor(e)).cloned())
+    }
+
+    fn delete<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<(), RepoError> {
+        let mut state = self.lock();
+        state.entries.remove(id).ok_or(RepoError::NotFound)?;
+        state.archives.remove(id);
+        Ok(())
+    }
+
+    fn prune<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<Vec<EntryId>, RepoError> {
+        let mut state = self.lock();
+        let of_feed: Vec<Entry> = state.entries.values().filter(|e| &e.feed == feed).cloned().collect();
+        let expired = policy.expired(&of_feed, now);
+        for id in &expired {
+            state.entries.remove(id);
+            state.archives.remove(id);
+        }
+        Ok(expired)
+    }
+}
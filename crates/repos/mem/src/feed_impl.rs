@@ -0,0 +1,51 @@
+/*
+File: crates/repos/mem/src/feed_impl.rs
+Purpose: FeedRepo impl for MemRepo.
+Inputs: rssify_core::{Feed, FeedId, FeedRepo, LastBlob, RepoError}.
+Outputs: Feeds and last blobs in the shared state.
+Side effects: None beyond memory.
+Invariants: list returns feeds ordered by id; delete cascades to entries, archives and schedule
+            state under one lock.
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{Feed, FeedId, FeedRepo, LastBlob, RepoError};
+
+impl FeedRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn get<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Feed, RepoError> {
+        self.lock().feeds.get(id).cloned().ok_or(RepoError::NotFound)
+    }
+
+    fn put<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &Feed) -> Result<(), RepoError> {
+        self.lock().feeds.insert(feed.id.clone(), feed.clone());
+        Ok(())
+    }
+
+    fn list<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>) -> Result<Vec<Feed>, RepoError> {
+        Ok(self.lock().feeds.values().cloned().collect())
+    }
+
+    fn last_blob<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Option<LastBlob>, RepoError> {
+        Ok(self.lock().last_blobs.get(id).cloned())
+    }
+
+    fn put_last_blob<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &FeedId, blob: &LastBlob) -> Result<(), RepoError> {
+        self.lock().last_blobs.insert(id.clone(), blob.clone());
+        Ok(())
+    }
+
+    fn delete<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<(), RepoError> {
+        let mut state = self.lock();
+        let had_feed = state.feeds.remove(id).is_some();
+        let had_blob = state.last_blobs.remove(id).is_some();
+        let had_schedule = state.schedule.remove(id).is_some();
+        let (entries, archives) = (state.entries.len(), state.archives.len());
+        state.entries.retain(|_, e| &e.feed != id);
+        state.archives.retain(|_, a| &a.feed != id);
+        let had_children = entries != state.entries.len() || archives != state.archives.len();
+        if had_feed || had_blob || had_schedule || had_children { Ok(()) } else { Err(RepoError::NotFound) }
+    }
+}
@@ -0,0 +1,23 @@
+/*
+File: crates/repos/mem/src/lib.rs
+Purpose: Module glue and public re-exports for the in-memory repository adapter.
+Inputs: rssify_core traits and types.
+Outputs: Public MemRepo, MemTx and Snapshot types; trait impls are in submodules.
+Side effects: None here.
+Invariants:
+ - Keep this file minimal and under 200 LOC.
+ - All heavy logic lives in dedicated modules.
+*/
+
+mod tx;
+mod repo;
+mod feed_impl;
+mod entry_impl;
+mod schedule_impl;
+mod run_impl;
+mod blob_impl;
+mod archive_impl;
+mod search_impl;
+
+pub use repo::{MemRepo, ScheduleState, Snapshot};
+pub use tx::MemTx;
@@ -0,0 +1,84 @@
+/*
+File: crates/repos/mem/src/repo.rs
+Purpose: MemRepo state behind a mutex, plus JSON snapshot export.
+Inputs: rssify_core domain records.
+Outputs: MemRepo; Snapshot (the whole store as one serde value); ScheduleState.
+Side effects: write_snapshot writes one JSON file (temp file, then rename).
+Invariants:
+ - All trait calls take the lock for the duration of one call; a poisoned lock is recovered,
+   since every call leaves the state consistent before it can panic.
+ - Maps are ordered, so snapshots of equal stores are byte-identical.
+ - Like the FS adapter, entries and schedule rows are accepted for feeds that are not stored.
+*/
+
+use rssify_core::{ArchivedEntry, Entry, EntryId, FailureStreak, Feed, FeedId, LastBlob, MirrorHealth, RepoError, RunManifest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Scheduling facts kept per feed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleState {
+    pub last_ok_ts: Option<i64>,
+    pub failures: FailureStreak,
+    pub mirrors: MirrorHealth,
+}
+
+/// Everything a MemRepo holds; `MemRepo::snapshot` exports it and `from_snapshot` seeds a repo.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub feeds: BTreeMap<FeedId, Feed>,
+    #[serde(default)]
+    pub last_blobs: BTreeMap<FeedId, LastBlob>,
+    #[serde(default)]
+    pub entries: BTreeMap<EntryId, Entry>,
+    #[serde(default)]
+    pub schedule: BTreeMap<FeedId, ScheduleState>,
+    #[serde(default)]
+    pub runs: BTreeMap<String, RunManifest>,
+    #[serde(default)]
+    pub blobs: BTreeMap<String, Vec<u8>>,
+    #[serde(default)]
+    pub archives: BTreeMap<EntryId, ArchivedEntry>,
+}
+
+/// Repository kept entirely in process memory; shareable across threads by reference.
+#[derive(Debug, Default)]
+pub struct MemRepo {
+    state: Mutex<Snapshot>,
+}
+
+impl MemRepo {
+    /// Empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store pre-filled with `snapshot` (e.g. one exported by an earlier run).
+    pub fn from_snapshot(snapshot: Snapshot) -> Self {
+        Self { state: Mutex::new(snapshot) }
+    }
+
+    /// Copy of the current contents.
+    pub fn snapshot(&self) -> Snapshot {
+        self.lock().clone()
+    }
+
+    /// Write the current contents as pretty JSON to `path` (parent directories are created).
+    pub fn write_snapshot(&self, path: impl AsRef<Path>) -> Result<(), RepoError> {
+        let path = path.as_ref();
+        let json = serde_json::to_vec_pretty(&*self.lock()).map_err(|e| RepoError::Ser(e.to_string()))?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| RepoError::Backend(e.to_string()))?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json).and_then(|()| fs::rename(&tmp, path)).map_err(|e| RepoError::Backend(e.to_string()))
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, Snapshot> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,44 @@
+/*
+File: crates/repos/mem/src/run_impl.rs
+Purpose: RunRepo impl for MemRepo (fetch run manifests plus per-feed checkpoints).
+Inputs: rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo}.
+Outputs: Run manifests in the shared state, checkpoints applied in place.
+Side effects: None beyond memory.
+Invariants: record_run_feed on an unknown run is NotFound (there is no log to replay later).
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo};
+
+impl RunRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn put_run<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, run: &RunManifest) -> Result<(), RepoError> {
+        self.lock().runs.insert(run.run_id.clone(), run.clone());
+        Ok(())
+    }
+
+    fn record_run_feed<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        run_id: &str,
+        feed: &str,
+        status: FeedRunStatus,
+    ) -> Result<(), RepoError> {
+        self.lock().runs.get_mut(run_id).ok_or(RepoError::NotFound)?.mark(feed, status);
+        Ok(())
+    }
+
+    fn get_run<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, run_id: &str) -> Result<RunManifest, RepoError> {
+        self.lock().runs.get(run_id).cloned().ok_or(RepoError::NotFound)
+    }
+
+    fn latest_incomplete_run<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>) -> Result<Option<RunManifest>, RepoError> {
+        let state = self.lock();
+        let latest = state.runs.values().filter(|r| r.finished_at.is_none()).max_by(|a, b| {
+            a.started_at.cmp(&b.started_at).then_with(|| a.run_id.cmp(&b.run_id))
+        });
+        Ok(latest.cloned())
+    }
+}
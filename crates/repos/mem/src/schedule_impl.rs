@@ -0,0 +1,53 @@
+/*
+File: crates/repos/mem/src/schedule_impl.rs
+Purpose: ScheduleRepo impl for MemRepo.
+Inputs: rssify_core::{FailureStreak, FeedId, MirrorHealth, RepoError, ScheduleRepo}.
+Outputs: One ScheduleState per feed in the shared state.
+Side effects: None beyond memory.
+Invariants: Reads of unknown feeds return the defaults (None, empty streak, empty health).
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{FailureStreak, FeedId, MirrorHealth, RepoError, ScheduleRepo};
+
+impl ScheduleRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn last_ok_fetch_ts<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<Option<i64>, RepoError> {
+        Ok(self.lock().schedule.get(feed).and_then(|s| s.last_ok_ts))
+    }
+
+    fn record_fetch_ts<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId, ts: i64) -> Result<(), RepoError> {
+        self.lock().schedule.entry(feed.clone()).or_default().last_ok_ts = Some(ts);
+        Ok(())
+    }
+
+    fn failure_streak<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<FailureStreak, RepoError> {
+        Ok(self.lock().schedule.get(feed).map(|s| s.failures).unwrap_or_default())
+    }
+
+    fn record_failure_streak<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        streak: &FailureStreak,
+    ) -> Result<(), RepoError> {
+        self.lock().schedule.entry(feed.clone()).or_default().failures = *streak;
+        Ok(())
+    }
+
+    fn mirror_health<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<MirrorHealth, RepoError> {
+        Ok(self.lock().schedule.get(feed).map(|s| s.mirrors.clone()).unwrap_or_default())
+    }
+
+    fn record_mirror_health<'a>(
+        &'a self,
+        _tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        health: &MirrorHealth,
+    ) -> Result<(), RepoError> {
+        self.lock().schedule.entry(feed.clone()).or_default().mirrors = health.clone();
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+/*
+File: crates/repos/mem/src/search_impl.rs
+Purpose: SearchRepo impl for MemRepo.
+Inputs: rssify_core::{MemIndex, SearchQuery, SearchRepo, rank}.
+Outputs: BM25-ranked hits over the stored entries.
+Side effects: None beyond memory.
+Invariants: No index is kept between calls: each search indexes the current entries under the
+            lock, so results always match the store and snapshots carry no index. reindex has
+            nothing to rebuild and reports the entry count.
+*/
+
+use crate::repo::MemRepo;
+use crate::tx::MemTx;
+use rssify_core::{rank, MemIndex, RepoError, SearchHit, SearchQuery, SearchRepo};
+
+impl SearchRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn search<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>, query: &SearchQuery, limit: usize) -> Result<Vec<SearchHit>, RepoError> {
+        let index = MemIndex::from_entries(self.lock().entries.values());
+        rank(&index, query, limit)
+    }
+
+    fn reindex<'a>(&'a self, _tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError> {
+        Ok(self.lock().entries.len())
+    }
+}
@@ -0,0 +1,36 @@
+/*
+File: crates/repos/mem/src/tx.rs
+Purpose: Transaction/context handle for the in-memory adapter.
+Inputs: rssify_core::Tx trait.
+Outputs: MemTx implementing Tx, TxRepo for MemRepo.
+Side effects: None.
+Invariants: Each call locks the repo on its own; a MemTx groups nothing and is never active.
+*/
+
+use crate::repo::MemRepo;
+use rssify_core::{RepoError, Tx as TxTrait, TxRepo};
+
+#[derive(Debug, Clone, Default)]
+pub struct MemTx;
+
+impl TxTrait for MemTx {
+    fn is_active(&self) -> bool {
+        false
+    }
+}
+
+impl TxRepo for MemRepo {
+    type Tx<'a> = MemTx where Self: 'a;
+
+    fn begin_tx(&self) -> Result<MemTx, RepoError> {
+        Ok(MemTx)
+    }
+
+    fn commit_tx(_tx: MemTx) -> Result<(), RepoError> {
+        Ok(())
+    }
+
+    fn reborrow<'s, 'r: 's>(tx: &'s MemTx) -> &'s MemTx {
+        tx
+    }
+}
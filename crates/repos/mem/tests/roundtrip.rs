@@ -0,0 +1,62 @@
+/*
+File: crates/repos/mem/tests/roundtrip.rs
+Purpose: Round-trip tests for MemRepo: the shared conformance suite plus snapshot export and
+         shared use across threads.
+Inputs: MemRepo; rssify_core domain types and traits.
+Outputs: Asserts on RepoError results; snapshot tests write JSON under a unique temp dir.
+Side effects: Snapshot files in a temp dir (not deleted).
+Invariants:
+ - No network.
+ - Each temp directory name uses pid+nanos to avoid collisions.
+*/
+
+#[macro_use]
+mod support;
+
+use rssify_core::{BlobRepo, EntryId, EntryRepo, FeedRepo, ScheduleRepo};
+use rssify_repo_mem::{MemRepo, Snapshot};
+use std::fs;
+use support::{entry, feed, temp_root};
+
+conformance_suite!(MemRepo::new());
+
+
+#[test]
+fn snapshot_export_restores_an_equal_store() {
+    let repo = MemRepo::new();
+    let f = feed("https://ex.com/rss");
+    FeedRepo::put(&repo, None, &f).expect("put feed");
+    EntryRepo::upsert(&repo, None, &entry(&f.id, "e1")).expect("upsert");
+    ScheduleRepo::record_fetch_ts(&repo, None, &f.id, 42).expect("record ts");
+    BlobRepo::put_blob(&repo, None, b"\x00\xffbytes").expect("put blob");
+
+    let path = temp_root().join("nested").join("snapshot.json");
+    repo.write_snapshot(&path).expect("write snapshot");
+    let read: Snapshot = serde_json::from_slice(&fs::read(&path).expect("read file")).expect("parse");
+    assert_eq!(read, repo.snapshot());
+    assert_eq!(read.feeds[&f.id], f);
+
+    let restored = MemRepo::from_snapshot(read);
+    assert_eq!(EntryRepo::get(&restored, None, &EntryId::new("e1")).expect("entry"), entry(&f.id, "e1"));
+    assert_eq!(ScheduleRepo::last_ok_fetch_ts(&restored, None, &f.id).expect("ts"), Some(42));
+    assert_eq!(MemRepo::new().snapshot(), Snapshot::default());
+}
+
+#[test]
+fn one_repo_is_shared_across_threads() {
+    let repo = MemRepo::new();
+    let f = feed("https://ex.com/rss");
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let (repo, f) = (&repo, &f);
+            s.spawn(move || {
+                for i in 0..25 {
+                    EntryRepo::upsert(repo, None, &entry(&f.id, &format!("e{t}-{i}"))).expect("upsert");
+                }
+            });
+        }
+    });
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).expect("list").len(), 100);
+    assert!(matches!(FeedRepo::get(&repo, None, &f.id), Err(rssify_core::RepoError::NotFound)));
+    assert!(!rssify_core::Tx::is_active(&rssify_repo_mem::MemTx));
+}
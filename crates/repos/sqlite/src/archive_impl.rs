@@ -0,0 +1,49 @@
+/*
+File: crates/repos/sqlite/src/archive_impl.rs
+Purpose: ArchiveRepo impl for SqliteRepo.
+Inputs: rssify_core::{ArchiveRepo, ArchivedEntry, EntryId, FeedId, RepoError}.
+Outputs: archives rows (the ArchivedEntry as JSON, indexed by feed).
+Side effects: SQLite I/O.
+*/
+
+use crate::repo::SqliteRepo;
+use crate::tx::SqliteTx;
+use crate::util::{backend, from_json, to_json};
+use rssify_core::{ArchiveRepo, ArchivedEntry, EntryId, FeedId, RepoError};
+use rusqlite::{params, OptionalExtension};
+
+impl ArchiveRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn put_archive<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, archived: &ArchivedEntry) -> Result<(), RepoError> {
+        self.conn(tx)
+            .execute(
+                "INSERT OR REPLACE INTO archives (entry_id, feed_id, doc) VALUES (?1, ?2, ?3)",
+                params![archived.entry.as_str(), archived.feed.as_str(), to_json(archived)?],
+            )
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    fn archive<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, entry: &EntryId) -> Result<Option<ArchivedEntry>, RepoError> {
+        let doc: Option<String> = self
+            .conn(tx)
+            .query_row("SELECT doc FROM archives WHERE entry_id = ?1", [entry.as_str()], |r| r.get(0))
+            .optional()
+            .map_err(backend)?;
+        doc.as_deref().map(from_json).transpose()
+    }
+
+    fn archives_by_feed<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+    ) -> Result<Vec<ArchivedEntry>, RepoError> {
+        let conn = self.conn(tx);
+        let mut stmt = conn
+            .prepare("SELECT doc FROM archives WHERE feed_id = ?1 ORDER BY entry_id")
+            .map_err(backend)?;
+        let rows = stmt.query_map([feed.as_str()], |r| r.get::<_, String>(0)).map_err(backend)?;
+        rows.map(|r| from_json(&r.map_err(backend)?)).collect()
+    }
+}
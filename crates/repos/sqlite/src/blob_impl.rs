@@ -0,0 +1,66 @@
+/*
+File: crates/repos/sqlite/src/blob_impl.rs
+Purpose: BlobRepo impl for SqliteRepo (sha256 content-addressed rows).
+Inputs: rssify_core::{BlobRepo, RepoError}; sha2 for hashing.
+Outputs: blobs rows keyed by lowercase sha256 hex.
+Side effects: SQLite I/O.
+Invariants: A row's key is the sha256 of its bytes; existing rows are never rewritten.
+            Entry content stays inline in entries.content (a BLOB column, so nothing is bloated);
+            only archive attachments keep a blob row alive.
+*/
+
+use crate::repo::SqliteRepo;
+use crate::tx::SqliteTx;
+use crate::util::{backend, from_json};
+use rssify_core::{ArchivedEntry, BlobRepo, RepoError};
+use rusqlite::{params, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().fold(String::with_capacity(64), |mut s, b| {
+        let _ = write!(s, "{b:02x}");
+        s
+    })
+}
+
+impl BlobRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn put_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, bytes: &[u8]) -> Result<String, RepoError> {
+        let hash = sha256_hex(bytes);
+        self.conn(tx)
+            .execute("INSERT OR IGNORE INTO blobs (sha256, bytes) VALUES (?1, ?2)", params![hash, bytes])
+            .map_err(backend)?;
+        Ok(hash)
+    }
+
+    fn get_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, sha256: &str) -> Result<Option<Vec<u8>>, RepoError> {
+        self.conn(tx)
+            .query_row("SELECT bytes FROM blobs WHERE sha256 = ?1", [sha256], |r| r.get(0))
+            .optional()
+            .map_err(backend)
+    }
+
+    fn gc_blobs<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError> {
+        self.in_tx(tx, |conn| {
+            let mut live = BTreeSet::new();
+            let mut stmt = conn.prepare("SELECT doc FROM archives").map_err(backend)?;
+            let mut rows = stmt.query([]).map_err(backend)?;
+            while let Some(row) = rows.next().map_err(backend)? {
+                let archived: ArchivedEntry = from_json(&row.get::<_, String>(0).map_err(backend)?)?;
+                live.extend(archived.attachments.into_iter().map(|a| a.sha256));
+            }
+            let hashes: Vec<String> = conn
+                .prepare("SELECT sha256 FROM blobs")
+                .and_then(|mut s| s.query_map([], |r| r.get(0))?.collect())
+                .map_err(backend)?;
+            let mut removed = 0;
+            for hash in hashes.iter().filter(|h| !live.contains(*h)) {
+                removed += conn.execute("DELETE FROM blobs WHERE sha256 = ?1", [hash]).map_err(backend)?;
+            }
+            Ok(removed)
+        })
+    }
+}
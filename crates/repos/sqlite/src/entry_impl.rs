@@ -0,0 +1,218 @@
+/*
+File: crates/repos/sqlite/src/entry_impl.rs
+Purpose: EntryRepo impl for SqliteRepo.
+Inputs: rssify_core::{Entry, EntryId, EntryRepo, FeedId, RepoError}.
+Outputs: Rows in entries (content as a BLOB, enclosures, WARC ids and authors as JSON, starred
+         as 0/1).
+Side effects: SQLite I/O.
+Invariants: An entry's feed must exist (foreign key); violations surface as RepoError::Conflict.
+            list_by_feed orders like the FS adapter: published_ts, updated_ts, then id.
+            upsert rewrites the entry's search postings in the same transaction; delete and prune
+            drop archive rows with their entries, in one transaction (postings cascade).
+            query pages with keyset conditions on (COALESCE(published_ts, i64::MIN), id), never OFFSET.
+*/
+
+use crate::repo::SqliteRepo;
+use crate::search_impl::index_entry;
+use crate::tx::SqliteTx;
+use crate::util::{backend, enum_from, enum_name, from_json, to_json};
+use rssify_core::{
+    ContentBlob, Entry, EntryId, EntryOrder, EntryPage, EntryQuery, EntryRepo, FeedId, RepoError, RetentionPolicy,
+};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, Row};
+
+const ENTRY_COLUMNS: &str = "id, feed_id, url, title, published_ts, updated_ts, summary, content_kind, content, \
+                             content_text, enclosures, warc_records, starred, authors";
+
+/// Raw row; JSON and enum columns are decoded afterwards so their errors stay RepoError::Ser.
+struct EntryRow {
+    entry: Entry,
+    content_kind: Option<String>,
+    content: Option<Vec<u8>>,
+    enclosures: String,
+    warc_records: String,
+    authors: String,
+}
+
+fn entry_row(row: &Row<'_>) -> rusqlite::Result<EntryRow> {
+    Ok(EntryRow {
+        entry: Entry {
+            id: EntryId::new(row.get::<_, String>(0)?),
+            feed: FeedId::new(row.get::<_, String>(1)?),
+            url: row.get(2)?,
+            title: row.get(3)?,
+            published_ts: row.get(4)?,
+            updated_ts: row.get(5)?,
+            summary: row.get(6)?,
+            content: None,
+            content_text: row.get(9)?,
+            enclosures: Vec::new(),
+            warc_records: Vec::new(),
+            starred: row.get(12)?,
+            authors: Vec::new(),
+        },
+        content_kind: row.get(7)?,
+        content: row.get(8)?,
+        enclosures: row.get(10)?,
+        warc_records: row.get(11)?,
+        authors: row.get(13)?,
+    })
+}
+
+fn decode(row: EntryRow) -> Result<Entry, RepoError> {
+    let mut entry = row.entry;
+    if let (Some(kind), Some(bytes)) = (row.content_kind, row.content) {
+        entry.content = Some(ContentBlob { kind: enum_from(&kind)?, bytes });
+    }
+    entry.enclosures = from_json(&row.enclosures)?;
+    entry.warc_records = from_json(&row.warc_records)?;
+    entry.authors = from_json(&row.authors)?;
+    Ok(entry)
+}
+
+/// Call `f` with every stored entry, streaming rows rather than loading them all.
+pub(crate) fn for_each_entry(conn: &Connection, mut f: impl FnMut(Entry) -> Result<(), RepoError>) -> Result<(), RepoError> {
+    let mut stmt = conn.prepare(&format!("SELECT {ENTRY_COLUMNS} FROM entries ORDER BY id")).map_err(backend)?;
+    let mut rows = stmt.query([]).map_err(backend)?;
+    while let Some(row) = rows.next().map_err(backend)? {
+        f(decode(entry_row(row).map_err(backend)?)?)?;
+    }
+    Ok(())
+}
+
+impl EntryRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn get<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<Entry, RepoError> {
+        let sql = format!("SELECT {ENTRY_COLUMNS} FROM entries WHERE id = ?1");
+        decode(self.conn(tx).query_row(&sql, [id.as_str()], entry_row).map_err(backend)?)
+    }
+
+    fn upsert<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, entry: &Entry) -> Result<(), RepoError> {
+        let kind = entry.content.as_ref().map(|c| enum_name(&c.kind)).transpose()?;
+        self.in_tx(tx, |conn| {
+            conn.execute(
+                &format!(
+                    "INSERT INTO entries ({ENTRY_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+                     ON CONFLICT(id) DO UPDATE SET feed_id = excluded.feed_id, url = excluded.url,
+                       title = excluded.title, published_ts = excluded.published_ts,
+                       updated_ts = excluded.updated_ts, summary = excluded.summary,
+                       content_kind = excluded.content_kind, content = excluded.content,
+                       content_text = excluded.content_text, enclosures = excluded.enclosures,
+                       warc_records = excluded.warc_records, starred = excluded.starred,
+                       authors = excluded.authors"
+                ),
+                params![
+                    entry.id.as_str(),
+                    entry.feed.as_str(),
+                    entry.url,
+                    entry.title,
+                    entry.published_ts,
+                    entry.updated_ts,
+                    entry.summary,
+                    kind,
+                    entry.content.as_ref().map(|c| &c.bytes),
+                    entry.content_text,
+                    to_json(&entry.enclosures)?,
+                    to_json(&entry.warc_records)?,
+                    entry.starred,
+                    to_json(&entry.authors)?,
+                ],
+            )
+            .map_err(backend)?;
+            index_entry(conn, entry)
+        })
+    }
+
+    fn list_by_feed<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<Vec<Entry>, RepoError> {
+        let conn = self.conn(tx);
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM entries WHERE feed_id = ?1 ORDER BY published_ts, updated_ts, id"
+            ))
+            .map_err(backend)?;
+        let rows = stmt.query_map([feed.as_str()], entry_row).map_err(backend)?;
+        rows.map(|r| decode(r.map_err(backend)?)).collect()
+    }
+
+    fn count_by_feed<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<usize, RepoError> {
+        let n: i64 = self
+            .conn(tx)
+            .query_row("SELECT COUNT(*) FROM entries WHERE feed_id = ?1", [feed.as_str()], |r| r.get(0))
+            .map_err(backend)?;
+        Ok(n as usize)
+    }
+
+    fn query<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, query: &EntryQuery) -> Result<EntryPage, RepoError> {
+        query.validate()?;
+        // ?1 is the sort key of undated entries, so COALESCE matches EntryQuery::sort_key.
+        let mut args: Vec<Value> = vec![Value::Integer(i64::MIN)];
+        let mut clauses: Vec<String> = Vec::new();
+        let mut arg = |v: Value| {
+            args.push(v);
+            format!("?{}", args.len())
+        };
+        if !query.feeds.is_empty() {
+            let ids: Vec<String> = query.feeds.iter().map(|f| arg(Value::Text(f.as_str().to_string()))).collect();
+            clauses.push(format!("feed_id IN ({})", ids.join(", ")));
+        }
+        if let Some(from) = query.published_from {
+            clauses.push(format!("published_ts >= {}", arg(Value::Integer(from))));
+        }
+        if let Some(until) = query.published_until {
+            clauses.push(format!("published_ts < {}", arg(Value::Integer(until))));
+        }
+        if let Some(needle) = &query.title_contains {
+            // lower() folds ASCII only, like EntryQuery::matches.
+            clauses.push(format!("instr(lower(title), {}) > 0", arg(Value::Text(needle.to_ascii_lowercase()))));
+        }
+        let (cmp, dir) = match query.order {
+            EntryOrder::NewestFirst => ("<", "DESC"),
+            EntryOrder::OldestFirst => (">", "ASC"),
+        };
+        if let Some(cursor) = &query.cursor {
+            let (key, id) = cursor.position();
+            let (key, id) = (arg(Value::Integer(key)), arg(Value::Text(id.as_str().to_string())));
+            clauses.push(format!("(COALESCE(published_ts, ?1), id) {cmp} ({key}, {id})"));
+        }
+        let limit = arg(Value::Integer(i64::try_from(query.limit.saturating_add(1)).unwrap_or(i64::MAX)));
+        let filter = if clauses.is_empty() { String::new() } else { format!("WHERE {}", clauses.join(" AND ")) };
+        let sql = format!(
+            "SELECT {ENTRY_COLUMNS} FROM entries {filter} ORDER BY COALESCE(published_ts, ?1) {dir}, id {dir} LIMIT {limit}"
+        );
+        let conn = self.conn(tx);
+        let mut stmt = conn.prepare(&sql).map_err(backend)?;
+        let rows = stmt.query_map(params_from_iter(args), entry_row).map_err(backend)?;
+        let rows = rows.map(|r| decode(r.map_err(backend)?)).collect::<Result<Vec<_>, _>>()?;
+        Ok(query.finish(rows))
+    }
+
+    fn delete<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &EntryId) -> Result<(), RepoError> {
+        self.in_tx(tx, |conn| {
+            conn.execute("DELETE FROM archives WHERE entry_id = ?1", [id.as_str()]).map_err(backend)?;
+            match conn.execute("DELETE FROM entries WHERE id = ?1", [id.as_str()]).map_err(backend)? {
+                0 => Err(RepoError::NotFound),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn prune<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<Vec<EntryId>, RepoError> {
+        let expired = policy.expired(&EntryRepo::list_by_feed(self, tx, feed)?, now);
+        self.in_tx(tx, |conn| {
+            for id in &expired {
+                conn.execute("DELETE FROM archives WHERE entry_id = ?1", [id.as_str()]).map_err(backend)?;
+                conn.execute("DELETE FROM entries WHERE id = ?1", [id.as_str()]).map_err(backend)?;
+            }
+            Ok(())
+        })?;
+        Ok(expired)
+    }
+}
@@ -0,0 +1,145 @@
+/*
+File: crates/repos/sqlite/src/feed_impl.rs
+Purpose: FeedRepo impl for SqliteRepo.
+Inputs: rssify_core::{Feed, FeedId, FeedRepo, LastBlob, RepoError}.
+Outputs: Rows in feeds (settings and deactivation as JSON) and last_blobs (raw bytes).
+Side effects: SQLite I/O.
+Invariants: put upserts in place; it never deletes the feed row, so entries are not cascaded away.
+            delete removes the feed's rows from every table in one transaction.
+*/
+
+use crate::repo::SqliteRepo;
+use crate::tx::SqliteTx;
+use crate::util::{backend, enum_from, enum_name, from_json, to_json};
+use rssify_core::{ContentBlob, Feed, FeedId, FeedRepo, LastBlob, RepoError};
+use rusqlite::{params, OptionalExtension, Row};
+
+const FEED_COLUMNS: &str = "id, url, title, site_url, etag, last_modified, active, deactivated, settings";
+
+fn feed_from_row(row: &Row<'_>) -> rusqlite::Result<(Feed, Option<String>, String)> {
+    let feed = Feed {
+        id: FeedId::new(row.get::<_, String>(0)?),
+        url: row.get(1)?,
+        title: row.get(2)?,
+        site_url: row.get(3)?,
+        etag: row.get(4)?,
+        last_modified: row.get(5)?,
+        active: row.get(6)?,
+        deactivated: None,
+        settings: Default::default(),
+    };
+    Ok((feed, row.get(7)?, row.get(8)?))
+}
+
+/// Fill the JSON columns read by `feed_from_row`.
+fn decode(parts: (Feed, Option<String>, String)) -> Result<Feed, RepoError> {
+    let (mut feed, deactivated, settings) = parts;
+    feed.deactivated = deactivated.as_deref().map(from_json).transpose()?;
+    feed.settings = from_json(&settings)?;
+    Ok(feed)
+}
+
+impl FeedRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn get<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Feed, RepoError> {
+        let sql = format!("SELECT {FEED_COLUMNS} FROM feeds WHERE id = ?1");
+        decode(self.conn(tx).query_row(&sql, [id.as_str()], feed_from_row).map_err(backend)?)
+    }
+
+    fn put<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &Feed) -> Result<(), RepoError> {
+        let deactivated = feed.deactivated.as_ref().map(to_json).transpose()?;
+        self.conn(tx)
+            .execute(
+                &format!(
+                    "INSERT INTO feeds ({FEED_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                     ON CONFLICT(id) DO UPDATE SET url = excluded.url, title = excluded.title,
+                       site_url = excluded.site_url, etag = excluded.etag, last_modified = excluded.last_modified,
+                       active = excluded.active, deactivated = excluded.deactivated, settings = excluded.settings"
+                ),
+                params![
+                    feed.id.as_str(),
+                    feed.url,
+                    feed.title,
+                    feed.site_url,
+                    feed.etag,
+                    feed.last_modified,
+                    feed.active,
+                    deactivated,
+                    to_json(&feed.settings)?,
+                ],
+            )
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    fn list<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<Vec<Feed>, RepoError> {
+        let conn = self.conn(tx);
+        let mut stmt = conn.prepare(&format!("SELECT {FEED_COLUMNS} FROM feeds ORDER BY id")).map_err(backend)?;
+        let rows = stmt.query_map([], feed_from_row).map_err(backend)?;
+        rows.map(|r| decode(r.map_err(backend)?)).collect()
+    }
+
+    fn last_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<Option<LastBlob>, RepoError> {
+        let row = self
+            .conn(tx)
+            .query_row(
+                "SELECT kind, headers, fetched_ts, warc_record, bytes FROM last_blobs WHERE feed_id = ?1",
+                [id.as_str()],
+                |r| {
+                    Ok((
+                        r.get::<_, String>(0)?,
+                        r.get::<_, String>(1)?,
+                        r.get::<_, i64>(2)?,
+                        r.get::<_, Option<String>>(3)?,
+                        r.get::<_, Vec<u8>>(4)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(backend)?;
+        let Some((kind, headers, fetched_ts, warc_record, bytes)) = row else {
+            return Ok(None);
+        };
+        Ok(Some(LastBlob {
+            blob: ContentBlob { kind: enum_from(&kind)?, bytes },
+            headers: from_json(&headers)?,
+            fetched_ts,
+            warc_record,
+        }))
+    }
+
+    fn put_last_blob<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId, blob: &LastBlob) -> Result<(), RepoError> {
+        self.conn(tx)
+            .execute(
+                "INSERT OR REPLACE INTO last_blobs (feed_id, kind, headers, fetched_ts, warc_record, bytes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    id.as_str(),
+                    enum_name(&blob.blob.kind)?,
+                    to_json(&blob.headers)?,
+                    blob.fetched_ts,
+                    blob.warc_record,
+                    blob.blob.bytes,
+                ],
+            )
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    fn delete<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, id: &FeedId) -> Result<(), RepoError> {
+        self.in_tx(tx, |conn| {
+            let mut removed = 0;
+            for sql in [
+                "DELETE FROM archives WHERE feed_id = ?1",
+                "DELETE FROM entries WHERE feed_id = ?1",
+                "DELETE FROM last_blobs WHERE feed_id = ?1",
+                "DELETE FROM schedule WHERE feed_id = ?1",
+                "DELETE FROM feeds WHERE id = ?1",
+            ] {
+                removed += conn.execute(sql, [id.as_str()]).map_err(backend)?;
+            }
+            if removed == 0 { Err(RepoError::NotFound) } else { Ok(()) }
+        })
+    }
+}
@@ -0,0 +1,28 @@
+/*
+File: crates/repos/sqlite/src/lib.rs
+Purpose: Module glue and public re-exports for the SQLite repository adapter.
+Inputs: rssify_core traits and types.
+Outputs: Public SqliteRepo and SqliteTx types, the schema version and migrations; trait impls (search included) are in submodules.
+Side effects: None here.
+Invariants:
+ - Keep this file minimal and under 200 LOC.
+ - All heavy logic lives in dedicated modules.
+*/
+
+mod tx;
+mod util;
+mod schema;
+mod migrate;
+mod repo;
+mod feed_impl;
+mod entry_impl;
+mod schedule_impl;
+mod run_impl;
+mod blob_impl;
+mod archive_impl;
+mod search_impl;
+
+pub use migrate::{backup, migrate, repo_version};
+pub use repo::SqliteRepo;
+pub use schema::SCHEMA_VERSION;
+pub use tx::SqliteTx;
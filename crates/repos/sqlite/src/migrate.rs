@@ -0,0 +1,74 @@
+/*
+File: crates/repos/sqlite/src/migrate.rs
+Purpose: Ordered upgrade steps for SQLite repos, run through rssify_core::run_migrations, plus the
+         pre-migration backup.
+Inputs: Database path.
+Outputs: MigrationReport; meta.repo_version updated in the same transaction as the steps.
+Side effects: Schema/data changes per step; backup writes a compacted copy via VACUUM INTO.
+Invariants:
+ - All pending steps and their version bumps run in one transaction, so a crash leaves the
+   database at its original version and the whole run is simply repeated.
+ - Only the version check runs before migration; PRAGMAs and DDL belong to SqliteRepo::open.
+*/
+
+use crate::schema::{stored_version, write_version, SCHEMA_VERSION, SEARCH_DDL};
+use crate::search_impl::rebuild;
+use crate::util::backend;
+use rssify_core::{run_migrations, Migration, MigrationReport, RepoError};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Upgrade steps, oldest first; append one (and bump SCHEMA_VERSION) per schema change.
+/// Version 1 is the initial schema.
+const STEPS: &[Migration<Connection>] = &[
+    Migration { to: 2, name: "entries.starred", apply: add_starred },
+    Migration { to: 3, name: "entries.authors, search index", apply: add_search },
+];
+
+/// Version 1 -> 2: the starred flag retention never prunes.
+fn add_starred(conn: &Connection) -> Result<String, RepoError> {
+    conn.execute_batch("ALTER TABLE entries ADD COLUMN starred INTEGER NOT NULL DEFAULT 0").map_err(backend)?;
+    Ok("added entries.starred".into())
+}
+
+/// Version 2 -> 3: entry authors and the full-text index, built from the existing entries.
+fn add_search(conn: &Connection) -> Result<String, RepoError> {
+    conn.execute_batch("ALTER TABLE entries ADD COLUMN authors TEXT NOT NULL DEFAULT '[]'").map_err(backend)?;
+    conn.execute_batch(SEARCH_DDL).map_err(backend)?;
+    let indexed = rebuild(conn)?;
+    Ok(format!("added entries.authors and the search index ({indexed} entries indexed)"))
+}
+
+/// `meta.repo_version` of the database at `path`; `None` when the file does not exist or holds
+/// no rssify schema yet.
+pub fn repo_version(path: &Path) -> Result<Option<u32>, RepoError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    stored_version(&Connection::open(path).map_err(backend)?)
+}
+
+/// Bring the database at `path` to `SCHEMA_VERSION`; a no-op for current or missing databases.
+pub fn migrate(path: &Path) -> Result<MigrationReport, RepoError> {
+    let Some(current) = repo_version(path)? else {
+        return Ok(MigrationReport { from: SCHEMA_VERSION, to: SCHEMA_VERSION, ..MigrationReport::default() });
+    };
+    let mut conn = Connection::open(path).map_err(backend)?;
+    let tx = conn.transaction().map_err(backend)?;
+    let report = run_migrations(&*tx, current, SCHEMA_VERSION, STEPS, write_version)?;
+    tx.commit().map_err(backend)?;
+    Ok(report)
+}
+
+/// Write a consistent copy of the database at `path` to `dest` (which must not exist yet).
+pub fn backup(path: &Path, dest: &Path) -> Result<(), RepoError> {
+    if dest.exists() {
+        return Err(RepoError::Conflict);
+    }
+    if let Some(parent) = dest.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| RepoError::Backend(e.to_string()))?;
+    }
+    let conn = Connection::open(path).map_err(backend)?;
+    conn.execute("VACUUM INTO ?1", [dest.to_string_lossy()]).map_err(backend)?;
+    Ok(())
+}
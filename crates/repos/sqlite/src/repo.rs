@@ -0,0 +1,71 @@
+/*
+File: crates/repos/sqlite/src/repo.rs
+Purpose: SqliteRepo struct, opening and tx creation.
+Inputs: Database path (or ":memory:") injected at construction.
+Outputs: SqliteRepo methods for connection access and transactions.
+Side effects: Opens/creates the database file and its schema.
+*/
+
+use crate::schema;
+use crate::tx::SqliteTx;
+use crate::util::backend;
+use rssify_core::RepoError;
+use rusqlite::{Connection, Transaction, TransactionBehavior};
+use std::path::Path;
+
+/// Repository over one SQLite connection. Calls without a tx autocommit.
+pub struct SqliteRepo {
+    pub(crate) conn: Connection,
+}
+
+impl std::fmt::Debug for SqliteRepo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteRepo").field("path", &self.conn.path()).finish()
+    }
+}
+
+impl SqliteRepo {
+    /// Open (creating if needed) the database at `path` and ensure the current schema.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, RepoError> {
+        if let Some(parent) = path.as_ref().parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| RepoError::Backend(e.to_string()))?;
+        }
+        let conn = Connection::open(path).map_err(backend)?;
+        schema::init(&conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Private in-memory database (tests, scratch runs).
+    pub fn open_in_memory() -> Result<Self, RepoError> {
+        let conn = Connection::open_in_memory().map_err(backend)?;
+        schema::init(&conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Begin a write transaction; uncommitted work is rolled back when it is dropped.
+    pub fn begin_tx(&self) -> Result<SqliteTx<'_>, RepoError> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate).map_err(backend)?;
+        Ok(SqliteTx { tx })
+    }
+
+    /// Connection to run a statement on: the transaction's when one is given.
+    pub(crate) fn conn<'a>(&'a self, tx: Option<&'a SqliteTx<'a>>) -> &'a Connection {
+        tx.map_or(&self.conn, |t| &t.tx)
+    }
+
+    /// Run `f` in `tx`, or in a transaction of its own committed on success, so multi-statement
+    /// changes (deletes, prunes) land together.
+    pub(crate) fn in_tx<'a, T>(
+        &'a self,
+        tx: Option<&'a SqliteTx<'a>>,
+        f: impl FnOnce(&Connection) -> Result<T, RepoError>,
+    ) -> Result<T, RepoError> {
+        if let Some(t) = tx {
+            return f(&t.tx);
+        }
+        let own = self.conn.unchecked_transaction().map_err(backend)?;
+        let out = f(&own)?;
+        own.commit().map_err(backend)?;
+        Ok(out)
+    }
+}
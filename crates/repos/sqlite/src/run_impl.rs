@@ -0,0 +1,80 @@
+/*
+File: crates/repos/sqlite/src/run_impl.rs
+Purpose: RunRepo impl for SqliteRepo (fetch run manifests plus per-feed checkpoints).
+Inputs: rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo}.
+Outputs: runs rows (manifest JSON) and run_feeds rows (latest status per feed).
+Side effects: SQLite I/O; each checkpoint is its own autocommitted statement unless a tx is given.
+Invariants: get_run applies checkpoints over the manifest in first-recorded order, like the FS log.
+            put_run updates in place, so recorded checkpoints survive a manifest rewrite.
+*/
+
+use crate::repo::SqliteRepo;
+use crate::tx::SqliteTx;
+use crate::util::{backend, enum_from, enum_name, from_json, to_json};
+use rssify_core::{FeedRunStatus, RepoError, RunManifest, RunRepo};
+use rusqlite::{params, OptionalExtension};
+
+impl RunRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn put_run<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, run: &RunManifest) -> Result<(), RepoError> {
+        self.conn(tx)
+            .execute(
+                "INSERT INTO runs (run_id, started_at, finished_at, manifest) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(run_id) DO UPDATE SET started_at = excluded.started_at,
+                   finished_at = excluded.finished_at, manifest = excluded.manifest",
+                params![run.run_id, run.started_at, run.finished_at, to_json(run)?],
+            )
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    fn record_run_feed<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        run_id: &str,
+        feed: &str,
+        status: FeedRunStatus,
+    ) -> Result<(), RepoError> {
+        self.conn(tx)
+            .execute(
+                "INSERT INTO run_feeds (run_id, feed_id, status) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(run_id, feed_id) DO UPDATE SET status = excluded.status",
+                params![run_id, feed, enum_name(&status)?],
+            )
+            .map_err(backend)?;
+        Ok(())
+    }
+
+    fn get_run<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, run_id: &str) -> Result<RunManifest, RepoError> {
+        let conn = self.conn(tx);
+        let manifest: String = conn
+            .query_row("SELECT manifest FROM runs WHERE run_id = ?1", [run_id], |r| r.get(0))
+            .map_err(backend)?;
+        let mut run: RunManifest = from_json(&manifest)?;
+        let mut stmt = conn
+            .prepare("SELECT feed_id, status FROM run_feeds WHERE run_id = ?1 ORDER BY rowid")
+            .map_err(backend)?;
+        let rows = stmt
+            .query_map([run_id], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))
+            .map_err(backend)?;
+        for row in rows {
+            let (feed, status) = row.map_err(backend)?;
+            run.mark(&feed, enum_from(&status)?);
+        }
+        Ok(run)
+    }
+
+    fn latest_incomplete_run<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<Option<RunManifest>, RepoError> {
+        let id: Option<String> = self
+            .conn(tx)
+            .query_row(
+                "SELECT run_id FROM runs WHERE finished_at IS NULL ORDER BY started_at DESC, run_id DESC LIMIT 1",
+                [],
+                |r| r.get(0),
+            )
+            .optional()
+            .map_err(backend)?;
+        id.map(|id| self.get_run(tx, &id)).transpose()
+    }
+}
@@ -0,0 +1,74 @@
+/*
+File: crates/repos/sqlite/src/schedule_impl.rs
+Purpose: ScheduleRepo impl for SqliteRepo.
+Inputs: rssify_core::{FailureStreak, FeedId, MirrorHealth, ScheduleRepo, RepoError}.
+Outputs: One schedule row per feed (last_ok_ts; failure streak and mirror health as JSON).
+Side effects: SQLite I/O.
+Invariants: Rows are keyed by feed id without a foreign key, matching the FS adapter, which keeps
+            schedule state for feeds that are not stored (yet).
+*/
+
+use crate::repo::SqliteRepo;
+use crate::tx::SqliteTx;
+use crate::util::{backend, from_json, to_json};
+use rssify_core::{FailureStreak, FeedId, MirrorHealth, RepoError, ScheduleRepo};
+use rusqlite::{Connection, OptionalExtension};
+
+/// Read one nullable schedule column (`column` is a fixed identifier, never user input).
+fn column<T: rusqlite::types::FromSql>(conn: &Connection, column: &str, feed: &FeedId) -> Result<Option<T>, RepoError> {
+    let v: Option<Option<T>> = conn
+        .query_row(&format!("SELECT {column} FROM schedule WHERE feed_id = ?1"), [feed.as_str()], |r| r.get(0))
+        .optional()
+        .map_err(backend)?;
+    Ok(v.flatten())
+}
+
+fn set_column<T: rusqlite::ToSql>(conn: &Connection, column: &str, feed: &FeedId, value: T) -> Result<(), RepoError> {
+    conn.execute(
+        &format!(
+            "INSERT INTO schedule (feed_id, {column}) VALUES (?1, ?2)
+             ON CONFLICT(feed_id) DO UPDATE SET {column} = excluded.{column}"
+        ),
+        rusqlite::params![feed.as_str(), value],
+    )
+    .map_err(backend)?;
+    Ok(())
+}
+
+impl ScheduleRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn last_ok_fetch_ts<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<Option<i64>, RepoError> {
+        column(self.conn(tx), "last_ok_ts", feed)
+    }
+
+    fn record_fetch_ts<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId, ts: i64) -> Result<(), RepoError> {
+        set_column(self.conn(tx), "last_ok_ts", feed, ts)
+    }
+
+    fn failure_streak<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<FailureStreak, RepoError> {
+        column::<String>(self.conn(tx), "failures", feed)?.map_or_else(|| Ok(FailureStreak::default()), |s| from_json(&s))
+    }
+
+    fn record_failure_streak<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        streak: &FailureStreak,
+    ) -> Result<(), RepoError> {
+        set_column(self.conn(tx), "failures", feed, to_json(streak)?)
+    }
+
+    fn mirror_health<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, feed: &FeedId) -> Result<MirrorHealth, RepoError> {
+        column::<String>(self.conn(tx), "mirrors", feed)?.map_or_else(|| Ok(MirrorHealth::default()), |s| from_json(&s))
+    }
+
+    fn record_mirror_health<'a>(
+        &'a self,
+        tx: Option<&'a Self::Tx<'a>>,
+        feed: &FeedId,
+        health: &MirrorHealth,
+    ) -> Result<(), RepoError> {
+        set_column(self.conn(tx), "mirrors", feed, to_json(health)?)
+    }
+}
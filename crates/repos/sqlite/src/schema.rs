@@ -0,0 +1,158 @@
+/*
+File: crates/repos/sqlite/src/schema.rs
+Purpose: Schema (v3) creation and version check for the SQLite adapter.
+Inputs: An open rusqlite::Connection.
+Outputs: Tables and indexes; meta.repo_version.
+Side effects: DDL on first open; PRAGMAs on every open.
+Invariants:
+ - DDL is idempotent (IF NOT EXISTS); opening never rewrites data.
+ - A database whose repo_version differs from SCHEMA_VERSION is refused: older ones need
+   `rssify migrate` (migrate.rs), newer ones a newer binary.
+*/
+
+use crate::util::backend;
+use rssify_core::RepoError;
+use rusqlite::{Connection, OptionalExtension};
+
+/// Schema version written to `meta.repo_version`.
+pub const SCHEMA_VERSION: u32 = 3;
+
+const DDL: &str = r"
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS feeds (
+    id TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    title TEXT,
+    site_url TEXT,
+    etag TEXT,
+    last_modified TEXT,
+    active INTEGER NOT NULL DEFAULT 1,
+    deactivated TEXT,
+    settings TEXT NOT NULL DEFAULT '{}'
+);
+CREATE TABLE IF NOT EXISTS last_blobs (
+    feed_id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,
+    headers TEXT NOT NULL,
+    fetched_ts INTEGER NOT NULL,
+    warc_record TEXT,
+    bytes BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS entries (
+    id TEXT PRIMARY KEY,
+    feed_id TEXT NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+    url TEXT,
+    title TEXT,
+    published_ts INTEGER,
+    updated_ts INTEGER,
+    summary TEXT,
+    content_kind TEXT,
+    content BLOB,
+    content_text TEXT,
+    enclosures TEXT NOT NULL DEFAULT '[]',
+    warc_records TEXT NOT NULL DEFAULT '[]',
+    starred INTEGER NOT NULL DEFAULT 0,
+    authors TEXT NOT NULL DEFAULT '[]',
+    created_ts INTEGER NOT NULL DEFAULT (strftime('%s','now'))
+);
+CREATE INDEX IF NOT EXISTS idx_entries_feed ON entries(feed_id, published_ts, updated_ts, id);
+CREATE TABLE IF NOT EXISTS schedule (
+    feed_id TEXT PRIMARY KEY,
+    last_ok_ts INTEGER,
+    failures TEXT,
+    mirrors TEXT
+);
+CREATE TABLE IF NOT EXISTS runs (
+    run_id TEXT PRIMARY KEY,
+    started_at INTEGER NOT NULL,
+    finished_at INTEGER,
+    manifest TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS run_feeds (
+    run_id TEXT NOT NULL REFERENCES runs(run_id) ON DELETE CASCADE,
+    feed_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    PRIMARY KEY (run_id, feed_id)
+);
+CREATE TABLE IF NOT EXISTS blobs (
+    sha256 TEXT PRIMARY KEY,
+    bytes BLOB NOT NULL
+);
+CREATE TABLE IF NOT EXISTS archives (
+    entry_id TEXT PRIMARY KEY,
+    feed_id TEXT NOT NULL,
+    doc TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_archives_feed ON archives(feed_id, entry_id);
+";
+
+/// Full-text index tables (search_impl.rs); deleting an entry cascades to its postings.
+pub(crate) const SEARCH_DDL: &str = r"
+CREATE TABLE IF NOT EXISTS search_docs (
+    entry_id TEXT PRIMARY KEY REFERENCES entries(id) ON DELETE CASCADE,
+    feed_id TEXT NOT NULL,
+    published_ts INTEGER,
+    len INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS search_postings (
+    term TEXT NOT NULL,
+    entry_id TEXT NOT NULL REFERENCES search_docs(entry_id) ON DELETE CASCADE,
+    positions TEXT NOT NULL,
+    PRIMARY KEY (term, entry_id)
+) WITHOUT ROWID;
+CREATE INDEX IF NOT EXISTS idx_search_postings_entry ON search_postings(entry_id);
+";
+
+/// Apply connection PRAGMAs, check the stored version and create missing tables.
+pub(crate) fn init(conn: &Connection) -> Result<(), RepoError> {
+    // journal_mode returns a row, so it cannot go through execute_batch's no-rows path.
+    conn.query_row("PRAGMA journal_mode=WAL", [], |_| Ok(())).map_err(backend)?;
+    conn.execute_batch("PRAGMA foreign_keys=ON; PRAGMA busy_timeout=5000;").map_err(backend)?;
+    match stored_version(conn)? {
+        None => {
+            create_tables(conn)?;
+            write_version(conn, SCHEMA_VERSION)
+        }
+        Some(SCHEMA_VERSION) => create_tables(conn),
+        Some(v) if v < SCHEMA_VERSION => Err(RepoError::Backend(format!(
+            "schema mismatch: repo_version {v} is older than {SCHEMA_VERSION}; run `rssify migrate` first"
+        ))),
+        Some(v) => Err(RepoError::Backend(format!(
+            "schema mismatch: repo_version {v} is newer than supported {SCHEMA_VERSION}"
+        ))),
+    }
+}
+
+fn create_tables(conn: &Connection) -> Result<(), RepoError> {
+    conn.execute_batch(DDL).map_err(backend)?;
+    conn.execute_batch(SEARCH_DDL).map_err(backend)
+}
+
+/// `meta.repo_version`, or `None` for a database without one (fresh or not an rssify repo).
+pub(crate) fn stored_version(conn: &Connection) -> Result<Option<u32>, RepoError> {
+    let has_meta: bool = conn
+        .query_row("SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'meta')", [], |r| r.get(0))
+        .map_err(backend)?;
+    if !has_meta {
+        return Ok(None);
+    }
+    let stored: Option<String> = conn
+        .query_row("SELECT value FROM meta WHERE key = 'repo_version'", [], |r| r.get(0))
+        .optional()
+        .map_err(backend)?;
+    stored
+        .map(|v| v.parse::<u32>().map_err(|_| RepoError::Backend(format!("schema mismatch: repo_version {v:?} is not a number"))))
+        .transpose()
+}
+
+pub(crate) fn write_version(conn: &Connection, version: u32) -> Result<(), RepoError> {
+    conn.execute(
+        "INSERT INTO meta(key, value) VALUES ('repo_version', ?1) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        [version.to_string()],
+    )
+    .map_err(backend)?;
+    Ok(())
+}
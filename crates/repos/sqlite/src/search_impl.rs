@@ -0,0 +1,95 @@
+/*
+File: crates/repos/sqlite/src/search_impl.rs
+Purpose: SearchRepo impl for SqliteRepo and the index upkeep entry writes call.
+Inputs: rssify_core::{IndexedDoc, SearchQuery, SearchRepo, rank}.
+Outputs: Rows in search_docs (one per entry) and search_postings (term, entry, positions as JSON).
+Side effects: SQLite I/O.
+Invariants: index_entry runs in the caller's transaction, so an entry and its postings commit
+            together. Deleting an entry (or its feed) cascades to its postings.
+            Corpus stats are aggregated from search_docs on every search.
+*/
+
+use crate::entry_impl::for_each_entry;
+use crate::repo::SqliteRepo;
+use crate::tx::SqliteTx;
+use crate::util::{backend, from_json, to_json};
+use rssify_core::{
+    rank, CorpusStats, Entry, EntryId, FeedId, IndexedDoc, Posting, PostingSource, RepoError, SearchHit, SearchQuery,
+    SearchRepo,
+};
+use rusqlite::{params, Connection};
+
+/// Replace whatever the index holds for `entry` with its current postings.
+pub(crate) fn index_entry(conn: &Connection, entry: &Entry) -> Result<(), RepoError> {
+    let doc = IndexedDoc::from_entry(entry);
+    // Postings go explicitly: migration connections run without foreign_keys, so no cascade.
+    conn.execute("DELETE FROM search_postings WHERE entry_id = ?1", [doc.entry.as_str()]).map_err(backend)?;
+    conn.execute("DELETE FROM search_docs WHERE entry_id = ?1", [doc.entry.as_str()]).map_err(backend)?;
+    conn.execute(
+        "INSERT INTO search_docs (entry_id, feed_id, published_ts, len) VALUES (?1, ?2, ?3, ?4)",
+        params![doc.entry.as_str(), doc.feed.as_str(), doc.published_ts, doc.len],
+    )
+    .map_err(backend)?;
+    let mut stmt = conn
+        .prepare_cached("INSERT INTO search_postings (term, entry_id, positions) VALUES (?1, ?2, ?3)")
+        .map_err(backend)?;
+    for (term, positions) in &doc.terms {
+        stmt.execute(params![term, doc.entry.as_str(), to_json(positions)?]).map_err(backend)?;
+    }
+    Ok(())
+}
+
+/// Drop the whole index and index every stored entry again; returns how many were indexed.
+pub(crate) fn rebuild(conn: &Connection) -> Result<usize, RepoError> {
+    conn.execute_batch("DELETE FROM search_postings; DELETE FROM search_docs;").map_err(backend)?;
+    let mut indexed = 0;
+    for_each_entry(conn, |entry| {
+        indexed += 1;
+        index_entry(conn, &entry)
+    })?;
+    Ok(indexed)
+}
+
+struct SqlPostings<'c>(&'c Connection);
+
+impl PostingSource for SqlPostings<'_> {
+    fn corpus(&self) -> Result<CorpusStats, RepoError> {
+        let (docs, total_len): (i64, i64) = self
+            .0
+            .query_row("SELECT COUNT(*), COALESCE(SUM(len), 0) FROM search_docs", [], |r| Ok((r.get(0)?, r.get(1)?)))
+            .map_err(backend)?;
+        Ok(CorpusStats { docs: docs.unsigned_abs(), total_len: total_len.unsigned_abs() })
+    }
+
+    fn postings(&self, term: &str) -> Result<Vec<Posting>, RepoError> {
+        let mut stmt = self
+            .0
+            .prepare_cached(
+                "SELECT p.entry_id, d.feed_id, d.published_ts, d.len, p.positions
+                 FROM search_postings p JOIN search_docs d ON d.entry_id = p.entry_id WHERE p.term = ?1",
+            )
+            .map_err(backend)?;
+        let rows = stmt
+            .query_map([term], |r| {
+                Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?, r.get(2)?, r.get(3)?, r.get::<_, String>(4)?))
+            })
+            .map_err(backend)?;
+        rows.map(|row| {
+            let (entry, feed, published_ts, len, positions) = row.map_err(backend)?;
+            Ok(Posting { entry: EntryId::new(entry), feed: FeedId::new(feed), published_ts, len, positions: from_json(&positions)? })
+        })
+        .collect()
+    }
+}
+
+impl SearchRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn search<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>, query: &SearchQuery, limit: usize) -> Result<Vec<SearchHit>, RepoError> {
+        rank(&SqlPostings(self.conn(tx)), query, limit)
+    }
+
+    fn reindex<'a>(&'a self, tx: Option<&'a Self::Tx<'a>>) -> Result<usize, RepoError> {
+        self.in_tx(tx, rebuild)
+    }
+}
@@ -0,0 +1,50 @@
+/*
+File: crates/repos/sqlite/src/tx.rs
+Purpose: Transaction handle for the SQLite adapter, backed by a real SQLite transaction.
+Inputs: rssify_core::Tx trait; rusqlite::Transaction.
+Outputs: SqliteTx implementing Tx, TxRepo for SqliteRepo.
+Side effects: Dropping an uncommitted SqliteTx rolls it back.
+*/
+
+use crate::repo::SqliteRepo;
+use crate::util::backend;
+use rssify_core::{RepoError, Tx as TxTrait, TxRepo};
+
+/// Open write transaction (BEGIN IMMEDIATE); pass it as `Some(&tx)` to repo calls.
+pub struct SqliteTx<'c> {
+    pub(crate) tx: rusqlite::Transaction<'c>,
+}
+
+impl SqliteTx<'_> {
+    /// Make every write done through this transaction durable.
+    pub fn commit(self) -> Result<(), RepoError> {
+        self.tx.commit().map_err(backend)
+    }
+
+    /// Discard every write done through this transaction (same as dropping it).
+    pub fn rollback(self) -> Result<(), RepoError> {
+        self.tx.rollback().map_err(backend)
+    }
+}
+
+impl TxTrait for SqliteTx<'_> {
+    fn is_active(&self) -> bool {
+        !self.tx.is_autocommit()
+    }
+}
+
+impl TxRepo for SqliteRepo {
+    type Tx<'a> = SqliteTx<'a> where Self: 'a;
+
+    fn begin_tx(&self) -> Result<SqliteTx<'_>, RepoError> {
+        SqliteRepo::begin_tx(self)
+    }
+
+    fn commit_tx(tx: SqliteTx<'_>) -> Result<(), RepoError> {
+        tx.commit()
+    }
+
+    fn reborrow<'s, 'r: 's>(tx: &'s SqliteTx<'r>) -> &'s SqliteTx<'s> {
+        tx
+    }
+}
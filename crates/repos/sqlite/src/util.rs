@@ -0,0 +1,40 @@
+/*
+File: crates/repos/sqlite/src/util.rs
+Purpose: Error mapping and JSON/enum column helpers shared by the trait impls.
+Inputs: rusqlite and serde_json errors; serde-serializable values.
+Outputs: RepoError values and column strings.
+Side effects: None.
+*/
+
+use rssify_core::RepoError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Map a SQLite error: missing rows are `NotFound`, constraint violations `Conflict`.
+pub(crate) fn backend(e: rusqlite::Error) -> RepoError {
+    match e {
+        rusqlite::Error::QueryReturnedNoRows => RepoError::NotFound,
+        rusqlite::Error::SqliteFailure(f, _) if f.code == rusqlite::ErrorCode::ConstraintViolation => RepoError::Conflict,
+        other => RepoError::Backend(other.to_string()),
+    }
+}
+
+pub(crate) fn to_json<T: ?Sized + Serialize>(value: &T) -> Result<String, RepoError> {
+    serde_json::to_string(value).map_err(|e| RepoError::Ser(e.to_string()))
+}
+
+pub(crate) fn from_json<T: DeserializeOwned>(s: &str) -> Result<T, RepoError> {
+    serde_json::from_str(s).map_err(|e| RepoError::Ser(e.to_string()))
+}
+
+/// Unit enums (ContentKind, FeedRunStatus) are stored as their serde name, e.g. `Xml`.
+pub(crate) fn enum_name<T: Serialize>(value: &T) -> Result<String, RepoError> {
+    match serde_json::to_value(value).map_err(|e| RepoError::Ser(e.to_string()))? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Err(RepoError::Ser(format!("not a unit enum: {other}"))),
+    }
+}
+
+pub(crate) fn enum_from<T: DeserializeOwned>(name: &str) -> Result<T, RepoError> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).map_err(|e| RepoError::Ser(e.to_string()))
+}
@@ -0,0 +1,138 @@
+/*
+File: crates/repos/sqlite/tests/roundtrip.rs
+Purpose: Round-trip tests for SqliteRepo: the shared conformance suite plus transaction,
+         referential-integrity, versioning and backup behavior.
+Inputs: SqliteRepo; rssify_core domain types and traits.
+Outputs: Asserts on RepoError results; creates unique temp databases.
+Side effects: SQLite files in a temp dir (not deleted).
+Invariants:
+ - No network.
+ - Each test uses a fresh directory name with pid+nanos to avoid collisions.
+*/
+
+#[macro_use]
+mod support;
+
+use rssify_core::{EntryId, EntryRepo, FeedId, FeedRepo, SearchQuery, SearchRepo};
+use rssify_repo_sqlite::{SqliteRepo, SCHEMA_VERSION};
+use support::{entry, feed, temp_root};
+
+conformance_suite!(SqliteRepo::open(temp_root().join("rssify.db")).expect("open db"));
+
+
+#[test]
+fn tx_commit_persists_and_drop_rolls_back() {
+    let root = temp_root();
+    let path = root.join("rssify.db");
+    let repo = SqliteRepo::open(&path).expect("open db");
+    let kept = feed("https://kept.test/rss");
+    let dropped = feed("https://dropped.test/rss");
+
+    let tx = repo.begin_tx().expect("begin tx");
+    assert!(rssify_core::Tx::is_active(&tx));
+    FeedRepo::put(&repo, Some(&tx), &kept).expect("put in tx");
+    EntryRepo::upsert(&repo, Some(&tx), &entry(&kept.id, "e1")).expect("upsert in tx");
+    tx.commit().expect("commit");
+
+    let tx = repo.begin_tx().expect("begin tx");
+    FeedRepo::put(&repo, Some(&tx), &dropped).expect("put in tx");
+    assert_eq!(FeedRepo::list(&repo, Some(&tx)).expect("list in tx").len(), 2, "tx sees its own writes");
+    drop(tx);
+
+    let reopened = SqliteRepo::open(&path).expect("reopen db");
+    let ids: Vec<FeedId> = FeedRepo::list(&reopened, None).expect("list").into_iter().map(|f| f.id).collect();
+    assert_eq!(ids, vec![kept.id.clone()]);
+    assert_eq!(EntryRepo::get(&reopened, None, &EntryId::new("e1")).expect("entry"), entry(&kept.id, "e1"));
+}
+
+#[test]
+fn entries_need_their_feed_and_survive_feed_updates() {
+    let root = temp_root();
+    let repo = SqliteRepo::open(root.join("rssify.db")).expect("open db");
+    let mut f = feed("https://ex.com/rss");
+    assert!(matches!(
+        EntryRepo::upsert(&repo, None, &entry(&f.id, "orphan")),
+        Err(rssify_core::RepoError::Conflict)
+    ));
+    assert!(matches!(FeedRepo::get(&repo, None, &f.id), Err(rssify_core::RepoError::NotFound)));
+
+    FeedRepo::put(&repo, None, &f).expect("put feed");
+    EntryRepo::upsert(&repo, None, &entry(&f.id, "e1")).expect("upsert");
+    f.etag = Some("\"v2\"".into());
+    f.settings.mirrors = vec!["https://mirror.ex.com/rss".into()];
+    FeedRepo::put(&repo, None, &f).expect("update feed");
+    assert_eq!(FeedRepo::get(&repo, None, &f.id).expect("get"), f);
+    assert_eq!(EntryRepo::list_by_feed(&repo, None, &f.id).expect("list").len(), 1, "update keeps entries");
+}
+
+#[test]
+fn newer_schema_versions_are_refused() {
+    let root = temp_root();
+    let path = root.join("rssify.db");
+    drop(SqliteRepo::open(&path).expect("create db"));
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute("UPDATE meta SET value = '99' WHERE key = 'repo_version'", []).unwrap();
+    drop(conn);
+    let err = SqliteRepo::open(&path).expect_err("newer schema");
+    assert!(err.to_string().contains("schema mismatch"), "{err}");
+}
+
+#[test]
+fn older_versions_need_migrate_and_backups_keep_the_data() {
+    let root = temp_root();
+    let path = root.join("rssify.db");
+    assert_eq!(rssify_repo_sqlite::repo_version(&path).unwrap(), None);
+    let repo = SqliteRepo::open(&path).expect("create db");
+    let f = feed("https://example.com/feed");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    drop(repo);
+    assert_eq!(rssify_repo_sqlite::repo_version(&path).unwrap(), Some(SCHEMA_VERSION));
+
+    let copy = root.join("backup/rssify.db");
+    rssify_repo_sqlite::backup(&path, &copy).expect("backup");
+    assert!(rssify_repo_sqlite::backup(&path, &copy).is_err(), "never overwrites a backup");
+    assert_eq!(FeedRepo::list(&SqliteRepo::open(&copy).unwrap(), None).unwrap(), vec![f]);
+
+    let report = rssify_repo_sqlite::migrate(&path).expect("current db");
+    assert_eq!((report.from, report.to, report.applied.len()), (SCHEMA_VERSION, SCHEMA_VERSION, 0));
+
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute("UPDATE meta SET value = '0' WHERE key = 'repo_version'", []).unwrap();
+    drop(conn);
+    let err = SqliteRepo::open(&path).expect_err("older schema");
+    assert!(err.to_string().contains("rssify migrate"), "{err}");
+    let err = rssify_repo_sqlite::migrate(&path).expect_err("no step from 0");
+    assert!(err.to_string().contains("no migration from version 0 to 1"), "{err}");
+}
+
+#[test]
+fn version_one_databases_gain_the_starred_column_and_search_index_on_migrate() {
+    let root = temp_root();
+    let path = root.join("rssify.db");
+    let repo = SqliteRepo::open(&path).expect("create db");
+    let f = feed("https://example.com/feed");
+    FeedRepo::put(&repo, None, &f).unwrap();
+    EntryRepo::upsert(&repo, None, &entry(&f.id, "e1")).unwrap();
+    drop(repo);
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute_batch(
+        "DROP TABLE search_postings; DROP TABLE search_docs; ALTER TABLE entries DROP COLUMN authors;
+         ALTER TABLE entries DROP COLUMN starred; UPDATE meta SET value = '1' WHERE key = 'repo_version';",
+    )
+    .unwrap();
+    drop(conn);
+    assert!(SqliteRepo::open(&path).is_err(), "version 1 needs migrate");
+
+    let report = rssify_repo_sqlite::migrate(&path).expect("migrate");
+    assert_eq!((report.from, report.to), (1, SCHEMA_VERSION));
+    assert_eq!(report.applied[0].name, "entries.starred");
+    assert_eq!(report.applied[1].note, "added entries.authors and the search index (1 entries indexed)");
+    let repo = SqliteRepo::open(&path).expect("open migrated db");
+    let hits = SearchRepo::search(&repo, None, &SearchQuery::parse("e1").unwrap(), 10).unwrap();
+    assert_eq!(hits.iter().map(|h| h.entry.as_str()).collect::<Vec<_>>(), ["e1"], "existing entries are indexed");
+    let mut e = EntryRepo::get(&repo, None, &EntryId::new("e1")).unwrap();
+    assert!(!e.starred);
+    e.starred = true;
+    EntryRepo::upsert(&repo, None, &e).unwrap();
+    assert!(EntryRepo::get(&repo, None, &e.id).unwrap().starred);
+}
@@ -0,0 +1,30 @@
+/*
+File: crates/repos/sqlite/tests/support.rs
+Purpose: Shared test helpers: the cross-backend conformance suite with its fixtures, and temp dirs.
+Inputs: crates/repos/tests/conformance.rs.
+Outputs: feed/entry/archived fixtures, temp_root, and the conformance_suite! macro.
+Side effects: temp_root creates a directory under the system temp dir (not deleted).
+Invariants:
+ - Each temp directory name uses pid+nanos to avoid collisions.
+*/
+
+#![allow(dead_code, unused_imports)]
+
+#[macro_use]
+#[path = "../../tests/conformance.rs"]
+pub mod conformance;
+
+pub use conformance::{archived, entry, feed};
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn temp_root() -> PathBuf {
+    let mut p = std::env::temp_dir();
+    let pid = std::process::id();
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().subsec_nanos();
+    p.push(format!("rssify-sqliterepo-{}-{}", pid, nanos));
+    fs::create_dir_all(&p).expect("create temp dir");
+    p
+}
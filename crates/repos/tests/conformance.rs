@@ -0,0 +1,367 @@
+/*
+File: crates/repos/tests/conformance.rs
+Purpose: Behavior every repo backend must share, written once against the core repo traits and
+         instantiated per backend with `conformance_suite!`; plus the feed/entry fixtures.
+Inputs: Any repo implementing FeedRepo, EntryRepo, ScheduleRepo, RunRepo, BlobRepo, ArchiveRepo, SearchRepo.
+Outputs: Asserts on RepoError results.
+Side effects: Whatever the backend under test does (temp dirs for fs and sqlite).
+Invariants:
+ - No network.
+ - Included by each repo crate's tests/support.rs; never built as a crate of its own.
+ - Writes go through `None` transactions, and feeds are stored before their entries (sqlite
+   enforces the reference).
+*/
+
+#![allow(dead_code, unused_macros)]
+
+use rssify_core::{
+    ArchiveRepo, ArchivedEntry, Attachment, AttachmentKind, BlobRepo, ContentBlob, ContentKind, Cursor, DeactivationReason, Entry, EntryId, EntryOrder, EntryQuery, EntryRepo, FailureStreak, Feed,
+    FeedId, FeedRepo, FeedRunStatus, LastBlob, MirrorHealth, RepoError, RetentionPolicy, RunManifest, RunRepo, ScheduleRepo,
+    SearchQuery, SearchRepo,
+};
+
+/// Emit one `#[test]` per conformance check, each against a fresh repo built by `$repo`.
+macro_rules! conformance_suite {
+    ($repo:expr) => {
+        conformance_suite!(@tests $repo;
+            feed_roundtrip_and_list,
+            entry_roundtrip_and_scan,
+            schedule_record_and_read,
+            failure_streak_record_and_read,
+            mirror_health_record_and_read,
+            run_checkpoints_apply_over_manifest,
+            deactivated_feed_roundtrip,
+            last_blob_roundtrip_keeps_raw_bytes,
+            blobs_are_content_addressed_and_archives_list_by_feed,
+            deleting_a_feed_cascades_to_its_entries_archives_and_schedule,
+            entries_delete_and_prune_never_touching_starred_ones,
+            query_pages_across_feeds_with_range_and_title_filters,
+            search_follows_upserts_deletes_and_reindex
+        );
+    };
+    (@tests $repo:expr; $($name:ident),*) => {
+        $(
+            #[test]
+            fn $name() {
+                $crate::support::conformance::$name(&$repo);
+            }
+        )*
+    };
+}
+
+pub fn feed(url: &str) -> Feed {
+    Feed {
+        id: FeedId::from_url(url),
+        url: url.into(),
+        title: None,
+        site_url: None,
+        etag: None,
+        last_modified: None,
+        active: true,
+        deactivated: None,
+        settings: Default::default(),
+    }
+}
+
+pub fn entry(feed: &FeedId, id: &str) -> Entry {
+    Entry {
+        id: EntryId::new(id),
+        feed: feed.clone(),
+        url: None,
+        title: Some(id.into()),
+        published_ts: None,
+        updated_ts: None,
+        summary: None,
+        content: None,
+        content_text: None,
+        enclosures: Vec::new(),
+        warc_records: Vec::new(),
+        starred: false,
+        authors: Vec::new(),
+    }
+}
+
+pub fn archived(entry: &Entry) -> ArchivedEntry {
+    ArchivedEntry { entry: entry.id.clone(), feed: entry.feed.clone(), archived_ts: 1, content: None, attachments: Vec::new(), skipped: Vec::new() }
+}
+
+pub fn feed_roundtrip_and_list<R: FeedRepo>(repo: &R) {
+    let f1 = Feed {
+        title: Some("Example".into()),
+        site_url: Some("https://example.com".into()),
+        ..feed("https://example.com/feed")
+    };
+    let f2 = Feed { etag: Some("W/123".into()), ..feed("https://blog.test/rss") };
+    FeedRepo::put(repo, None, &f1).expect("put f1");
+    FeedRepo::put(repo, None, &f2).expect("put f2");
+
+    let got = FeedRepo::get(repo, None, &f1.id).expect("get f1");
+    assert_eq!(got, f1);
+
+    let list = FeedRepo::list(repo, None).expect("list feeds");
+    assert_eq!(list.len(), 2);
+}
+
+pub fn entry_roundtrip_and_scan<R: FeedRepo + EntryRepo>(repo: &R) {
+    let feed = feed("https://ex.com/rss");
+    FeedRepo::put(repo, None, &feed).expect("put feed");
+
+    let e1 = Entry {
+        id: EntryId::from_parts(&feed.id, Some("guid-1"), None, Some("A"), Some(10)),
+        url: Some("https://ex.com/a".into()),
+        title: Some("A".into()),
+        published_ts: Some(10),
+        content: Some(ContentBlob { kind: ContentKind::Xml, bytes: b"<xml/>".to_vec() }),
+        warc_records: vec!["<urn:uuid:00000000-0000-4000-8000-000000000000>".into()],
+        ..entry(&feed.id, "")
+    };
+    let e2 = Entry {
+        id: EntryId::from_parts(&feed.id, None, Some("https://ex.com/b"), Some("B"), Some(20)),
+        url: Some("https://ex.com/b".into()),
+        title: Some("B".into()),
+        published_ts: Some(20),
+        ..entry(&feed.id, "")
+    };
+
+    EntryRepo::upsert(repo, None, &e1).expect("upsert e1");
+    EntryRepo::upsert(repo, None, &e1).expect("upsert e1 again (idempotent)");
+    EntryRepo::upsert(repo, None, &e2).expect("upsert e2");
+
+    let got = EntryRepo::get(repo, None, &e1.id).expect("get e1");
+    assert_eq!(got, e1);
+
+    let list = EntryRepo::list_by_feed(repo, None, &feed.id).expect("list entries by feed");
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[0].id, e1.id);
+    assert_eq!(list[1].id, e2.id);
+    assert_eq!(EntryRepo::count_by_feed(repo, None, &feed.id).expect("count entries by feed"), 2);
+    assert_eq!(EntryRepo::count_by_feed(repo, None, &FeedId::from_url("https://none.test/rss")).unwrap(), 0);
+}
+
+pub fn schedule_record_and_read<R: ScheduleRepo>(repo: &R) {
+    let feed = FeedId::from_url("https://ex.com/rss");
+    assert_eq!(ScheduleRepo::last_ok_fetch_ts(repo, None, &feed).expect("none yet"), None);
+    ScheduleRepo::record_fetch_ts(repo, None, &feed, 12345).expect("record ts");
+    assert_eq!(ScheduleRepo::last_ok_fetch_ts(repo, None, &feed).expect("read ts"), Some(12345));
+}
+
+pub fn failure_streak_record_and_read<R: ScheduleRepo>(repo: &R) {
+    let feed = FeedId::from_url("https://ex.com/rss");
+    assert_eq!(ScheduleRepo::failure_streak(repo, None, &feed).expect("default streak"), FailureStreak::default());
+    let streak = FailureStreak { permanent: 2, since_ts: Some(99) };
+    ScheduleRepo::record_failure_streak(repo, None, &feed, &streak).expect("record streak");
+    assert_eq!(ScheduleRepo::failure_streak(repo, None, &feed).expect("read streak"), streak);
+}
+
+pub fn mirror_health_record_and_read<R: ScheduleRepo>(repo: &R) {
+    let feed = FeedId::from_url("https://ex.com/rss");
+    assert_eq!(ScheduleRepo::mirror_health(repo, None, &feed).expect("default health"), MirrorHealth::default());
+    let mut health = MirrorHealth::default();
+    health.record("https://mirror.ex.com/rss", true, 120, 7);
+    ScheduleRepo::record_mirror_health(repo, None, &feed, &health).expect("record health");
+    assert_eq!(ScheduleRepo::mirror_health(repo, None, &feed).expect("read health"), health);
+}
+
+pub fn run_checkpoints_apply_over_manifest<R: RunRepo>(repo: &R) {
+    assert!(RunRepo::latest_incomplete_run(repo, None).expect("no runs").is_none());
+
+    let ids = ["url:a".to_string(), "url:b".to_string(), "url:c".to_string()];
+    let mut old = RunManifest::new("100-1", 100, &ids);
+    old.finish(150);
+    RunRepo::put_run(repo, None, &old).expect("put finished run");
+    let run = RunManifest::new("200-1", 200, &ids);
+    RunRepo::put_run(repo, None, &run).expect("put run");
+    RunRepo::record_run_feed(repo, None, "200-1", "url:a", FeedRunStatus::Done).expect("checkpoint a");
+    RunRepo::record_run_feed(repo, None, "200-1", "url:b", FeedRunStatus::Failed).expect("checkpoint b");
+    let got = RunRepo::latest_incomplete_run(repo, None).expect("read").expect("incomplete run");
+    assert_eq!(got.run_id, "200-1");
+    assert_eq!(got.pending(), ["url:c"]);
+    assert_eq!(got.feeds[1].status, FeedRunStatus::Failed);
+    assert!(matches!(RunRepo::get_run(repo, None, "nope"), Err(RepoError::NotFound)));
+}
+
+pub fn deactivated_feed_roundtrip<R: FeedRepo>(repo: &R) {
+    let mut feed = feed("https://gone.test/rss");
+    feed.deactivate(DeactivationReason::PermanentFailures { count: 5 }, 1_700_000_000);
+    FeedRepo::put(repo, None, &feed).expect("put feed");
+
+    let got = FeedRepo::get(repo, None, &feed.id).expect("get feed");
+    assert!(!got.active);
+    assert_eq!(got.deactivated, feed.deactivated);
+}
+
+pub fn last_blob_roundtrip_keeps_raw_bytes<R: FeedRepo>(repo: &R) {
+    let feed = FeedId::from_url("https://ex.com/rss");
+    assert_eq!(FeedRepo::last_blob(repo, None, &feed).expect("none yet"), None);
+
+    let blob = LastBlob {
+        blob: ContentBlob { kind: ContentKind::Xml, bytes: b"<rss>\xff</rss>".to_vec() },
+        headers: vec![("ETag".into(), "\"v1\"".into())],
+        fetched_ts: 1_700_000_000,
+        warc_record: Some("<urn:uuid:00000000-0000-4000-8000-000000000000>".into()),
+    };
+    FeedRepo::put_last_blob(repo, None, &feed, &blob).expect("put blob");
+    assert_eq!(FeedRepo::last_blob(repo, None, &feed).expect("read blob"), Some(blob));
+}
+
+pub fn blobs_are_content_addressed_and_archives_list_by_feed<R: BlobRepo + ArchiveRepo>(repo: &R) {
+    let sha = BlobRepo::put_blob(repo, None, b"abc").unwrap();
+    assert_eq!(sha, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_eq!(BlobRepo::put_blob(repo, None, b"abc").unwrap(), sha, "same bytes, same blob");
+    assert_eq!(BlobRepo::get_blob(repo, None, &sha).unwrap().as_deref(), Some(&b"abc"[..]));
+    assert_eq!(BlobRepo::get_blob(repo, None, "../../etc").unwrap(), None);
+
+    let feed = FeedId::new("f");
+    let archived = ArchivedEntry {
+        entry: EntryId::new("e/1"),
+        feed: feed.clone(),
+        archived_ts: 7,
+        content: Some(ContentBlob { kind: ContentKind::Html, bytes: b"<img src=\"x\">".to_vec() }),
+        attachments: vec![Attachment {
+            url: "https://a.test/x.png".into(),
+            kind: AttachmentKind::Image,
+            mime: "image/png".into(),
+            size: 3,
+            sha256: sha,
+            warc_record: None,
+        }],
+        skipped: Vec::new(),
+    };
+    ArchiveRepo::put_archive(repo, None, &archived).unwrap();
+    assert_eq!(ArchiveRepo::archive(repo, None, &archived.entry).unwrap(), Some(archived.clone()));
+    assert_eq!(ArchiveRepo::archive(repo, None, &EntryId::new("other")).unwrap(), None);
+    assert_eq!(ArchiveRepo::archives_by_feed(repo, None, &feed).unwrap(), vec![archived.clone()]);
+
+    let orphan = BlobRepo::put_blob(repo, None, b"orphan").unwrap();
+    assert_eq!(BlobRepo::gc_blobs(repo, None).unwrap(), 1, "the attachment blob stays");
+    assert_eq!(BlobRepo::get_blob(repo, None, &orphan).unwrap(), None);
+    assert!(BlobRepo::get_blob(repo, None, &archived.attachments[0].sha256).unwrap().is_some());
+}
+
+pub fn deleting_a_feed_cascades_to_its_entries_archives_and_schedule<R>(repo: &R)
+where
+    R: FeedRepo + EntryRepo + ScheduleRepo + ArchiveRepo,
+{
+    let (f, g) = (feed("https://a.test/rss"), feed("https://b.test/rss"));
+    let (e1, e2, e3) = (entry(&f.id, "a1"), entry(&f.id, "a2"), entry(&g.id, "b1"));
+    for x in [&f, &g] {
+        FeedRepo::put(repo, None, x).unwrap();
+    }
+    for e in [&e1, &e2, &e3] {
+        EntryRepo::upsert(repo, None, e).unwrap();
+        ArchiveRepo::put_archive(repo, None, &archived(e)).unwrap();
+    }
+    let blob = LastBlob { blob: ContentBlob { kind: ContentKind::Xml, bytes: b"<rss/>".to_vec() }, headers: Vec::new(), fetched_ts: 1, warc_record: None };
+    FeedRepo::put_last_blob(repo, None, &f.id, &blob).unwrap();
+    ScheduleRepo::record_failure_streak(repo, None, &f.id, &FailureStreak { permanent: 1, since_ts: Some(2) }).unwrap();
+
+    FeedRepo::delete(repo, None, &f.id).expect("delete feed");
+    assert!(FeedRepo::get(repo, None, &f.id).is_err());
+    assert_eq!(FeedRepo::list(repo, None).unwrap(), vec![g.clone()]);
+    assert!(EntryRepo::list_by_feed(repo, None, &f.id).unwrap().is_empty());
+    assert_eq!(EntryRepo::count_by_feed(repo, None, &f.id).unwrap(), 0);
+    assert!(EntryRepo::get(repo, None, &e1.id).is_err());
+    assert_eq!(FeedRepo::last_blob(repo, None, &f.id).unwrap(), None);
+    assert_eq!(ScheduleRepo::failure_streak(repo, None, &f.id).unwrap(), FailureStreak::default());
+    assert_eq!(ArchiveRepo::archive(repo, None, &e2.id).unwrap(), None);
+    assert!(ArchiveRepo::archives_by_feed(repo, None, &f.id).unwrap().is_empty());
+    assert_eq!(EntryRepo::list_by_feed(repo, None, &g.id).unwrap(), vec![e3.clone()], "other feeds untouched");
+    assert_eq!(ArchiveRepo::archive(repo, None, &e3.id).unwrap(), Some(archived(&e3)));
+    assert!(matches!(FeedRepo::delete(repo, None, &f.id), Err(RepoError::NotFound)));
+}
+
+pub fn entries_delete_and_prune_never_touching_starred_ones<R: FeedRepo + EntryRepo + ArchiveRepo>(repo: &R) {
+    let f = feed("https://a.test/rss");
+    FeedRepo::put(repo, None, &f).unwrap();
+    let day = 86_400;
+    let now = 10 * day;
+    let entries: Vec<Entry> = (1..=5)
+        .map(|i| Entry { published_ts: Some(i * day), starred: i == 1, ..entry(&f.id, &format!("p{i}")) })
+        .collect();
+    for e in &entries {
+        EntryRepo::upsert(repo, None, e).unwrap();
+    }
+    ArchiveRepo::put_archive(repo, None, &archived(&entries[1])).unwrap();
+    assert!(EntryRepo::get(repo, None, &entries[0].id).unwrap().starred);
+
+    EntryRepo::delete(repo, None, &entries[4].id).expect("delete entry");
+    assert!(EntryRepo::get(repo, None, &entries[4].id).is_err());
+    assert!(matches!(EntryRepo::delete(repo, None, &entries[4].id), Err(RepoError::NotFound)));
+
+    let unbounded = EntryRepo::prune(repo, None, &f.id, &RetentionPolicy::default(), now).unwrap();
+    assert!(unbounded.is_empty());
+    let policy = RetentionPolicy { keep_last: Some(2), max_age_days: None };
+    let pruned = EntryRepo::prune(repo, None, &f.id, &policy, now).unwrap();
+    assert_eq!(pruned, vec![entries[1].id.clone()]);
+    assert_eq!(ArchiveRepo::archive(repo, None, &entries[1].id).unwrap(), None);
+    let left: Vec<_> = EntryRepo::list_by_feed(repo, None, &f.id).unwrap().into_iter().map(|e| e.id).collect();
+    assert_eq!(left, vec![entries[0].id.clone(), entries[2].id.clone(), entries[3].id.clone()]);
+}
+
+pub fn query_pages_across_feeds_with_range_and_title_filters<R: FeedRepo + EntryRepo>(repo: &R) {
+    let (a, b) = (feed("https://a.test/rss"), feed("https://b.test/rss"));
+    for (f, tag) in [(&a, "a"), (&b, "b")] {
+        FeedRepo::put(repo, None, f).unwrap();
+        for i in 1..=3 {
+            let title = if i == 2 { format!("Rust news {tag}") } else { format!("Notes {tag}{i}") };
+            let e = Entry { published_ts: Some(i * 100), title: Some(title), ..entry(&f.id, &format!("{tag}{i}")) };
+            EntryRepo::upsert(repo, None, &e).unwrap();
+        }
+    }
+    EntryRepo::upsert(repo, None, &Entry { title: None, ..entry(&b.id, "b0") }).unwrap();
+
+    let mut q = EntryQuery { limit: 3, ..EntryQuery::default() };
+    let mut pages = Vec::new();
+    loop {
+        let page = EntryRepo::query(repo, None, &q).unwrap();
+        pages.push(page.entries.iter().map(|e| e.id.as_str().to_string()).collect::<Vec<_>>());
+        match page.next {
+            Some(c) => q.cursor = Some(Cursor::decode(&c.encode()).unwrap()),
+            None => break,
+        }
+    }
+    assert_eq!(pages, [vec!["b3", "a3", "b2"], vec!["a2", "b1", "a1"], vec!["b0"]]);
+
+    let filtered = EntryQuery {
+        feeds: vec![a.id.clone()],
+        published_from: Some(200),
+        title_contains: Some("RUST".into()),
+        order: EntryOrder::OldestFirst,
+        ..EntryQuery::default()
+    };
+    let page = EntryRepo::query(repo, None, &filtered).unwrap();
+    assert_eq!(page.entries.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(), ["a2"]);
+    assert!(page.next.is_none());
+    let zero = EntryQuery { limit: 0, ..EntryQuery::default() };
+    assert!(matches!(EntryRepo::query(repo, None, &zero), Err(RepoError::InvalidQuery(_))));
+}
+
+pub fn search_follows_upserts_deletes_and_reindex<R: FeedRepo + EntryRepo + SearchRepo>(repo: &R) {
+    let (a, b) = (feed("https://a.test/rss"), feed("https://b.test/rss"));
+    FeedRepo::put(repo, None, &a).unwrap();
+    FeedRepo::put(repo, None, &b).unwrap();
+    let doc = |f: &Feed, id: &str, ts: i64, title: &str| Entry { published_ts: Some(ts), title: Some(title.into()), ..entry(&f.id, id) };
+    let a1 = Entry { authors: vec!["Jane Doe".into()], ..doc(&a, "a1", 100, "Rust async runtimes") };
+    let a2 = Entry { summary: Some("<p>rust rust</p>".into()), ..doc(&a, "a2", 200, "Weekly notes") };
+    for e in [&a1, &a2, &doc(&b, "b1", 300, "Gardening")] {
+        EntryRepo::upsert(repo, None, e).unwrap();
+    }
+    let ids = |q: &str| -> Vec<String> {
+        let hits = SearchRepo::search(repo, None, &SearchQuery::parse(q).unwrap(), 10).unwrap();
+        hits.into_iter().map(|h| h.entry.as_str().to_string()).collect()
+    };
+    assert_eq!(ids("rust"), ["a2", "a1"]);
+    assert_eq!(ids("rust author:jane"), ["a1"]);
+    assert_eq!(ids(r#""async runtimes" after:50"#), ["a1"]);
+    assert_eq!(ids(&format!("rust feed:{}", b.url)), Vec::<String>::new());
+
+    EntryRepo::upsert(repo, None, &Entry { title: Some("Gardening tips".into()), ..a1.clone() }).unwrap();
+    assert_eq!(ids("rust"), ["a2"], "upsert replaces the old postings");
+    assert_eq!(ids("gardening"), ["b1", "a1"]);
+    EntryRepo::delete(repo, None, &EntryId::new("b1")).unwrap();
+    assert_eq!(ids("gardening"), ["a1"]);
+    assert_eq!(SearchRepo::reindex(repo, None).unwrap(), 2);
+    assert_eq!((ids("gardening"), ids("rust")), (vec!["a1".to_string()], vec!["a2".to_string()]));
+    FeedRepo::delete(repo, None, &a.id).unwrap();
+    assert_eq!(ids("gardening"), Vec::<String>::new(), "deleting a feed drops its entries' postings");
+}